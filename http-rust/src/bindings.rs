@@ -12,17 +12,82 @@ pub mod fermyon {
             static __FORCE_SECTION_REF: fn() =
                 super::super::super::__link_custom_section_describing_imports;
             use super::super::super::_rt;
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, PartialEq)]
+            pub enum TemplateErrorKind {
+                Parse,
+                Render,
+                UndefinedVariables,
+            }
+            impl ::core::fmt::Debug for TemplateErrorKind {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        TemplateErrorKind::Parse => {
+                            f.debug_tuple("TemplateErrorKind::Parse").finish()
+                        }
+                        TemplateErrorKind::Render => {
+                            f.debug_tuple("TemplateErrorKind::Render").finish()
+                        }
+                        TemplateErrorKind::UndefinedVariables => f
+                            .debug_tuple("TemplateErrorKind::UndefinedVariables")
+                            .finish(),
+                    }
+                }
+            }
+
+            impl TemplateErrorKind {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> TemplateErrorKind {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+
+                    match val {
+                        0 => TemplateErrorKind::Parse,
+                        1 => TemplateErrorKind::Render,
+                        2 => TemplateErrorKind::UndefinedVariables,
+
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+
+            /// Where possible the host says where in the template things went wrong.  `source-file`
+            /// is the content file path, and is not known for strings passed to `evaluate-template`.
+            #[derive(Clone)]
+            pub struct TemplateError {
+                pub kind: TemplateErrorKind,
+                pub message: _rt::String,
+                pub source_file: Option<_rt::String>,
+                pub line: Option<u32>,
+                pub column: Option<u32>,
+                pub snippet: Option<_rt::String>,
+            }
+            impl ::core::fmt::Debug for TemplateError {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("TemplateError")
+                        .field("kind", &self.kind)
+                        .field("message", &self.message)
+                        .field("source-file", &self.source_file)
+                        .field("line", &self.line)
+                        .field("column", &self.column)
+                        .field("snippet", &self.snippet)
+                        .finish()
+                }
+            }
             #[derive(Clone)]
             pub enum Error {
                 Cancel,
                 /// I know it's not truly an error but work with me here
                 Other(_rt::String),
+                Template(TemplateError),
             }
             impl ::core::fmt::Debug for Error {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                     match self {
                         Error::Cancel => f.debug_tuple("Error::Cancel").finish(),
                         Error::Other(e) => f.debug_tuple("Error::Other").field(e).finish(),
+                        Error::Template(e) => f.debug_tuple("Error::Template").field(e).finish(),
                     }
                 }
             }
@@ -96,7 +161,7 @@ pub mod fermyon {
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[resource-drop]execution-context"]
                             fn drop(_: u32);
@@ -119,7 +184,7 @@ pub mod fermyon {
                         let len1 = vec1.len();
 
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]execution-context.set-variable"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8, _: usize);
@@ -144,14 +209,14 @@ pub mod fermyon {
                 pub fn evaluate_template(&self, template: &str) -> Result<_rt::String, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let vec0 = template;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]execution-context.evaluate-template"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8);
@@ -178,11 +243,10 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l6 = i32::from(*ptr1.add(4).cast::<u8>());
-                                    let v10 = match l6 {
+                                    let v26 = match l6 {
                                         0 => Error::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e10 = {
+                                        1 => {
+                                            let e26 = {
                                                 let l7 = *ptr1.add(8).cast::<*mut u8>();
                                                 let l8 = *ptr1.add(12).cast::<usize>();
                                                 let len9 = l8;
@@ -191,11 +255,105 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes9)
                                             };
-                                            Error::Other(e10)
+                                            Error::Other(e26)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e26 = {
+                                                let l10 = i32::from(*ptr1.add(8).cast::<u8>());
+                                                let l11 = *ptr1.add(12).cast::<*mut u8>();
+                                                let l12 = *ptr1.add(16).cast::<usize>();
+                                                let len13 = l12;
+                                                let bytes13 = _rt::Vec::from_raw_parts(
+                                                    l11.cast(),
+                                                    len13,
+                                                    len13,
+                                                );
+                                                let l14 = i32::from(*ptr1.add(20).cast::<u8>());
+                                                let l18 = i32::from(*ptr1.add(32).cast::<u8>());
+                                                let l20 = i32::from(*ptr1.add(40).cast::<u8>());
+                                                let l22 = i32::from(*ptr1.add(48).cast::<u8>());
+
+                                                TemplateError {
+                                                    kind: TemplateErrorKind::_lift(l10 as u8),
+                                                    message: _rt::string_lift(bytes13),
+                                                    source_file: match l14 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l15 =
+                                                                    *ptr1.add(24).cast::<*mut u8>();
+                                                                let l16 =
+                                                                    *ptr1.add(28).cast::<usize>();
+                                                                let len17 = l16;
+                                                                let bytes17 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l15.cast(),
+                                                                        len17,
+                                                                        len17,
+                                                                    );
+
+                                                                _rt::string_lift(bytes17)
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    line: match l18 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l19 =
+                                                                    *ptr1.add(36).cast::<i32>();
+
+                                                                l19 as u32
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    column: match l20 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l21 =
+                                                                    *ptr1.add(44).cast::<i32>();
+
+                                                                l21 as u32
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    snippet: match l22 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l23 =
+                                                                    *ptr1.add(52).cast::<*mut u8>();
+                                                                let l24 =
+                                                                    *ptr1.add(56).cast::<usize>();
+                                                                let len25 = l24;
+                                                                let bytes25 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l23.cast(),
+                                                                        len25,
+                                                                        len25,
+                                                                    );
+
+                                                                _rt::string_lift(bytes25)
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                }
+                                            };
+                                            Error::Template(e26)
                                         }
                                     };
 
-                                    v10
+                                    v26
                                 };
                                 Err(e)
                             }
@@ -204,6 +362,50 @@ pub mod fermyon {
                     }
                 }
             }
+            impl ExecutionContext {
+                #[allow(unused_unsafe, clippy::all)]
+                /// A child context sees the parent's variables (including ones set on the parent
+                /// later) but variables set on the child don't affect the parent or its siblings.
+                pub fn fork(&self) -> ExecutionContext {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
+                        extern "C" {
+                            #[link_name = "[method]execution-context.fork"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ExecutionContext::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl ExecutionContext {
+                #[allow(unused_unsafe, clippy::all)]
+                /// A context with a copy of the variables as they are now.  Binding an action to a snapshot
+                /// means it renders with these values even if the guest goes on to change them.
+                pub fn snapshot(&self) -> ExecutionContext {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
+                        extern "C" {
+                            #[link_name = "[method]execution-context.snapshot"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ExecutionContext::from_handle(ret as u32)
+                    }
+                }
+            }
         }
 
         #[allow(dead_code, clippy::all)]
@@ -249,7 +451,7 @@ pub mod fermyon {
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[resource-drop]file"]
                             fn drop(_: u32);
@@ -281,7 +483,7 @@ pub mod fermyon {
                     };
                     let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "prompt"]
                         fn wit_import(
@@ -323,7 +525,7 @@ pub mod fermyon {
                         None => (0i32, 0i32),
                     };
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "confirm"]
                         fn wit_import(_: *mut u8, _: usize, _: i32, _: i32) -> i32;
@@ -372,7 +574,7 @@ pub mod fermyon {
                         None => (0i32, 0i32),
                     };
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "select"]
                         fn wit_import(
@@ -413,7 +615,7 @@ pub mod fermyon {
                         let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[static]file.list-all"]
                             fn wit_import(_: *mut u8);
@@ -452,7 +654,7 @@ pub mod fermyon {
                         let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.path"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -476,11 +678,11 @@ pub mod fermyon {
                 pub fn read(&self) -> Result<_rt::String, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.read"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -507,12 +709,11 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l5 = i32::from(*ptr0.add(4).cast::<u8>());
-                                    use super::super::super::fermyon::spin_template::types::Error as V9;
-                                    let v9 = match l5 {
-                                        0 => V9::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e9 = {
+                                    use super::super::super::fermyon::spin_template::types::Error as V25;
+                                    let v25 = match l5 {
+                                        0 => V25::Cancel,
+                                        1 => {
+                                            let e25 = {
                                                 let l6 = *ptr0.add(8).cast::<*mut u8>();
                                                 let l7 = *ptr0.add(12).cast::<usize>();
                                                 let len8 = l7;
@@ -521,11 +722,89 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes8)
                                             };
-                                            V9::Other(e9)
+                                            V25::Other(e25)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e25 = {
+                                                let l9 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let l10 = *ptr0.add(12).cast::<*mut u8>();
+                                                let l11 = *ptr0.add(16).cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                let l13 = i32::from(*ptr0.add(20).cast::<u8>());
+                                                let l17 = i32::from(*ptr0.add(32).cast::<u8>());
+                                                let l19 = i32::from(*ptr0.add(40).cast::<u8>());
+                                                let l21 = i32::from(*ptr0.add(48).cast::<u8>());
+
+                                                super::super::super::fermyon::spin_template::types::TemplateError{
+                          kind: super::super::super::fermyon::spin_template::types::TemplateErrorKind::_lift(l9 as u8),
+                          message: _rt::string_lift(bytes12),
+                          source_file: match l13 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l14 = *ptr0.add(24).cast::<*mut u8>();
+                                let l15 = *ptr0.add(28).cast::<usize>();
+                                let len16 = l15;
+                                let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+
+                                _rt::string_lift(bytes16)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          line: match l17 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l18 = *ptr0.add(36).cast::<i32>();
+
+                                l18 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          column: match l19 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l20 = *ptr0.add(44).cast::<i32>();
+
+                                l20 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          snippet: match l21 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l22 = *ptr0.add(52).cast::<*mut u8>();
+                                let l23 = *ptr0.add(56).cast::<usize>();
+                                let len24 = l23;
+                                let bytes24 = _rt::Vec::from_raw_parts(l22.cast(), len24, len24);
+
+                                _rt::string_lift(bytes24)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                        }
+                                            };
+                                            V25::Template(e25)
                                         }
                                     };
 
-                                    v9
+                                    v25
                                 };
                                 Err(e)
                             }
@@ -539,11 +818,11 @@ pub mod fermyon {
                 pub fn read_binary(&self) -> Result<_rt::Vec<u8>, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.read-binary"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -569,12 +848,11 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l5 = i32::from(*ptr0.add(4).cast::<u8>());
-                                    use super::super::super::fermyon::spin_template::types::Error as V9;
-                                    let v9 = match l5 {
-                                        0 => V9::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e9 = {
+                                    use super::super::super::fermyon::spin_template::types::Error as V25;
+                                    let v25 = match l5 {
+                                        0 => V25::Cancel,
+                                        1 => {
+                                            let e25 = {
                                                 let l6 = *ptr0.add(8).cast::<*mut u8>();
                                                 let l7 = *ptr0.add(12).cast::<usize>();
                                                 let len8 = l7;
@@ -583,11 +861,89 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes8)
                                             };
-                                            V9::Other(e9)
+                                            V25::Other(e25)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e25 = {
+                                                let l9 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let l10 = *ptr0.add(12).cast::<*mut u8>();
+                                                let l11 = *ptr0.add(16).cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                let l13 = i32::from(*ptr0.add(20).cast::<u8>());
+                                                let l17 = i32::from(*ptr0.add(32).cast::<u8>());
+                                                let l19 = i32::from(*ptr0.add(40).cast::<u8>());
+                                                let l21 = i32::from(*ptr0.add(48).cast::<u8>());
+
+                                                super::super::super::fermyon::spin_template::types::TemplateError{
+                          kind: super::super::super::fermyon::spin_template::types::TemplateErrorKind::_lift(l9 as u8),
+                          message: _rt::string_lift(bytes12),
+                          source_file: match l13 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l14 = *ptr0.add(24).cast::<*mut u8>();
+                                let l15 = *ptr0.add(28).cast::<usize>();
+                                let len16 = l15;
+                                let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+
+                                _rt::string_lift(bytes16)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          line: match l17 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l18 = *ptr0.add(36).cast::<i32>();
+
+                                l18 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          column: match l19 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l20 = *ptr0.add(44).cast::<i32>();
+
+                                l20 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          snippet: match l21 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l22 = *ptr0.add(52).cast::<*mut u8>();
+                                let l23 = *ptr0.add(56).cast::<usize>();
+                                let len24 = l23;
+                                let bytes24 = _rt::Vec::from_raw_parts(l22.cast(), len24, len24);
+
+                                _rt::string_lift(bytes24)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                        }
+                                            };
+                                            V25::Template(e25)
                                         }
                                     };
 
-                                    v9
+                                    v25
                                 };
                                 Err(e)
                             }
@@ -749,7 +1105,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-drop]edit"]
@@ -763,7 +1119,10 @@ pub mod exports {
 
                 pub enum Action {
                     CopyFileSubstituted(_rt::String),
+                    CopyFileSubstitutedIn((_rt::String, ExecutionContext)),
                     CopyFileToSubstituted((_rt::String, _rt::String)),
+                    CopyFileToSubstitutedIn((_rt::String, _rt::String, ExecutionContext)),
+                    /// e.g. rendering the same file for each of several components
                     CopyFileToRaw((_rt::String, _rt::String)),
                     WriteFile((_rt::String, _rt::String)),
                     WriteFileBinary((_rt::String, _rt::Vec<u8>)),
@@ -771,6 +1130,7 @@ pub mod exports {
                     /// normally auto but in case you need an empty directory a la fileserver
                     /// edit-file(tuple<string, func(existing: string) -> string>), // no! no! no!
                     EditFile((_rt::String, Edit)),
+                    EditFileIn((_rt::String, Edit, ExecutionContext)),
                 }
                 impl ::core::fmt::Debug for Action {
                     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
@@ -779,10 +1139,18 @@ pub mod exports {
                                 .debug_tuple("Action::CopyFileSubstituted")
                                 .field(e)
                                 .finish(),
+                            Action::CopyFileSubstitutedIn(e) => f
+                                .debug_tuple("Action::CopyFileSubstitutedIn")
+                                .field(e)
+                                .finish(),
                             Action::CopyFileToSubstituted(e) => f
                                 .debug_tuple("Action::CopyFileToSubstituted")
                                 .field(e)
                                 .finish(),
+                            Action::CopyFileToSubstitutedIn(e) => f
+                                .debug_tuple("Action::CopyFileToSubstitutedIn")
+                                .field(e)
+                                .finish(),
                             Action::CopyFileToRaw(e) => {
                                 f.debug_tuple("Action::CopyFileToRaw").field(e).finish()
                             }
@@ -798,6 +1166,9 @@ pub mod exports {
                             Action::EditFile(e) => {
                                 f.debug_tuple("Action::EditFile").field(e).finish()
                             }
+                            Action::EditFileIn(e) => {
+                                f.debug_tuple("Action::EditFileIn").field(e).finish()
+                            }
                         }
                     }
                 }
@@ -835,12 +1206,12 @@ pub mod exports {
                         }
                         Err(e) => {
                             *ptr3.add(0).cast::<u8>() = (1i32) as u8;
-                            use super::super::super::super::fermyon::spin_template::types::Error as V6;
+                            use super::super::super::super::fermyon::spin_template::types::Error as V10;
                             match e {
-                                V6::Cancel => {
+                                V10::Cancel => {
                                     *ptr3.add(4).cast::<u8>() = (0i32) as u8;
                                 }
-                                V6::Other(e) => {
+                                V10::Other(e) => {
                                     *ptr3.add(4).cast::<u8>() = (1i32) as u8;
                                     let vec5 = (e.into_bytes()).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
@@ -849,6 +1220,63 @@ pub mod exports {
                                     *ptr3.add(12).cast::<usize>() = len5;
                                     *ptr3.add(8).cast::<*mut u8>() = ptr5.cast_mut();
                                 }
+                                V10::Template(e) => {
+                                    *ptr3.add(4).cast::<u8>() = (2i32) as u8;
+                                    let super::super::super::super::fermyon::spin_template::types::TemplateError{ kind:kind6, message:message6, source_file:source_file6, line:line6, column:column6, snippet:snippet6, } = e;
+                                    *ptr3.add(8).cast::<u8>() = (kind6.clone() as i32) as u8;
+                                    let vec7 = (message6.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr3.add(16).cast::<usize>() = len7;
+                                    *ptr3.add(12).cast::<*mut u8>() = ptr7.cast_mut();
+                                    match source_file6 {
+                                        Some(e) => {
+                                            *ptr3.add(20).cast::<u8>() = (1i32) as u8;
+                                            let vec8 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr8 = vec8.as_ptr().cast::<u8>();
+                                            let len8 = vec8.len();
+                                            ::core::mem::forget(vec8);
+                                            *ptr3.add(28).cast::<usize>() = len8;
+                                            *ptr3.add(24).cast::<*mut u8>() = ptr8.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr3.add(20).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match line6 {
+                                        Some(e) => {
+                                            *ptr3.add(32).cast::<u8>() = (1i32) as u8;
+                                            *ptr3.add(36).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr3.add(32).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match column6 {
+                                        Some(e) => {
+                                            *ptr3.add(40).cast::<u8>() = (1i32) as u8;
+                                            *ptr3.add(44).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr3.add(40).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match snippet6 {
+                                        Some(e) => {
+                                            *ptr3.add(48).cast::<u8>() = (1i32) as u8;
+                                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *ptr3.add(56).cast::<usize>() = len9;
+                                            *ptr3.add(52).cast::<*mut u8>() = ptr9.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr3.add(48).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
                             }
                         }
                     };
@@ -868,11 +1296,34 @@ pub mod exports {
                             let l3 = i32::from(*arg0.add(4).cast::<u8>());
                             match l3 {
                                 0 => (),
-                                _ => {
+                                1 => {
                                     let l4 = *arg0.add(8).cast::<*mut u8>();
                                     let l5 = *arg0.add(12).cast::<usize>();
                                     _rt::cabi_dealloc(l4, l5, 1);
                                 }
+                                _ => {
+                                    let l6 = *arg0.add(12).cast::<*mut u8>();
+                                    let l7 = *arg0.add(16).cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                    let l8 = i32::from(*arg0.add(20).cast::<u8>());
+                                    match l8 {
+                                        0 => (),
+                                        _ => {
+                                            let l9 = *arg0.add(24).cast::<*mut u8>();
+                                            let l10 = *arg0.add(28).cast::<usize>();
+                                            _rt::cabi_dealloc(l9, l10, 1);
+                                        }
+                                    }
+                                    let l11 = i32::from(*arg0.add(48).cast::<u8>());
+                                    match l11 {
+                                        0 => (),
+                                        _ => {
+                                            let l12 = *arg0.add(52).cast::<*mut u8>();
+                                            let l13 = *arg0.add(56).cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -917,14 +1368,14 @@ pub mod exports {
                     match result3 {
                         Ok(e) => {
                             *ptr4.add(0).cast::<u8>() = (0i32) as u8;
-                            let vec21 = e;
-                            let len21 = vec21.len();
-                            let layout21 =
-                                _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * 20, 4);
-                            let result21 = if layout21.size() != 0 {
-                                let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+                            let vec28 = e;
+                            let len28 = vec28.len();
+                            let layout28 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec28.len() * 24, 4);
+                            let result28 = if layout28.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout28).cast::<u8>();
                                 if ptr.is_null() {
-                                    _rt::alloc::handle_alloc_error(layout21);
+                                    _rt::alloc::handle_alloc_error(layout28);
                                 }
                                 ptr
                             } else {
@@ -932,8 +1383,8 @@ pub mod exports {
                                     ::core::ptr::null_mut()
                                 }
                             };
-                            for (i, e) in vec21.into_iter().enumerate() {
-                                let base = result21.add(i * 20);
+                            for (i, e) in vec28.into_iter().enumerate() {
+                                let base = result28.add(i * 24);
                                 {
                                     match e {
                                         Action::CopyFileSubstituted(e) => {
@@ -945,7 +1396,7 @@ pub mod exports {
                                             *base.add(8).cast::<usize>() = len5;
                                             *base.add(4).cast::<*mut u8>() = ptr5.cast_mut();
                                         }
-                                        Action::CopyFileToSubstituted(e) => {
+                                        Action::CopyFileSubstitutedIn(e) => {
                                             *base.add(0).cast::<u8>() = (1i32) as u8;
                                             let (t6_0, t6_1) = e;
                                             let vec7 = (t6_0.into_bytes()).into_boxed_slice();
@@ -954,103 +1405,204 @@ pub mod exports {
                                             ::core::mem::forget(vec7);
                                             *base.add(8).cast::<usize>() = len7;
                                             *base.add(4).cast::<*mut u8>() = ptr7.cast_mut();
-                                            let vec8 = (t6_1.into_bytes()).into_boxed_slice();
-                                            let ptr8 = vec8.as_ptr().cast::<u8>();
-                                            let len8 = vec8.len();
-                                            ::core::mem::forget(vec8);
-                                            *base.add(16).cast::<usize>() = len8;
-                                            *base.add(12).cast::<*mut u8>() = ptr8.cast_mut();
+                                            *base.add(12).cast::<i32>() =
+                                                (t6_1).take_handle() as i32;
                                         }
-                                        Action::CopyFileToRaw(e) => {
+                                        Action::CopyFileToSubstituted(e) => {
                                             *base.add(0).cast::<u8>() = (2i32) as u8;
-                                            let (t9_0, t9_1) = e;
-                                            let vec10 = (t9_0.into_bytes()).into_boxed_slice();
+                                            let (t8_0, t8_1) = e;
+                                            let vec9 = (t8_0.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *base.add(8).cast::<usize>() = len9;
+                                            *base.add(4).cast::<*mut u8>() = ptr9.cast_mut();
+                                            let vec10 = (t8_1.into_bytes()).into_boxed_slice();
                                             let ptr10 = vec10.as_ptr().cast::<u8>();
                                             let len10 = vec10.len();
                                             ::core::mem::forget(vec10);
-                                            *base.add(8).cast::<usize>() = len10;
-                                            *base.add(4).cast::<*mut u8>() = ptr10.cast_mut();
-                                            let vec11 = (t9_1.into_bytes()).into_boxed_slice();
-                                            let ptr11 = vec11.as_ptr().cast::<u8>();
-                                            let len11 = vec11.len();
-                                            ::core::mem::forget(vec11);
-                                            *base.add(16).cast::<usize>() = len11;
-                                            *base.add(12).cast::<*mut u8>() = ptr11.cast_mut();
+                                            *base.add(16).cast::<usize>() = len10;
+                                            *base.add(12).cast::<*mut u8>() = ptr10.cast_mut();
                                         }
-                                        Action::WriteFile(e) => {
+                                        Action::CopyFileToSubstitutedIn(e) => {
                                             *base.add(0).cast::<u8>() = (3i32) as u8;
-                                            let (t12_0, t12_1) = e;
-                                            let vec13 = (t12_0.into_bytes()).into_boxed_slice();
+                                            let (t11_0, t11_1, t11_2) = e;
+                                            let vec12 = (t11_0.into_bytes()).into_boxed_slice();
+                                            let ptr12 = vec12.as_ptr().cast::<u8>();
+                                            let len12 = vec12.len();
+                                            ::core::mem::forget(vec12);
+                                            *base.add(8).cast::<usize>() = len12;
+                                            *base.add(4).cast::<*mut u8>() = ptr12.cast_mut();
+                                            let vec13 = (t11_1.into_bytes()).into_boxed_slice();
                                             let ptr13 = vec13.as_ptr().cast::<u8>();
                                             let len13 = vec13.len();
                                             ::core::mem::forget(vec13);
-                                            *base.add(8).cast::<usize>() = len13;
-                                            *base.add(4).cast::<*mut u8>() = ptr13.cast_mut();
-                                            let vec14 = (t12_1.into_bytes()).into_boxed_slice();
-                                            let ptr14 = vec14.as_ptr().cast::<u8>();
-                                            let len14 = vec14.len();
-                                            ::core::mem::forget(vec14);
-                                            *base.add(16).cast::<usize>() = len14;
-                                            *base.add(12).cast::<*mut u8>() = ptr14.cast_mut();
+                                            *base.add(16).cast::<usize>() = len13;
+                                            *base.add(12).cast::<*mut u8>() = ptr13.cast_mut();
+                                            *base.add(20).cast::<i32>() =
+                                                (t11_2).take_handle() as i32;
                                         }
-                                        Action::WriteFileBinary(e) => {
+                                        Action::CopyFileToRaw(e) => {
                                             *base.add(0).cast::<u8>() = (4i32) as u8;
-                                            let (t15_0, t15_1) = e;
-                                            let vec16 = (t15_0.into_bytes()).into_boxed_slice();
+                                            let (t14_0, t14_1) = e;
+                                            let vec15 = (t14_0.into_bytes()).into_boxed_slice();
+                                            let ptr15 = vec15.as_ptr().cast::<u8>();
+                                            let len15 = vec15.len();
+                                            ::core::mem::forget(vec15);
+                                            *base.add(8).cast::<usize>() = len15;
+                                            *base.add(4).cast::<*mut u8>() = ptr15.cast_mut();
+                                            let vec16 = (t14_1.into_bytes()).into_boxed_slice();
                                             let ptr16 = vec16.as_ptr().cast::<u8>();
                                             let len16 = vec16.len();
                                             ::core::mem::forget(vec16);
-                                            *base.add(8).cast::<usize>() = len16;
-                                            *base.add(4).cast::<*mut u8>() = ptr16.cast_mut();
-                                            let vec17 = (t15_1).into_boxed_slice();
-                                            let ptr17 = vec17.as_ptr().cast::<u8>();
-                                            let len17 = vec17.len();
-                                            ::core::mem::forget(vec17);
-                                            *base.add(16).cast::<usize>() = len17;
-                                            *base.add(12).cast::<*mut u8>() = ptr17.cast_mut();
+                                            *base.add(16).cast::<usize>() = len16;
+                                            *base.add(12).cast::<*mut u8>() = ptr16.cast_mut();
                                         }
-                                        Action::CreateDir(e) => {
+                                        Action::WriteFile(e) => {
                                             *base.add(0).cast::<u8>() = (5i32) as u8;
-                                            let vec18 = (e.into_bytes()).into_boxed_slice();
+                                            let (t17_0, t17_1) = e;
+                                            let vec18 = (t17_0.into_bytes()).into_boxed_slice();
                                             let ptr18 = vec18.as_ptr().cast::<u8>();
                                             let len18 = vec18.len();
                                             ::core::mem::forget(vec18);
                                             *base.add(8).cast::<usize>() = len18;
                                             *base.add(4).cast::<*mut u8>() = ptr18.cast_mut();
+                                            let vec19 = (t17_1.into_bytes()).into_boxed_slice();
+                                            let ptr19 = vec19.as_ptr().cast::<u8>();
+                                            let len19 = vec19.len();
+                                            ::core::mem::forget(vec19);
+                                            *base.add(16).cast::<usize>() = len19;
+                                            *base.add(12).cast::<*mut u8>() = ptr19.cast_mut();
                                         }
-                                        Action::EditFile(e) => {
+                                        Action::WriteFileBinary(e) => {
                                             *base.add(0).cast::<u8>() = (6i32) as u8;
-                                            let (t19_0, t19_1) = e;
-                                            let vec20 = (t19_0.into_bytes()).into_boxed_slice();
-                                            let ptr20 = vec20.as_ptr().cast::<u8>();
-                                            let len20 = vec20.len();
-                                            ::core::mem::forget(vec20);
-                                            *base.add(8).cast::<usize>() = len20;
-                                            *base.add(4).cast::<*mut u8>() = ptr20.cast_mut();
+                                            let (t20_0, t20_1) = e;
+                                            let vec21 = (t20_0.into_bytes()).into_boxed_slice();
+                                            let ptr21 = vec21.as_ptr().cast::<u8>();
+                                            let len21 = vec21.len();
+                                            ::core::mem::forget(vec21);
+                                            *base.add(8).cast::<usize>() = len21;
+                                            *base.add(4).cast::<*mut u8>() = ptr21.cast_mut();
+                                            let vec22 = (t20_1).into_boxed_slice();
+                                            let ptr22 = vec22.as_ptr().cast::<u8>();
+                                            let len22 = vec22.len();
+                                            ::core::mem::forget(vec22);
+                                            *base.add(16).cast::<usize>() = len22;
+                                            *base.add(12).cast::<*mut u8>() = ptr22.cast_mut();
+                                        }
+                                        Action::CreateDir(e) => {
+                                            *base.add(0).cast::<u8>() = (7i32) as u8;
+                                            let vec23 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr23 = vec23.as_ptr().cast::<u8>();
+                                            let len23 = vec23.len();
+                                            ::core::mem::forget(vec23);
+                                            *base.add(8).cast::<usize>() = len23;
+                                            *base.add(4).cast::<*mut u8>() = ptr23.cast_mut();
+                                        }
+                                        Action::EditFile(e) => {
+                                            *base.add(0).cast::<u8>() = (8i32) as u8;
+                                            let (t24_0, t24_1) = e;
+                                            let vec25 = (t24_0.into_bytes()).into_boxed_slice();
+                                            let ptr25 = vec25.as_ptr().cast::<u8>();
+                                            let len25 = vec25.len();
+                                            ::core::mem::forget(vec25);
+                                            *base.add(8).cast::<usize>() = len25;
+                                            *base.add(4).cast::<*mut u8>() = ptr25.cast_mut();
+                                            *base.add(12).cast::<i32>() =
+                                                (t24_1).take_handle() as i32;
+                                        }
+                                        Action::EditFileIn(e) => {
+                                            *base.add(0).cast::<u8>() = (9i32) as u8;
+                                            let (t26_0, t26_1, t26_2) = e;
+                                            let vec27 = (t26_0.into_bytes()).into_boxed_slice();
+                                            let ptr27 = vec27.as_ptr().cast::<u8>();
+                                            let len27 = vec27.len();
+                                            ::core::mem::forget(vec27);
+                                            *base.add(8).cast::<usize>() = len27;
+                                            *base.add(4).cast::<*mut u8>() = ptr27.cast_mut();
                                             *base.add(12).cast::<i32>() =
-                                                (t19_1).take_handle() as i32;
+                                                (t26_1).take_handle() as i32;
+                                            *base.add(16).cast::<i32>() =
+                                                (t26_2).take_handle() as i32;
                                         }
                                     }
                                 }
                             }
-                            *ptr4.add(8).cast::<usize>() = len21;
-                            *ptr4.add(4).cast::<*mut u8>() = result21;
+                            *ptr4.add(8).cast::<usize>() = len28;
+                            *ptr4.add(4).cast::<*mut u8>() = result28;
                         }
                         Err(e) => {
                             *ptr4.add(0).cast::<u8>() = (1i32) as u8;
-                            use super::super::super::super::fermyon::spin_template::types::Error as V23;
+                            use super::super::super::super::fermyon::spin_template::types::Error as V34;
                             match e {
-                                V23::Cancel => {
+                                V34::Cancel => {
                                     *ptr4.add(4).cast::<u8>() = (0i32) as u8;
                                 }
-                                V23::Other(e) => {
+                                V34::Other(e) => {
                                     *ptr4.add(4).cast::<u8>() = (1i32) as u8;
-                                    let vec22 = (e.into_bytes()).into_boxed_slice();
-                                    let ptr22 = vec22.as_ptr().cast::<u8>();
-                                    let len22 = vec22.len();
-                                    ::core::mem::forget(vec22);
-                                    *ptr4.add(12).cast::<usize>() = len22;
-                                    *ptr4.add(8).cast::<*mut u8>() = ptr22.cast_mut();
+                                    let vec29 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr29 = vec29.as_ptr().cast::<u8>();
+                                    let len29 = vec29.len();
+                                    ::core::mem::forget(vec29);
+                                    *ptr4.add(12).cast::<usize>() = len29;
+                                    *ptr4.add(8).cast::<*mut u8>() = ptr29.cast_mut();
+                                }
+                                V34::Template(e) => {
+                                    *ptr4.add(4).cast::<u8>() = (2i32) as u8;
+                                    let super::super::super::super::fermyon::spin_template::types::TemplateError{ kind:kind30, message:message30, source_file:source_file30, line:line30, column:column30, snippet:snippet30, } = e;
+                                    *ptr4.add(8).cast::<u8>() = (kind30.clone() as i32) as u8;
+                                    let vec31 = (message30.into_bytes()).into_boxed_slice();
+                                    let ptr31 = vec31.as_ptr().cast::<u8>();
+                                    let len31 = vec31.len();
+                                    ::core::mem::forget(vec31);
+                                    *ptr4.add(16).cast::<usize>() = len31;
+                                    *ptr4.add(12).cast::<*mut u8>() = ptr31.cast_mut();
+                                    match source_file30 {
+                                        Some(e) => {
+                                            *ptr4.add(20).cast::<u8>() = (1i32) as u8;
+                                            let vec32 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr32 = vec32.as_ptr().cast::<u8>();
+                                            let len32 = vec32.len();
+                                            ::core::mem::forget(vec32);
+                                            *ptr4.add(28).cast::<usize>() = len32;
+                                            *ptr4.add(24).cast::<*mut u8>() = ptr32.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr4.add(20).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match line30 {
+                                        Some(e) => {
+                                            *ptr4.add(32).cast::<u8>() = (1i32) as u8;
+                                            *ptr4.add(36).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr4.add(32).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match column30 {
+                                        Some(e) => {
+                                            *ptr4.add(40).cast::<u8>() = (1i32) as u8;
+                                            *ptr4.add(44).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr4.add(40).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match snippet30 {
+                                        Some(e) => {
+                                            *ptr4.add(48).cast::<u8>() = (1i32) as u8;
+                                            let vec33 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr33 = vec33.as_ptr().cast::<u8>();
+                                            let len33 = vec33.len();
+                                            ::core::mem::forget(vec33);
+                                            *ptr4.add(56).cast::<usize>() = len33;
+                                            *ptr4.add(52).cast::<*mut u8>() = ptr33.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr4.add(48).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
                                 }
                             }
                         }
@@ -1063,12 +1615,12 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l25 = *arg0.add(4).cast::<*mut u8>();
-                            let l26 = *arg0.add(8).cast::<usize>();
-                            let base27 = l25;
-                            let len27 = l26;
-                            for i in 0..len27 {
-                                let base = base27.add(i * 20);
+                            let l33 = *arg0.add(4).cast::<*mut u8>();
+                            let l34 = *arg0.add(8).cast::<usize>();
+                            let base35 = l33;
+                            let len35 = l34;
+                            for i in 0..len35 {
+                                let base = base35.add(i * 24);
                                 {
                                     let l1 = i32::from(*base.add(0).cast::<u8>());
                                     match l1 {
@@ -1081,59 +1633,100 @@ pub mod exports {
                                             let l4 = *base.add(4).cast::<*mut u8>();
                                             let l5 = *base.add(8).cast::<usize>();
                                             _rt::cabi_dealloc(l4, l5, 1);
-                                            let l6 = *base.add(12).cast::<*mut u8>();
-                                            let l7 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l6, l7, 1);
                                         }
                                         2 => {
-                                            let l8 = *base.add(4).cast::<*mut u8>();
-                                            let l9 = *base.add(8).cast::<usize>();
+                                            let l6 = *base.add(4).cast::<*mut u8>();
+                                            let l7 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l6, l7, 1);
+                                            let l8 = *base.add(12).cast::<*mut u8>();
+                                            let l9 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l8, l9, 1);
-                                            let l10 = *base.add(12).cast::<*mut u8>();
-                                            let l11 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l10, l11, 1);
                                         }
                                         3 => {
-                                            let l12 = *base.add(4).cast::<*mut u8>();
-                                            let l13 = *base.add(8).cast::<usize>();
+                                            let l10 = *base.add(4).cast::<*mut u8>();
+                                            let l11 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l10, l11, 1);
+                                            let l12 = *base.add(12).cast::<*mut u8>();
+                                            let l13 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l12, l13, 1);
-                                            let l14 = *base.add(12).cast::<*mut u8>();
-                                            let l15 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l14, l15, 1);
                                         }
                                         4 => {
-                                            let l16 = *base.add(4).cast::<*mut u8>();
-                                            let l17 = *base.add(8).cast::<usize>();
+                                            let l14 = *base.add(4).cast::<*mut u8>();
+                                            let l15 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l14, l15, 1);
+                                            let l16 = *base.add(12).cast::<*mut u8>();
+                                            let l17 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l16, l17, 1);
-                                            let l18 = *base.add(12).cast::<*mut u8>();
-                                            let l19 = *base.add(16).cast::<usize>();
-                                            let base20 = l18;
-                                            let len20 = l19;
-                                            _rt::cabi_dealloc(base20, len20 * 1, 1);
                                         }
                                         5 => {
-                                            let l21 = *base.add(4).cast::<*mut u8>();
-                                            let l22 = *base.add(8).cast::<usize>();
-                                            _rt::cabi_dealloc(l21, l22, 1);
+                                            let l18 = *base.add(4).cast::<*mut u8>();
+                                            let l19 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l18, l19, 1);
+                                            let l20 = *base.add(12).cast::<*mut u8>();
+                                            let l21 = *base.add(16).cast::<usize>();
+                                            _rt::cabi_dealloc(l20, l21, 1);
+                                        }
+                                        6 => {
+                                            let l22 = *base.add(4).cast::<*mut u8>();
+                                            let l23 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l22, l23, 1);
+                                            let l24 = *base.add(12).cast::<*mut u8>();
+                                            let l25 = *base.add(16).cast::<usize>();
+                                            let base26 = l24;
+                                            let len26 = l25;
+                                            _rt::cabi_dealloc(base26, len26 * 1, 1);
+                                        }
+                                        7 => {
+                                            let l27 = *base.add(4).cast::<*mut u8>();
+                                            let l28 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l27, l28, 1);
+                                        }
+                                        8 => {
+                                            let l29 = *base.add(4).cast::<*mut u8>();
+                                            let l30 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l29, l30, 1);
                                         }
                                         _ => {
-                                            let l23 = *base.add(4).cast::<*mut u8>();
-                                            let l24 = *base.add(8).cast::<usize>();
-                                            _rt::cabi_dealloc(l23, l24, 1);
+                                            let l31 = *base.add(4).cast::<*mut u8>();
+                                            let l32 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l31, l32, 1);
                                         }
                                     }
                                 }
                             }
-                            _rt::cabi_dealloc(base27, len27 * 20, 4);
+                            _rt::cabi_dealloc(base35, len35 * 24, 4);
                         }
                         _ => {
-                            let l28 = i32::from(*arg0.add(4).cast::<u8>());
-                            match l28 {
+                            let l36 = i32::from(*arg0.add(4).cast::<u8>());
+                            match l36 {
                                 0 => (),
+                                1 => {
+                                    let l37 = *arg0.add(8).cast::<*mut u8>();
+                                    let l38 = *arg0.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l37, l38, 1);
+                                }
                                 _ => {
-                                    let l29 = *arg0.add(8).cast::<*mut u8>();
-                                    let l30 = *arg0.add(12).cast::<usize>();
-                                    _rt::cabi_dealloc(l29, l30, 1);
+                                    let l39 = *arg0.add(12).cast::<*mut u8>();
+                                    let l40 = *arg0.add(16).cast::<usize>();
+                                    _rt::cabi_dealloc(l39, l40, 1);
+                                    let l41 = i32::from(*arg0.add(20).cast::<u8>());
+                                    match l41 {
+                                        0 => (),
+                                        _ => {
+                                            let l42 = *arg0.add(24).cast::<*mut u8>();
+                                            let l43 = *arg0.add(28).cast::<usize>();
+                                            _rt::cabi_dealloc(l42, l43, 1);
+                                        }
+                                    }
+                                    let l44 = i32::from(*arg0.add(48).cast::<u8>());
+                                    match l44 {
+                                        0 => (),
+                                        _ => {
+                                            let l45 = *arg0.add(52).cast::<*mut u8>();
+                                            let l46 = *arg0.add(56).cast::<usize>();
+                                            _rt::cabi_dealloc(l45, l46, 1);
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -1161,7 +1754,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-new]edit"]
@@ -1185,7 +1778,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-rep]edit"]
@@ -1203,44 +1796,44 @@ pub mod exports {
                 }
                 #[doc(hidden)]
 
-                macro_rules! __export_fermyon_spin_template_template_0_0_1_cabi{
-      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+                macro_rules! __export_fermyon_spin_template_template_0_0_2_cabi{
+          ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
 
-        #[export_name = "fermyon:spin-template/template@0.0.1#[method]edit.apply"]
-        unsafe extern "C" fn export_method_edit_apply(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,) -> *mut u8 {
-          $($path_to_types)*::_export_method_edit_apply_cabi::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0, arg1, arg2, arg3)
-        }
-        #[export_name = "cabi_post_fermyon:spin-template/template@0.0.1#[method]edit.apply"]
-        unsafe extern "C" fn _post_return_method_edit_apply(arg0: *mut u8,) {
-          $($path_to_types)*::__post_return_method_edit_apply::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0)
-        }
-        #[export_name = "fermyon:spin-template/template@0.0.1#run"]
-        unsafe extern "C" fn export_run(arg0: i32,arg1: i32,arg2: *mut u8,arg3: usize,arg4: i32,) -> *mut u8 {
-          $($path_to_types)*::_export_run_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4)
-        }
-        #[export_name = "cabi_post_fermyon:spin-template/template@0.0.1#run"]
-        unsafe extern "C" fn _post_return_run(arg0: *mut u8,) {
-          $($path_to_types)*::__post_return_run::<$ty>(arg0)
-        }
+            #[export_name = "fermyon:spin-template/template@0.0.2#[method]edit.apply"]
+            unsafe extern "C" fn export_method_edit_apply(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,) -> *mut u8 {
+              $($path_to_types)*::_export_method_edit_apply_cabi::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0, arg1, arg2, arg3)
+            }
+            #[export_name = "cabi_post_fermyon:spin-template/template@0.0.2#[method]edit.apply"]
+            unsafe extern "C" fn _post_return_method_edit_apply(arg0: *mut u8,) {
+              $($path_to_types)*::__post_return_method_edit_apply::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0)
+            }
+            #[export_name = "fermyon:spin-template/template@0.0.2#run"]
+            unsafe extern "C" fn export_run(arg0: i32,arg1: i32,arg2: *mut u8,arg3: usize,arg4: i32,) -> *mut u8 {
+              $($path_to_types)*::_export_run_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4)
+            }
+            #[export_name = "cabi_post_fermyon:spin-template/template@0.0.2#run"]
+            unsafe extern "C" fn _post_return_run(arg0: *mut u8,) {
+              $($path_to_types)*::__post_return_run::<$ty>(arg0)
+            }
 
-        const _: () = {
-          #[doc(hidden)]
-          #[export_name = "fermyon:spin-template/template@0.0.1#[dtor]edit"]
-          #[allow(non_snake_case)]
-          unsafe extern "C" fn dtor(rep: *mut u8) {
-            $($path_to_types)*::Edit::dtor::<
-            <$ty as $($path_to_types)*::Guest>::Edit
-            >(rep)
-          }
-        };
-
-      };);
-    }
+            const _: () = {
+              #[doc(hidden)]
+              #[export_name = "fermyon:spin-template/template@0.0.2#[dtor]edit"]
+              #[allow(non_snake_case)]
+              unsafe extern "C" fn dtor(rep: *mut u8) {
+                $($path_to_types)*::Edit::dtor::<
+                <$ty as $($path_to_types)*::Guest>::Edit
+                >(rep)
+              }
+            };
+
+          };);
+        }
                 #[doc(hidden)]
-                pub(crate) use __export_fermyon_spin_template_template_0_0_1_cabi;
+                pub(crate) use __export_fermyon_spin_template_template_0_0_2_cabi;
                 #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                struct _RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 60]);
             }
         }
     }
@@ -1477,7 +2070,7 @@ mod _rt {
 macro_rules! __export_run_template_impl {
   ($ty:ident) => (self::export!($ty with_types_in self););
   ($ty:ident with_types_in $($path_to_types_root:tt)*) => (
-  $($path_to_types_root)*::exports::fermyon::spin_template::template::__export_fermyon_spin_template_template_0_0_1_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template::template);
+  $($path_to_types_root)*::exports::fermyon::spin_template::template::__export_fermyon_spin_template_template_0_0_2_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template::template);
   )
 }
 #[doc(inline)]
@@ -1486,36 +2079,43 @@ pub(crate) use __export_run_template_impl as export;
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.25.0:run-template:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1311] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x9c\x09\x01A\x02\x01\
-A\x09\x01B\x0d\x01q\x02\x06cancel\0\0\x05other\x01s\0\x04\0\x05error\x03\0\0\x01\
-q\x02\x0acreate-new\0\0\x06add-to\x01s\0\x04\0\x0bcreate-mode\x03\0\x02\x01r\x02\
-\x04mode\x03\x12use-default-values\x7f\x04\0\x0brun-options\x03\0\x04\x04\0\x11e\
-xecution-context\x03\x01\x01h\x06\x01@\x03\x04self\x07\x04names\x05values\x01\0\x04\
-\0&[method]execution-context.set-variable\x01\x08\x01j\x01s\x01\x01\x01@\x02\x04\
-self\x07\x08templates\0\x09\x04\0+[method]execution-context.evaluate-template\x01\
-\x0a\x03\x01!fermyon:spin-template/types@0.0.1\x05\0\x02\x03\0\0\x05error\x01B\x1b\
-\x02\x03\x02\x01\x01\x04\0\x05error\x03\0\0\x04\0\x04file\x03\x01\x01i\x02\x01p\x03\
-\x01@\0\0\x04\x04\0\x15[static]file.list-all\x01\x05\x01h\x02\x01@\x01\x04self\x06\
-\0s\x04\0\x11[method]file.path\x01\x07\x01j\x01s\x01\x01\x01@\x01\x04self\x06\0\x08\
-\x04\0\x11[method]file.read\x01\x09\x01p}\x01j\x01\x0a\x01\x01\x01@\x01\x04self\x06\
-\0\x0b\x04\0\x18[method]file.read-binary\x01\x0c\x01ks\x01@\x02\x06prompts\x0dde\
-fault-value\x0d\0s\x04\0\x06prompt\x01\x0e\x01k\x7f\x01@\x02\x06prompts\x0ddefau\
-lt-value\x0f\0\x7f\x04\0\x07confirm\x01\x10\x01ps\x01k}\x01@\x03\x06prompts\x05i\
-tems\x11\x0ddefault-index\x12\0}\x04\0\x06select\x01\x13\x03\x01\x1efermyon:spin\
--template/ui@0.0.1\x05\x02\x02\x03\0\0\x11execution-context\x02\x03\0\0\x0brun-o\
-ptions\x01B\x17\x02\x03\x02\x01\x01\x04\0\x05error\x03\0\0\x02\x03\x02\x01\x03\x04\
-\0\x11execution-context\x03\0\x02\x02\x03\x02\x01\x04\x04\0\x0brun-options\x03\0\
-\x04\x04\0\x04edit\x03\x01\x01o\x02ss\x01p}\x01o\x02s\x08\x01i\x06\x01o\x02s\x0a\
-\x01q\x07\x15copy-file-substituted\x01s\0\x18copy-file-to-substituted\x01\x07\0\x10\
-copy-file-to-raw\x01\x07\0\x0awrite-file\x01\x07\0\x11write-file-binary\x01\x09\0\
-\x0acreate-dir\x01s\0\x09edit-file\x01\x0b\0\x04\0\x06action\x03\0\x0c\x01h\x06\x01\
-h\x03\x01j\x01s\x01\x01\x01@\x03\x04self\x0e\x04texts\x07context\x0f\0\x10\x04\0\
-\x12[method]edit.apply\x01\x11\x01p\x0d\x01j\x01\x12\x01\x01\x01@\x02\x07context\
-\x0f\x07options\x05\0\x13\x04\0\x03run\x01\x14\x04\x01$fermyon:spin-template/tem\
-plate@0.0.1\x05\x05\x04\x01(fermyon:spin-template/run-template@0.0.1\x04\0\x0b\x12\
-\x01\0\x0crun-template\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-co\
-mponent\x070.208.1\x10wit-bindgen-rust\x060.25.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1647] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xec\x0b\x01A\x02\x01\
+A\x09\x01B\x17\x01m\x03\x05parse\x06render\x13undefined-variables\x04\0\x13templ\
+ate-error-kind\x03\0\0\x01ks\x01ky\x01r\x06\x04kind\x01\x07messages\x0bsource-fi\
+le\x02\x04line\x03\x06column\x03\x07snippet\x02\x04\0\x0etemplate-error\x03\0\x04\
+\x01q\x03\x06cancel\0\0\x05other\x01s\0\x08template\x01\x05\0\x04\0\x05error\x03\
+\0\x06\x01q\x02\x0acreate-new\0\0\x06add-to\x01s\0\x04\0\x0bcreate-mode\x03\0\x08\
+\x01r\x02\x04mode\x09\x12use-default-values\x7f\x04\0\x0brun-options\x03\0\x0a\x04\
+\0\x11execution-context\x03\x01\x01h\x0c\x01@\x03\x04self\x0d\x04names\x05values\
+\x01\0\x04\0&[method]execution-context.set-variable\x01\x0e\x01j\x01s\x01\x07\x01\
+@\x02\x04self\x0d\x08templates\0\x0f\x04\0+[method]execution-context.evaluate-te\
+mplate\x01\x10\x01i\x0c\x01@\x01\x04self\x0d\0\x11\x04\0\x1e[method]execution-co\
+ntext.fork\x01\x12\x04\0\"[method]execution-context.snapshot\x01\x12\x03\x01!fer\
+myon:spin-template/types@0.0.2\x05\0\x02\x03\0\0\x05error\x01B\x1b\x02\x03\x02\x01\
+\x01\x04\0\x05error\x03\0\0\x04\0\x04file\x03\x01\x01i\x02\x01p\x03\x01@\0\0\x04\
+\x04\0\x15[static]file.list-all\x01\x05\x01h\x02\x01@\x01\x04self\x06\0s\x04\0\x11\
+[method]file.path\x01\x07\x01j\x01s\x01\x01\x01@\x01\x04self\x06\0\x08\x04\0\x11\
+[method]file.read\x01\x09\x01p}\x01j\x01\x0a\x01\x01\x01@\x01\x04self\x06\0\x0b\x04\
+\0\x18[method]file.read-binary\x01\x0c\x01ks\x01@\x02\x06prompts\x0ddefault-valu\
+e\x0d\0s\x04\0\x06prompt\x01\x0e\x01k\x7f\x01@\x02\x06prompts\x0ddefault-value\x0f\
+\0\x7f\x04\0\x07confirm\x01\x10\x01ps\x01k}\x01@\x03\x06prompts\x05items\x11\x0d\
+default-index\x12\0}\x04\0\x06select\x01\x13\x03\x01\x1efermyon:spin-template/ui\
+@0.0.2\x05\x02\x02\x03\0\0\x11execution-context\x02\x03\0\0\x0brun-options\x01B\x1b\
+\x02\x03\x02\x01\x01\x04\0\x05error\x03\0\0\x02\x03\x02\x01\x03\x04\0\x11executi\
+on-context\x03\0\x02\x02\x03\x02\x01\x04\x04\0\x0brun-options\x03\0\x04\x04\0\x04\
+edit\x03\x01\x01i\x03\x01o\x02s\x07\x01o\x02ss\x01o\x03ss\x07\x01p}\x01o\x02s\x0b\
+\x01i\x06\x01o\x02s\x0d\x01o\x03s\x0d\x07\x01q\x0a\x15copy-file-substituted\x01s\
+\0\x18copy-file-substituted-in\x01\x08\0\x18copy-file-to-substituted\x01\x09\0\x1b\
+copy-file-to-substituted-in\x01\x0a\0\x10copy-file-to-raw\x01\x09\0\x0awrite-fil\
+e\x01\x09\0\x11write-file-binary\x01\x0c\0\x0acreate-dir\x01s\0\x09edit-file\x01\
+\x0e\0\x0cedit-file-in\x01\x0f\0\x04\0\x06action\x03\0\x10\x01h\x06\x01h\x03\x01\
+j\x01s\x01\x01\x01@\x03\x04self\x12\x04texts\x07context\x13\0\x14\x04\0\x12[meth\
+od]edit.apply\x01\x15\x01p\x11\x01j\x01\x16\x01\x01\x01@\x02\x07context\x13\x07o\
+ptions\x05\0\x17\x04\0\x03run\x01\x18\x04\x01$fermyon:spin-template/template@0.0\
+.2\x05\x05\x04\x01(fermyon:spin-template/run-template@0.0.2\x04\0\x0b\x12\x01\0\x0c\
+run-template\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x07\
+0.208.0\x10wit-bindgen-rust\x060.25.0";
 
 #[inline(never)]
 #[doc(hidden)]
@@ -15,8 +15,9 @@ impl Guest for Component {
 
     fn run(context: &bindings::exports::fermyon::spin_template::template::ExecutionContext, options: bindings::exports::fermyon::spin_template::template::RunOptions) -> Result<Vec<Action>, TemplateError> {
         let is_add = matches!(options.mode, bindings::fermyon::spin_template::types::CreateMode::AddTo(_));
+        // The app-level files are already in the app being added to
         let skip_copies = if is_add {
-            vec!["spin.toml.tmpl", "Cargo.toml.tmpl"]
+            vec!["spin.toml.tmpl", "Cargo.toml.tmpl", ".gitignore"]
         } else {
             vec![]
         };
@@ -73,16 +74,8 @@ impl bindings::exports::fermyon::spin_template::template::GuestEdit for Box<dyn
 
 impl bindings::exports::fermyon::spin_template::template::GuestEdit for AddComponentToManifest {
     fn apply(&self, text: String, context: &bindings::exports::fermyon::spin_template::template::ExecutionContext) -> Result<String, TemplateError> {
-        let if_it_were_new_tpl = ui::File::list_all().iter().find(|f| f.path() == "spin.toml.tmpl")
-            .ok_or_else(|| TemplateError::Other("spin.toml.tmpl not found".to_owned()))?
-            .read()
-            .map_err(|e| TemplateError::Other(e.to_string()))?;
-        let if_it_were_new_text: String = context.evaluate_template(&if_it_were_new_tpl)?;
-        let mut if_it_were_new: toml_edit::DocumentMut = if_it_were_new_text.parse().map_err(|e: toml_edit::TomlError| TemplateError::Other(e.to_string()))?;
-
-        if_it_were_new.retain(|k, _| k == "trigger" || k == "component");
-
-        let new_stuff = if_it_were_new.to_string();
+        // The trigger and component are shared with `spin.toml.tmpl` via a partial
+        let new_stuff = context.evaluate_template(r#"{% include "component.toml" %}"#)?;
         Ok(format!("{text}\n{new_stuff}"))
 
     }
@@ -1,9 +1,23 @@
-use std::{path::{Path, PathBuf}, sync::{Arc, RwLock}};
+use std::{collections::HashSet, path::{Path, PathBuf}, sync::{Arc, Mutex}};
+
+use anyhow::Context;
 
 use crate::bindings::{fermyon, exports, RunTemplate};
 use crate::host::{ExecutionContext, Host};
+use crate::limits::{self, LimitExceeded};
+use crate::manifest::{Capability, Manifest};
+use crate::paths::{normalise_app_path, resolve_template_path};
+use crate::render_error::RenderError;
+use crate::template::CONTENT_DIR;
+use crate::template_files::TemplateFiles;
 
 pub trait ActionExecutor {
+    /// Checks that every action is allowed, before any is executed, so that a
+    /// template can't get part way through before being stopped.
+    fn check(&self, _actions: &[exports::fermyon::spin_template::template::Action]) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn execute(&self, action: &exports::fermyon::spin_template::template::Action) -> anyhow::Result<()>;
 }
 
@@ -12,25 +26,122 @@ pub fn dry_run() -> Box<dyn ActionExecutor> {
 }
 
 pub fn apply(
-    store: &Arc<RwLock<wasmtime::Store<Host>>>,
+    store: &Arc<Mutex<wasmtime::Store<Host>>>,
     guest: RunTemplate,
     execution_context: &ExecutionContext,
     execution_context_rsrc: u32, // like the animal that I am
-    content_dir: impl AsRef<Path>,
-    output_dir: impl AsRef<Path>,
-    edit_dir_base: impl AsRef<Path>,
+    template_files: TemplateFiles,
+    destination: Destination,
 ) -> Box<dyn ActionExecutor> {
     Box::new(Apply {
         store: store.clone(),
         guest,
         execution_context: execution_context.clone(),
         execution_context_rsrc,
-        content_dir: content_dir.as_ref().to_owned(),
-        output_dir: output_dir.as_ref().to_owned(),
-        edit_dir_base: edit_dir_base.as_ref().to_owned(),
+        template_files,
+        destination,
+        written: Mutex::new(HashSet::new()),
     })
 }
 
+/// Where a run writes to, and what the template may do there.
+pub struct Destination {
+    /// The app directory, which edits are relative to. For a new app this is
+    /// the output directory.
+    pub app_dir: PathBuf,
+    /// The directory that the paths of copy and write actions are relative to,
+    /// relative to `app_dir`. This is the app directory itself for templates that
+    /// create their own subdirectories.
+    pub write_dir: PathBuf,
+    /// The template's own directory for the item it generates, relative to `app_dir`.
+    /// Anything outside it needs a capability.
+    pub output_dir: PathBuf,
+    /// The manifest of the existing app being added to, relative to `app_dir`.
+    /// This is `None` for a new app, which has no existing files to protect.
+    pub app_manifest: Option<PathBuf>,
+    /// The capabilities that the template declared and the user granted.
+    pub capabilities: Vec<Capability>,
+}
+
+impl Destination {
+    /// For a new app, which the template has to itself.
+    pub fn new_app(name: &str) -> Self {
+        Self {
+            app_dir: PathBuf::from(name),
+            write_dir: PathBuf::new(),
+            output_dir: PathBuf::new(),
+            app_manifest: None,
+            capabilities: vec![],
+        }
+    }
+
+    /// For adding the item `name` to the existing app in `app_dir`, whose manifest is `app_manifest`.
+    pub fn add_to(app_dir: &Path, app_manifest: &str, name: &str, manifest: &Manifest) -> Self {
+        Self {
+            app_dir: app_dir.to_owned(),
+            // Templates that create their own subdirectories write relative to the app, but
+            // their own output is still only the directory named for the item
+            write_dir: if manifest.create_own_subdirs_for_add { PathBuf::new() } else { PathBuf::from(name) },
+            output_dir: PathBuf::from(name),
+            app_manifest: Some(PathBuf::from(app_manifest)),
+            capabilities: manifest.capabilities.clone(),
+        }
+    }
+
+    /// Checks that the template may do everything that `actions` do.
+    pub fn check(&self, actions: &[exports::fermyon::spin_template::template::Action]) -> anyhow::Result<()> {
+        use exports::fermyon::spin_template::template::Action;
+        let mut written = HashSet::new();
+        for action in actions {
+            let write_dir = self.write_dir.as_path();
+            let (base, path, edit) = match action {
+                Action::CopyFileSubstituted(path) | Action::CopyFileSubstitutedIn((path, _)) => (write_dir, path, false),
+                Action::CopyFileToSubstituted((_, to)) | Action::CopyFileToSubstitutedIn((_, to, _)) | Action::CopyFileToRaw((_, to)) => (write_dir, to, false),
+                Action::WriteFile((path, _)) | Action::CreateDir(path) => (write_dir, path, false),
+                Action::WriteFileBinary((path, _)) => (write_dir, path, false),
+                Action::EditFile((path, _)) | Action::EditFileIn((path, _, _)) => (Path::new(""), path, true),
+            };
+            self.resolve(base, path, edit, &mut written)?;
+        }
+        Ok(())
+    }
+
+    // Resolves a path that the template writes to (or edits, which means reading it
+    // first), relative to `base` within the app, and checks that the template may.
+    // `written` is the files that the run has already written, relative to the app.
+    fn resolve(&self, base: &Path, path: &str, edit: bool, written: &mut HashSet<PathBuf>) -> anyhow::Result<PathBuf> {
+        let relative = normalise_app_path(base.join(path))
+            .with_context(|| format!("The template tried to write to '{path}', which is outside the app"))?;
+        let absolute = self.app_dir.join(&relative);
+
+        if self.app_manifest.as_ref() == Some(&relative) {
+            self.require(Capability::EditAppManifest, &relative)?;
+        } else if self.app_manifest.is_some() {
+            if !relative.starts_with(&self.output_dir) {
+                self.require(Capability::EditOutsideOutput, &relative)?;
+            }
+            // A file that was in the app before the run is the user's, even inside the output directory
+            if absolute.is_file() && !written.contains(&relative) {
+                let capability = if edit { Capability::ReadAppFiles } else { Capability::OverwriteAppFiles };
+                self.require(capability, &relative)?;
+            }
+        }
+
+        written.insert(relative);
+        Ok(absolute)
+    }
+
+    fn require(&self, capability: Capability, path: &Path) -> anyhow::Result<()> {
+        if !self.capabilities.contains(&capability) {
+            anyhow::bail!(
+                "The template tried to change {}, which needs the '{capability}' capability, but its manifest doesn't declare it",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
 struct DryRun;
 
 impl ActionExecutor for DryRun {
@@ -38,12 +149,15 @@ impl ActionExecutor for DryRun {
         // TODO: this should probably at least eval substitutions
         let dryrun = match action {
             exports::fermyon::spin_template::template::Action::CopyFileSubstituted(path) => format!("Copy {path}"),
+            exports::fermyon::spin_template::template::Action::CopyFileSubstitutedIn((path, _context)) => format!("Copy {path} using a bound context"),
             exports::fermyon::spin_template::template::Action::CopyFileToSubstituted((from, to)) => format!("Copy file {from} to {to}"),
+            exports::fermyon::spin_template::template::Action::CopyFileToSubstitutedIn((from, to, _context)) => format!("Copy file {from} to {to} using a bound context"),
             exports::fermyon::spin_template::template::Action::CopyFileToRaw((from, to)) => format!("Copy raw file {from} to {to}"),
             exports::fermyon::spin_template::template::Action::WriteFile((path, content)) => format!("Write '{content}' to {path}"),
             exports::fermyon::spin_template::template::Action::WriteFileBinary((path, content)) => format!("Write {} bytes to {path}", content.len()),
             exports::fermyon::spin_template::template::Action::CreateDir(path) => format!("Create empty directory {path}"),
             exports::fermyon::spin_template::template::Action::EditFile((path, _edit)) => format!("Edit {path}"),
+            exports::fermyon::spin_template::template::Action::EditFileIn((path, _edit, _context)) => format!("Edit {path} using a bound context"),
         };
         println!("{dryrun}");
         Ok(())
@@ -51,17 +165,57 @@ impl ActionExecutor for DryRun {
 }
 
 struct Apply {
-    store: Arc<RwLock<wasmtime::Store<Host>>>,  // we're going to need a mutable ref via an immutable self
+    store: Arc<Mutex<wasmtime::Store<Host>>>,  // we're going to need a mutable ref via an immutable self
     guest: RunTemplate,
-    content_dir: PathBuf,
-    output_dir: PathBuf,
-    edit_dir_base: PathBuf,
+    template_files: TemplateFiles,
+    destination: Destination,
+    written: Mutex<HashSet<PathBuf>>,
     execution_context: ExecutionContext,
     execution_context_rsrc: u32,
 }
 
 impl ActionExecutor for Apply {
+    fn check(&self, actions: &[exports::fermyon::spin_template::template::Action]) -> anyhow::Result<()> {
+        self.destination.check(actions)
+    }
+
     fn execute(&self, action: &exports::fermyon::spin_template::template::Action) -> anyhow::Result<()> {
+        self.run(action)
+    }
+}
+
+/// The execution contexts that the guest bound to the actions it returned. The guest
+/// gave them up by returning them, so they are freed when this is dropped, whether
+/// the actions were all run or the run stopped at the check or a failed action.
+pub struct BoundContexts {
+    store: Arc<Mutex<wasmtime::Store<Host>>>,
+    reps: Vec<u32>,
+}
+
+impl BoundContexts {
+    pub fn new(store: &Arc<Mutex<wasmtime::Store<Host>>>, actions: &[exports::fermyon::spin_template::template::Action]) -> Self {
+        use exports::fermyon::spin_template::template::Action;
+        let reps = actions.iter()
+            .filter_map(|action| match action {
+                Action::CopyFileSubstitutedIn((_, context)) | Action::CopyFileToSubstitutedIn((_, _, context)) | Action::EditFileIn((_, _, context)) => Some(context.rep()),
+                _ => None,
+            })
+            .collect();
+        Self { store: store.clone(), reps }
+    }
+}
+
+impl Drop for BoundContexts {
+    fn drop(&mut self) {
+        let mut store = self.store.lock().unwrap_or_else(|e| e.into_inner());
+        for rep in self.reps.drain(..) {
+            _ = store.data_mut().execution_contexts.delete(wasmtime::component::Resource::<ExecutionContext>::new_own(rep));
+        }
+    }
+}
+
+impl Apply {
+    fn run(&self, action: &exports::fermyon::spin_template::template::Action) -> anyhow::Result<()> {
         // TODO: Ideally we would eval all this stuff before writing any files - like
         // verify overwrites, Liquid expansion, etc.  Like eval it to the point of
         // "write these buffers to these locations, which either do not exist or we are
@@ -69,70 +223,94 @@ impl ActionExecutor for Apply {
         // file writes.  This creates a mild faff for the copy operation, though, and
         // would need delicate handling for multiple edits to the same file.
 
-        // TODO: a buttload of validation. Paths must be relative and must resolve to within
-        // the output tree.  Ironically having the guest use WASI filesystem operations
-        // would do this for us!
+        // Paths must be relative and must resolve to within the app, and anything outside
+        // the output directory needs a capability (see `Destination::resolve`).  Ironically
+        // having the guest use WASI filesystem operations would do some of this for us!
         match action {
             exports::fermyon::spin_template::template::Action::CopyFileSubstituted(path) => {
-                let out_file = self.output_dir.join(&path);
-                if let Some(d) = out_file.parent() {
-                    std::fs::create_dir_all(d)?;
-                }
-                let tpl = std::fs::read_to_string(self.content_dir.join(&path))?;
-                let new_text = self.execution_context.evaluate_template(&tpl)?;
-                std::fs::write(&out_file, &new_text)?;
+                self.copy_substituted(path, path, &self.execution_context)?;
+            }
+            exports::fermyon::spin_template::template::Action::CopyFileSubstitutedIn((path, context)) => {
+                self.copy_substituted(path, path, &self.bound_context(context)?)?;
             }
             exports::fermyon::spin_template::template::Action::CopyFileToSubstituted((from, to)) => {
-                let out_file = self.output_dir.join(&to);
-                if let Some(d) = out_file.parent() {
-                    std::fs::create_dir_all(d)?;
-                }
-                let tpl = std::fs::read_to_string(self.content_dir.join(&from))?;
-                let new_text = self.execution_context.evaluate_template(&tpl)?;
-                std::fs::write(&out_file, &new_text)?;
+                self.copy_substituted(from, to, &self.execution_context)?;
+            }
+            exports::fermyon::spin_template::template::Action::CopyFileToSubstitutedIn((from, to, context)) => {
+                self.copy_substituted(from, to, &self.bound_context(context)?)?;
             }
             exports::fermyon::spin_template::template::Action::CopyFileToRaw((from, to)) => {
-                let out_file = self.output_dir.join(&to);
+                let out_file = self.output_file(to)?;
                 if let Some(d) = out_file.parent() {
                     std::fs::create_dir_all(d)?;
                 }
-                std::fs::copy(self.content_dir.join(&from), &out_file)?;
+                std::fs::write(&out_file, self.template_files.read(&resolve_template_path(CONTENT_DIR, from)?)?)?;
             }
             exports::fermyon::spin_template::template::Action::WriteFile((path, content)) => {
-                let out_file = self.output_dir.join(&path);
+                let out_file = self.output_file(path)?;
                 if let Some(d) = out_file.parent() {
                     std::fs::create_dir_all(d)?;
                 }
-                std::fs::write(&out_file, &content)?;
+                std::fs::write(&out_file, content)?;
             }
             exports::fermyon::spin_template::template::Action::WriteFileBinary((path, content)) => {
-                let out_file = self.output_dir.join(&path);
+                let out_file = self.output_file(path)?;
                 if let Some(d) = out_file.parent() {
                     std::fs::create_dir_all(d)?;
                 }
-                std::fs::write(&out_file, &content)?;
+                std::fs::write(&out_file, content)?;
             }
             exports::fermyon::spin_template::template::Action::CreateDir(path) => {
-                let out_dir = self.output_dir.join(&path);
+                let out_dir = self.output_file(path)?;
                 std::fs::create_dir_all(out_dir)?;
             }
             exports::fermyon::spin_template::template::Action::EditFile((path, edit)) => {
-                use std::ops::DerefMut;
-                //println!("***EDIT PATH FROM TPL {path:?}");
-                let mut store = self.store.write().unwrap();
-                let store = store.deref_mut();
-                let guest = self.guest.fermyon_spin_template_template();
-                let ec_rsrc = wasmtime::component::Resource::new_borrow(self.execution_context_rsrc);
-
-                let edit_file = self.edit_dir_base.join(&path);
-                //println!("***EDB {:?}, ABS EDIT FILE {edit_file:?}", self.edit_dir_base);
-                let edit_result = apply_edit(edit_file, ec_rsrc, edit, guest, store);
-                _ = edit.resource_drop(store);
-                edit_result?;
+                self.edit(path, edit, self.execution_context_rsrc)?;
+            }
+            exports::fermyon::spin_template::template::Action::EditFileIn((path, edit, context)) => {
+                self.edit(path, edit, context.rep())?;
             }
         }
         Ok(())
     }
+
+    // The file that the path of a copy or write action refers to, if the template may write it
+    fn output_file(&self, path: &str) -> anyhow::Result<PathBuf> {
+        let mut written = self.written.lock().unwrap();
+        self.destination.resolve(&self.destination.write_dir, path, false, &mut written)
+    }
+
+    fn copy_substituted(&self, from: &str, to: &str, context: &ExecutionContext) -> anyhow::Result<()> {
+        let out_file = self.output_file(to)?;
+        if let Some(d) = out_file.parent() {
+            std::fs::create_dir_all(d)?;
+        }
+        let tpl = self.template_files.read_to_string(&resolve_template_path(CONTENT_DIR, from)?)?;
+        let new_text = context.evaluate_template(&tpl).map_err(|e| e.in_file(from))?;
+        std::fs::write(&out_file, &new_text)?;
+        Ok(())
+    }
+
+    fn edit(&self, path: &str, edit: &wasmtime::component::ResourceAny, context_rsrc: u32) -> anyhow::Result<()> {
+        use std::ops::DerefMut;
+        //println!("***EDIT PATH FROM TPL {path:?}");
+        let mut store = self.store.lock().unwrap();
+        let store = store.deref_mut();
+        let guest = self.guest.fermyon_spin_template_template();
+        let ec_rsrc = wasmtime::component::Resource::new_borrow(context_rsrc);
+
+        // Edit paths are relative to the app directory rather than the output directory
+        let edit_file = self.destination.resolve(Path::new(""), path, true, &mut self.written.lock().unwrap());
+        //println!("***ABS EDIT FILE {edit_file:?}");
+        let edit_result = edit_file.and_then(|edit_file| apply_edit(edit_file, ec_rsrc, edit, guest, store));
+        _ = edit.resource_drop(store);
+        edit_result
+    }
+
+    // The context that the guest bound to an action, as opposed to the run's own context
+    fn bound_context(&self, context: &wasmtime::component::Resource<ExecutionContext>) -> anyhow::Result<ExecutionContext> {
+        Ok(self.store.lock().unwrap().data().execution_contexts.get(context)?.clone())
+    }
 }
 
 // Extracts implementation of edit callback so that we can make sure to dispose the ResourceAny without
@@ -140,18 +318,185 @@ impl ActionExecutor for Apply {
 fn apply_edit(edit_file: impl AsRef<Path>, context: wasmtime::component::Resource<ExecutionContext>, edit: &wasmtime::component::ResourceAny, guest: &exports::fermyon::spin_template::template::Guest, store: &mut wasmtime::Store<Host>) -> anyhow::Result<()> {
     let edit_file = edit_file.as_ref();
     //println!("***APPLYING EDIT TO {edit_file:?}");
-    let edit_content = std::fs::read_to_string(&edit_file).unwrap_or_default();
+    let edit_content = std::fs::read_to_string(edit_file).unwrap_or_default();
+    let limits = store.data().limits;
+    limits.refuel(store)?;
     let edit_result = match guest.edit().call_apply(store, *edit, &edit_content, context) {
         Ok(Ok(r)) => r,
         Ok(Err(fermyon::spin_template::types::Error::Cancel)) => return Ok(()),
+        Ok(Err(fermyon::spin_template::types::Error::Template(e))) => return Err(RenderError::from(e).into()),
         Ok(Err(e)) => anyhow::bail!("Inner err! {e:#}"),
+        Err(e) if LimitExceeded::of(&e).is_some() => return Err(limits::template_error(e, &limits)),
         Err(e) => anyhow::bail!("Outer err! {e:#}"),
     };
     if edit_result != edit_content {
         if let Some(d) = edit_file.parent() {
             std::fs::create_dir_all(d)?;
         }
-        std::fs::write(&edit_file, edit_result)?;
+        std::fs::write(edit_file, edit_result)?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use exports::fermyon::spin_template::template::Action;
+
+    #[test]
+    fn bound_contexts_are_freed() {
+        let host = Host::new(TemplateFiles::Dir(std::env::temp_dir()), false, crate::limits::Limits::TEMPLATE);
+        let store = Arc::new(Mutex::new(wasmtime::Store::new(&wasmtime::Engine::default(), host)));
+        let context = || ExecutionContext::new(Default::default(), liquid::ParserBuilder::with_stdlib().build().unwrap(), Default::default());
+        let (bound, unbound) = {
+            let mut store = store.lock().unwrap();
+            let table = &mut store.data_mut().execution_contexts;
+            (table.push(context()).unwrap(), table.push(context()).unwrap())
+        };
+        let actions = vec![
+            Action::CreateDir("api".to_owned()),
+            Action::CopyFileSubstitutedIn(("api.txt".to_owned(), wasmtime::component::Resource::new_own(bound.rep()))),
+        ];
+
+        // As when the actions fail the check, or one of them fails
+        drop(BoundContexts::new(&store, &actions));
+
+        let store = store.lock().unwrap();
+        assert!(store.data().execution_contexts.get(&bound).is_err());
+        assert!(store.data().execution_contexts.get(&unbound).is_ok());
+    }
+
+    #[test]
+    fn writes_outside_the_output_need_capabilities() {
+        let app_dir = std::env::temp_dir().join(format!("run-template-actions-test-{}", std::process::id()));
+        std::fs::create_dir_all(&app_dir).unwrap();
+        std::fs::write(app_dir.join("Cargo.toml"), "[workspace]").unwrap();
+        let destination = |capabilities| Destination {
+            app_dir: app_dir.clone(),
+            write_dir: PathBuf::from("api"),
+            output_dir: PathBuf::from("api"),
+            app_manifest: Some(PathBuf::from("spin.toml")),
+            capabilities,
+        };
+        let resolve = |destination: &Destination, base: &str, path: &str, edit| {
+            destination.resolve(Path::new(base), path, edit, &mut HashSet::new())
+        };
+
+        let none = destination(vec![]);
+        assert_eq!(app_dir.join("api/src/lib.rs"), resolve(&none, "api", "./src/lib.rs", false).unwrap());
+        assert!(resolve(&none, "api", "../../escape.txt", false).unwrap_err().to_string().contains("outside the app"));
+        assert!(resolve(&none, "", "spin.toml", true).unwrap_err().to_string().contains("'edit-app-manifest'"));
+        assert!(resolve(&none, "api", "../notes.txt", false).unwrap_err().to_string().contains("'edit-outside-output'"));
+
+        let outside = destination(vec![Capability::EditOutsideOutput]);
+        resolve(&outside, "", "notes.txt", true).unwrap();
+        assert!(resolve(&outside, "", "Cargo.toml", true).unwrap_err().to_string().contains("'read-app-files'"));
+
+        // Files the run wrote itself can be edited without reading the app's files
+        let mut written = HashSet::new();
+        std::fs::create_dir_all(app_dir.join("api")).unwrap();
+        std::fs::write(app_dir.join("api/fruit.txt"), "apple").unwrap();
+        destination(vec![Capability::OverwriteAppFiles]).resolve(Path::new("api"), "fruit.txt", false, &mut written).unwrap();
+        none.resolve(Path::new(""), "api/fruit.txt", true, &mut written).unwrap();
+
+        _ = std::fs::remove_dir_all(&app_dir);
+    }
+
+    #[test]
+    fn overwriting_existing_app_files_needs_a_capability() {
+        let app_dir = std::env::temp_dir().join(format!("run-template-overwrite-test-{}", std::process::id()));
+        std::fs::create_dir_all(app_dir.join("api")).unwrap();
+        std::fs::write(app_dir.join("Cargo.toml"), "[workspace]").unwrap();
+        std::fs::write(app_dir.join("api").join("lib.rs"), "// mine").unwrap();
+        // As for a template that creates its own subdirectories, writing relative to the app
+        let destination = |capabilities| Destination {
+            app_dir: app_dir.clone(),
+            write_dir: PathBuf::new(),
+            output_dir: PathBuf::from("api"),
+            app_manifest: Some(PathBuf::from("spin.toml")),
+            capabilities,
+        };
+        let write = |destination: &Destination, path: &str| destination.resolve(Path::new(""), path, false, &mut HashSet::new());
+
+        let outside = destination(vec![Capability::EditOutsideOutput]);
+        assert!(write(&outside, "spin.toml").unwrap_err().to_string().contains("'edit-app-manifest'"));
+        assert!(write(&outside, "Cargo.toml").unwrap_err().to_string().contains("'overwrite-app-files'"));
+        assert!(write(&outside, "api/lib.rs").unwrap_err().to_string().contains("'overwrite-app-files'"));
+        write(&outside, "api/Cargo.toml").unwrap();
+        write(&destination(vec![Capability::EditOutsideOutput, Capability::OverwriteAppFiles]), "Cargo.toml").unwrap();
+
+        _ = std::fs::remove_dir_all(&app_dir);
+    }
+
+    // The files that a shipped sample's component writes and edits, as paths relative to where
+    // it writes (the app or the item's directory) and whether each is an edit. This mirrors the
+    // samples' `run` functions, with "api" typed where the sample asks for a file name.
+    fn sample_writes(sample: &str, name: &str, add: bool) -> Vec<(String, bool)> {
+        let content = TemplateFiles::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(sample).join("template").join(CONTENT_DIR));
+        let skipped: &[&str] = match (sample, add) {
+            ("sample-template", true) => &["spin.toml"],
+            ("http-rust", true) => &["spin.toml.tmpl", "Cargo.toml.tmpl", ".gitignore"],
+            _ => &[],
+        };
+        let mut writes = content.files_in(Path::new(""))
+            .into_iter()
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .filter(|path| !skipped.contains(&path.as_str()))
+            .map(|path| {
+                let path = path.strip_suffix(".raw").or_else(|| path.strip_suffix(".tmpl")).unwrap_or(&path).to_owned();
+                (path.replace("{{ project-name | kebab_case }}", name), false)
+            })
+            .collect::<Vec<_>>();
+        let edits: &[&str] = match (sample, add) {
+            ("sample-template", false) => {
+                writes.extend(["api.txt", "cherry.txt", "binned.bin"].map(|path| (path.to_owned(), false)));
+                &["spork.txt"]
+            }
+            ("sample-template", true) => {
+                writes.extend(["api.txt", "cherry.txt", "binned.bin"].map(|path| (path.to_owned(), false)));
+                &["spork.txt", "spin.toml"]
+            }
+            ("http-rust", true) => &["spin.toml", "Cargo.toml"],
+            _ => &[],
+        };
+        writes.extend(edits.iter().map(|path| (path.to_string(), true)));
+        writes
+    }
+
+    #[test]
+    fn samples_declare_the_capabilities_they_use() {
+        let mut store = wasmtime::Store::<()>::default();
+        for sample in ["sample-template", "http-rust"] {
+            let app_dir = std::env::temp_dir().join(format!("run-template-{sample}-capabilities-test-{}", std::process::id()));
+            let sample_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(sample).join("template");
+            let manifest = Manifest::load(&TemplateFiles::Dir(sample_dir), Path::new(crate::template::MANIFEST_FILE)).unwrap();
+
+            // An app that the sample created itself
+            for (path, _) in sample_writes(sample, "my-app", false) {
+                let path = app_dir.join(path);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(path, "").unwrap();
+            }
+
+            let actions = sample_writes(sample, "api", true).into_iter()
+                .map(|(path, edit)| match edit {
+                    true => {
+                        let edit = wasmtime::component::Resource::<()>::new_own(0);
+                        Action::EditFile((path, wasmtime::component::ResourceAny::try_from_resource(edit, &mut store).unwrap()))
+                    }
+                    false => Action::WriteFile((path, String::new())),
+                })
+                .collect::<Vec<_>>();
+
+            let destination = |capabilities| Destination { capabilities, ..Destination::add_to(&app_dir, "spin.toml", "api", &manifest) };
+            destination(manifest.capabilities.clone()).check(&actions)
+                .unwrap_or_else(|e| panic!("{sample} doesn't declare a capability it uses: {e}"));
+            for capability in &manifest.capabilities {
+                let others = manifest.capabilities.iter().filter(|c| c != &capability).cloned().collect();
+                assert!(destination(others).check(&actions).is_err(), "{sample} declares '{capability}' but doesn't use it");
+            }
+
+            _ = std::fs::remove_dir_all(&app_dir);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 wasmtime::component::bindgen!({
     path: "../wit/template",
+    world: "run-template",
     trappable_imports: ["prompt", "confirm", "select"],
 
     with: {
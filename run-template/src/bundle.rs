@@ -0,0 +1,193 @@
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+
+use crate::paths::resolve_template_path;
+use crate::signing::{Signature, SigningKey};
+
+/// A template packed into a single file, so that it can be shared and run
+/// without unpacking it. A bundle is read into memory whole when it is opened.
+///
+/// The file is a header line, then the length of the index as a little-endian
+/// u64, then the index, then the contents of each file in index order. The index
+/// is TOML listing every file's path, size and SHA-256 hash. The hashes are
+/// checked when the bundle is opened, so a damaged bundle is refused rather than run.
+/// A signed bundle ends with a signature header line and then the signature of
+/// the index (see `signing`) as TOML.
+pub struct Bundle {
+    pub(crate) path: PathBuf,
+    pub(crate) files: BTreeMap<String, Vec<u8>>,
+    /// The index as it is in the file, which is what the signature signs.
+    pub(crate) index: Vec<u8>,
+    pub(crate) signature: Option<Signature>,
+}
+
+const HEADER: &[u8] = b"run-template bundle v1\n";
+const SIGNATURE_HEADER: &[u8] = b"run-template signature v1\n";
+
+/// The extension that `pack` gives bundles. Bundles are recognised by their
+/// header rather than their extension, so this is only a convention.
+pub const EXTENSION: &str = "rtb";
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Index {
+    #[serde(default)]
+    file: Vec<IndexEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IndexEntry {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+impl Bundle {
+    /// Whether `path` is a bundle file, as opposed to a manifest or directory.
+    pub fn is_bundle(path: &Path) -> bool {
+        let mut header = [0u8; HEADER.len()];
+        path.is_file()
+            && std::fs::File::open(path).and_then(|mut f| f.read_exact(&mut header)).is_ok()
+            && header == HEADER
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read template bundle {}", path.display()))?;
+        parse(path, &data)
+            .with_context(|| format!("{} is not a valid template bundle", path.display()))
+    }
+
+    /// Writes `files`, keyed by their paths with `/` separators, as a bundle,
+    /// signed with `key` if there is one.
+    pub fn write(files: &BTreeMap<String, Vec<u8>>, path: &Path, key: Option<&SigningKey>) -> anyhow::Result<()> {
+        std::fs::write(path, serialize(files, key)?)
+            .with_context(|| format!("Failed to write template bundle {}", path.display()))
+    }
+}
+
+impl std::fmt::Debug for Bundle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Bundle")
+            .field("path", &self.path)
+            .field("files", &self.files.len())
+            .finish()
+    }
+}
+
+fn serialize(files: &BTreeMap<String, Vec<u8>>, key: Option<&SigningKey>) -> anyhow::Result<Vec<u8>> {
+    let index = Index {
+        file: files.iter()
+            .map(|(path, contents)| IndexEntry {
+                path: path.clone(),
+                size: contents.len() as u64,
+                sha256: sha256(contents),
+            })
+            .collect(),
+    };
+    let index = toml::to_string(&index)?;
+
+    let mut data = HEADER.to_vec();
+    data.extend((index.len() as u64).to_le_bytes());
+    data.extend(index.as_bytes());
+    for contents in files.values() {
+        data.extend(contents);
+    }
+    if let Some(key) = key {
+        data.extend(SIGNATURE_HEADER);
+        data.extend(toml::to_string(&key.sign(index.as_bytes()))?.as_bytes());
+    }
+    Ok(data)
+}
+
+fn parse(path: &Path, data: &[u8]) -> anyhow::Result<Bundle> {
+    let data = data.strip_prefix(HEADER).context("it doesn't start with the bundle header")?;
+    let (index_len, data) = data.split_first_chunk::<8>().context("it is truncated")?;
+    let index_len = usize::try_from(u64::from_le_bytes(*index_len))?;
+    if index_len > data.len() {
+        anyhow::bail!("it is truncated");
+    }
+    let (index_bytes, mut data) = data.split_at(index_len);
+    let index: Index = toml::from_str(std::str::from_utf8(index_bytes)?).context("its index is not valid")?;
+
+    let mut files = BTreeMap::new();
+    for entry in index.file {
+        resolve_template_path("", &entry.path)
+            .with_context(|| format!("its index contains an invalid path '{}'", entry.path))?;
+        let size = usize::try_from(entry.size)?;
+        if size > data.len() {
+            anyhow::bail!("it is truncated: '{}' is incomplete", entry.path);
+        }
+        let (contents, rest) = data.split_at(size);
+        if sha256(contents) != entry.sha256 {
+            anyhow::bail!("'{}' doesn't match its hash in the index, so the bundle is damaged or has been tampered with", entry.path);
+        }
+        if files.insert(entry.path.clone(), contents.to_vec()).is_some() {
+            anyhow::bail!("its index lists '{}' more than once", entry.path);
+        }
+        data = rest;
+    }
+
+    let signature = match data.strip_prefix(SIGNATURE_HEADER) {
+        Some(signature) => Some(toml::from_str(std::str::from_utf8(signature)?).context("its signature is not valid")?),
+        None if data.is_empty() => None,
+        None => anyhow::bail!("it has {} bytes after the last file in its index", data.len()),
+    };
+
+    Ok(Bundle {
+        path: path.to_owned(),
+        files,
+        index: index_bytes.to_vec(),
+        signature,
+    })
+}
+
+fn sha256(contents: &[u8]) -> String {
+    format!("{:x}", sha2::Sha256::digest(contents))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn files() -> BTreeMap<String, Vec<u8>> {
+        [
+            ("spin-template.toml", "id = \"test\""),
+            ("content/spin.toml", "name = \"{{ project-name }}\""),
+            ("content/src/lib.rs", "// {{ project-name }}"),
+        ].into_iter().map(|(path, text)| (path.to_owned(), text.as_bytes().to_vec())).collect()
+    }
+
+    fn parse(data: &[u8]) -> anyhow::Result<Bundle> {
+        super::parse(Path::new("test.rtb"), data)
+    }
+
+    #[test]
+    fn bundles_round_trip() {
+        let data = serialize(&files(), None).unwrap();
+        assert!(data.starts_with(HEADER));
+        let bundle = parse(&data).unwrap();
+        assert_eq!(files(), bundle.files);
+        assert!(bundle.signature.is_none());
+    }
+
+    #[test]
+    fn damaged_bundles_are_refused() {
+        let mut data = serialize(&files(), None).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 1;
+        let err = parse(&data).unwrap_err().to_string();
+        assert!(err.contains("'spin-template.toml' doesn't match its hash"), "{err}");
+
+        data.truncate(last);
+        assert!(parse(&data).is_err());
+    }
+}
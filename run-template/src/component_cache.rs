@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Context;
+use sha2::Digest;
+use wasmtime::component::Component;
+
+use crate::template_files::TemplateFiles;
+
+/// The one wasmtime `Engine` used for the template and all its filters, plus
+/// caches of compiled components.
+///
+/// Components are cached in memory for the run (so a filter file registered
+/// under several names is compiled once), and on disk as precompiled
+/// artifacts so that later runs of the same template don't need to compile at
+/// all. The disk cache is keyed by the hash of the Wasm file and by the
+/// engine's compatibility hash, which changes with the wasmtime version and
+/// with any engine settings that affect code generation.
+pub struct ComponentCache {
+    engine: wasmtime::Engine,
+    cache_dir: Option<PathBuf>,
+    loaded: Mutex<HashMap<String, Component>>,
+}
+
+impl ComponentCache {
+    /// Uses the per-user cache directory, if there is one.
+    pub fn new() -> anyhow::Result<Self> {
+        let cache_dir = dirs::cache_dir().map(|d| d.join("run-template").join("components"));
+        Self::with_cache_dir(cache_dir)
+    }
+
+    pub fn with_cache_dir(cache_dir: Option<PathBuf>) -> anyhow::Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        // Components are given a fuel budget; see `limits`
+        config.consume_fuel(true);
+        let engine = wasmtime::Engine::new(&config)?;
+
+        let cache_dir = cache_dir.map(|d| d.join(engine_key(&engine)));
+
+        Ok(Self {
+            engine,
+            cache_dir,
+            loaded: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn engine(&self) -> &wasmtime::Engine {
+        &self.engine
+    }
+
+    pub fn load(&self, files: &TemplateFiles, wasm_path: &Path) -> anyhow::Result<Component> {
+        let wasm = files.read(wasm_path)
+            .with_context(|| format!("Failed to read component {}", files.display(wasm_path)))?;
+        let content_hash = format!("{:x}", sha2::Sha256::digest(&wasm));
+
+        let mut loaded = self.loaded.lock().unwrap();
+        if let Some(component) = loaded.get(&content_hash) {
+            return Ok(component.clone());
+        }
+
+        let cached_path = self.cache_dir.as_ref().map(|d| d.join(format!("{content_hash}.cwasm")));
+        let component = match cached_path.as_ref().and_then(|p| self.load_precompiled(p)) {
+            Some(component) => component,
+            None => {
+                let component = Component::new(&self.engine, &wasm)
+                    .with_context(|| format!("{} is not a valid Wasm component", files.display(wasm_path)))?;
+                if let Some(p) = &cached_path {
+                    // The cache is only an optimisation so don't fail the run if we can't write it
+                    _ = save_precompiled(&component, p);
+                }
+                component
+            }
+        };
+
+        loaded.insert(content_hash, component.clone());
+        Ok(component)
+    }
+
+    fn load_precompiled(&self, path: &Path) -> Option<Component> {
+        if !path.is_file() {
+            return None;
+        }
+        // SAFETY: deserialising is only safe for artifacts produced by `Component::serialize`
+        // with a compatible engine. We only read artifacts that we wrote, from a directory
+        // keyed by the engine's compatibility hash; wasmtime also checks the artifact header
+        // and we fall back to compiling if it doesn't match.
+        unsafe { Component::deserialize_file(&self.engine, path) }.ok()
+    }
+}
+
+fn save_precompiled(component: &Component, path: &Path) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // Write then rename so that a concurrent run never sees a partial artifact
+    let temp_path = path.with_extension(format!("cwasm.{}", std::process::id()));
+    std::fs::write(&temp_path, component.serialize()?)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+// Names the cache directory for artifacts the engine can load. `DefaultHasher` could
+// change between Rust versions, so the compatibility hash is fed to sha256 instead.
+fn engine_key(engine: &wasmtime::Engine) -> String {
+    let mut hasher = Sha256Hasher(sha2::Sha256::new());
+    engine.precompile_compatibility_hash().hash(&mut hasher);
+    format!("{:x}", hasher.0.finalize())
+}
+
+struct Sha256Hasher(sha2::Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    // `engine_key` uses the whole digest; this is only here to satisfy `Hasher`
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().expect("sha256 digests are 32 bytes"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn engine_keys_are_sha256_digests() {
+        let key = engine_key(ComponentCache::with_cache_dir(None).unwrap().engine());
+        assert_eq!(64, key.len());
+        assert_eq!(key, engine_key(ComponentCache::with_cache_dir(None).unwrap().engine()));
+    }
+
+    #[test]
+    fn precompiled_components_are_reused() {
+        let dir = std::env::temp_dir().join(format!("run-template-cache-test-{}", std::process::id()));
+        let files = TemplateFiles::Dir(dir.clone());
+        let wasm_path = Path::new("empty.wasm");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(wasm_path), "(component)").unwrap();  // wasmtime accepts text format too
+
+        let cache_dir = dir.join("cache");
+        ComponentCache::with_cache_dir(Some(cache_dir.clone())).unwrap().load(&files, wasm_path).unwrap();
+
+        let artifacts = walkdir::WalkDir::new(&cache_dir).into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|x| x == "cwasm"))
+            .map(|e| e.path().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(1, artifacts.len());
+
+        let cache = ComponentCache::with_cache_dir(Some(cache_dir)).unwrap();
+        assert!(cache.load_precompiled(&artifacts[0]).is_some());
+        cache.load(&files, wasm_path).unwrap();
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,44 +1,230 @@
 use std::{
+    collections::BTreeMap,
     fmt::{Debug, Display},
-    path::Path,
-    sync::{Arc, RwLock},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
 };
 
-// use anyhow::Context;
-use liquid_core::{Filter, ParseFilter, Runtime, ValueView};
+use anyhow::Context;
+use liquid_core::{Expression, Filter, ParseFilter, Runtime, ValueView};
+
+use crate::component_cache::ComponentCache;
+use crate::host::Host;
+use crate::limits::{LimitExceeded, Limits};
+use crate::template_files::TemplateFiles;
+use crate::world::{World, FILTER_INTERFACE};
 
 wasmtime::component::bindgen!({
     path: "../wit/filter",
+    world: "custom-filter",
 });
 
+use exports::fermyon::spin_template_filter::types::{Arguments, FilterDescription, Guest, Parameter, ValueNode};
+
 #[derive(Clone)]
 pub struct CustomFilterParser {
     name: String,
-    store: Arc<RwLock<wasmtime::Store<()>>>,
-    bindings: Arc<CustomFilter>,
-    _instance: wasmtime::component::Instance,
+    source: PathBuf,
+    description: FilterDescription,
+    positional_reflection: &'static [liquid_core::parser::ParameterReflection],
+    keyword_reflection: &'static [liquid_core::parser::ParameterReflection],
+    instance: FilterInstance,
+}
+
+/// The component instance that a filter runs in. Filter components get a store
+/// of their own. Filters exported by the template component run in a second
+/// instance of it, because the template's own instance is busy whenever it
+/// renders, e.g. in `evaluate-template` or an edit.
+#[derive(Clone)]
+enum FilterInstance {
+    Own {
+        store: Arc<RwLock<wasmtime::Store<FilterHost>>>,
+        bindings: Arc<CustomFilter>,
+        _instance: wasmtime::component::Instance,
+    },
+    Template {
+        store: Arc<Mutex<wasmtime::Store<Host>>>,
+        guest: Arc<Guest>,
+        _instance: wasmtime::component::Instance,
+    },
 }
 
 impl CustomFilterParser {
-    pub fn load(name: &str, wasm_path: &Path) -> anyhow::Result<Self> {
-        let mut config = wasmtime::Config::new();
-        config.wasm_component_model(true);
-        let engine = wasmtime::Engine::new(&config).expect("shoulda engined");
-    
-        let component = wasmtime::component::Component::from_file(&engine, wasm_path).expect("shoulda loaded a component");
-    
-        let linker = wasmtime::component::Linker::new(&engine);
-    
-        let mut store = wasmtime::Store::new(&engine, ());
-
-        let (bindings, instance) = CustomFilter::instantiate(&mut store, &component, &linker).expect("should instantiated");
-    
-        Ok(Self {
-            name: name.to_owned(),
+    /// Loads a component that provides a single filter, to be registered as `name`.
+    pub fn load(name: &str, files: &TemplateFiles, wasm_path: &Path, components: &ComponentCache) -> anyhow::Result<Self> {
+        let mut filters = Self::load_pack("", files, wasm_path, components)?;
+        if filters.len() != 1 {
+            anyhow::bail!(
+                "Filter '{name}' is {}, which provides {} filters: register it as a filter pack instead",
+                files.display(wasm_path),
+                filters.len()
+            );
+        }
+        let mut filter = filters.remove(0);
+        filter.name = name.to_owned();
+        Ok(filter)
+    }
+
+    /// Loads a component that provides any number of filters, each to be registered
+    /// as its described name with `prefix` in front. The filters share one instance.
+    pub fn load_pack(prefix: &str, files: &TemplateFiles, wasm_path: &Path, components: &ComponentCache) -> anyhow::Result<Vec<Self>> {
+        let engine = components.engine();
+
+        let component = World::FILTER.load(files, wasm_path, components)?;
+
+        let mut linker = wasmtime::component::Linker::new(engine);
+        CustomFilter::add_to_linker(&mut linker, |state: &mut FilterHost| state)?;
+
+        let mut store = FilterHost::store(engine)?;
+
+        let (bindings, instance) = CustomFilter::instantiate(&mut store, &component, &linker)
+            .with_context(|| World::FILTER.instantiate_context(files, wasm_path))?;
+
+        let descriptions = bindings.fermyon_spin_template_filter_types().call_describe(&mut store)
+            .with_context(|| format!("Failed to get the filter descriptions from {}", files.display(wasm_path)))?;
+
+        let instance = FilterInstance::Own {
             store: Arc::new(RwLock::new(store)),
             bindings: Arc::new(bindings),
             _instance: instance,
+        };
+        Ok(Self::from_descriptions(prefix, wasm_path, descriptions, instance))
+    }
+
+    /// Loads the filters that the template component exports, if it exports any.
+    /// These are registered under their described names. `store` and `instance` must
+    /// be an instance of the template component set aside for running them.
+    pub fn load_from_template(files: &TemplateFiles, template_path: &Path, store: Arc<Mutex<wasmtime::Store<Host>>>, instance: wasmtime::component::Instance) -> anyhow::Result<Vec<Self>> {
+        let (guest, descriptions) = {
+            let mut store = store.lock().unwrap();
+            let guest = {
+                let mut exports = instance.exports(&mut *store);
+                let Some(mut filters) = exports.instance(FILTER_INTERFACE) else {
+                    return Ok(vec![]);
+                };
+                Guest::new(&mut filters)
+                    .with_context(|| format!("The filters exported by {} don't match {FILTER_INTERFACE}", files.display(template_path)))?
+            };
+            let descriptions = guest.call_describe(&mut *store)
+                .with_context(|| format!("Failed to get the filter descriptions from {}", files.display(template_path)))?;
+            (guest, descriptions)
+        };
+
+        let instance = FilterInstance::Template {
+            store,
+            guest: Arc::new(guest),
+            _instance: instance,
+        };
+        Ok(Self::from_descriptions("", template_path, descriptions, instance))
+    }
+
+    fn from_descriptions(prefix: &str, source: &Path, descriptions: Vec<FilterDescription>, instance: FilterInstance) -> Vec<Self> {
+        descriptions.into_iter()
+            .map(|description| Self {
+                name: format!("{prefix}{}", description.name),
+                source: source.to_owned(),
+                positional_reflection: parameter_reflection(&description.positional),
+                keyword_reflection: parameter_reflection(&description.keyword),
+                description,
+                instance: instance.clone(),
+            })
+            .collect()
+    }
+
+    /// The name the filter describes itself as having, which may not be the
+    /// name that the template registered it under.
+    pub fn described_name(&self) -> &str {
+        &self.description.name
+    }
+
+    /// The Wasm file that provides the filter, relative to the template.
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+}
+
+/// The state of a filter component's store: the variables of the render in
+/// progress, which filters can read through the `variables` import, and the
+/// limits that stop a buggy or malicious filter hanging or exhausting the machine.
+/// Tag components use the same state.
+pub(crate) struct FilterHost {
+    pub(crate) variables: BTreeMap<String, String>,
+    pub(crate) limits: Limits,
+}
+
+impl FilterHost {
+    /// A store for a newly loaded component, fuelled for its `describe` call.
+    pub(crate) fn store(engine: &wasmtime::Engine) -> anyhow::Result<wasmtime::Store<Self>> {
+        let host = Self { variables: BTreeMap::new(), limits: Limits::FILTER };
+        let mut store = wasmtime::Store::new(engine, host);
+        store.limiter(|state| &mut state.limits);
+        Limits::FILTER.refuel(&mut store)?;
+        Ok(store)
+    }
+}
+
+impl fermyon::spin_template_filter::variables::Host for FilterHost {
+    fn get(&mut self, name: String) -> Option<String> {
+        self.variables.get(&name).cloned()
+    }
+
+    fn names(&mut self) -> Vec<String> {
+        self.variables.keys().cloned().collect()
+    }
+}
+
+impl fermyon::spin_template_filter::variables::Host for Host {
+    fn get(&mut self, name: String) -> Option<String> {
+        self.variables.get(&name).cloned()
+    }
+
+    fn names(&mut self) -> Vec<String> {
+        self.variables.keys().cloned().collect()
+    }
+}
+
+// Liquid wants parameter reflections to be 'static. Filters are loaded once per run
+// so we leak them rather than bending the design around it.
+fn parameter_reflection(parameters: &[Parameter]) -> &'static [liquid_core::parser::ParameterReflection] {
+    let reflection = parameters.iter()
+        .map(|p| liquid_core::parser::ParameterReflection {
+            name: p.name.clone().leak(),
+            description: p.description.clone().leak(),
+            is_optional: !p.required,
         })
+        .collect::<Vec<_>>();
+    reflection.leak()
+}
+
+impl CustomFilterParser {
+    // Same checks (and messages) as filters derived with `liquid_derive::FilterParameters`
+    fn check_arguments(&self, positional: &[Expression], keyword: &[(String, Expression)]) -> liquid_core::Result<()> {
+        let signature = &self.description;
+
+        if positional.len() > signature.positional.len() {
+            return Err(liquid_err("Invalid number of positional arguments".to_owned())
+                .context("cause", format!("expected at most {} positional arguments", signature.positional.len())));
+        }
+        let required = signature.positional.iter().filter(|p| p.required).count();
+        if positional.len() < required {
+            return Err(liquid_err("Invalid number of arguments".to_owned())
+                .context("cause", format!("expected at least {required} positional arguments")));
+        }
+
+        for (name, _) in keyword {
+            if !signature.keyword.iter().any(|p| &p.name == name) {
+                return Err(liquid_err("Unexpected named argument".to_owned())
+                    .context("argument", name.to_owned()));
+            }
+        }
+        for p in signature.keyword.iter().filter(|p| p.required) {
+            if !keyword.iter().any(|(name, _)| name == &p.name) {
+                return Err(liquid_err("Missing required named argument".to_owned())
+                    .context("argument", p.name.to_owned()));
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -53,12 +239,18 @@ impl Debug for CustomFilterParser {
 impl ParseFilter for CustomFilterParser {
     fn parse(
         &self,
-        _arguments: liquid_core::parser::FilterArguments,
+        arguments: liquid_core::parser::FilterArguments,
     ) -> liquid_core::Result<Box<dyn Filter>> {
+        let positional = arguments.positional.collect::<Vec<_>>();
+        let keyword = arguments.keyword.map(|(k, v)| (k.to_owned(), v)).collect::<Vec<_>>();
+        self.check_arguments(&positional, &keyword)?;
+
         Ok(Box::new(CustomFilterRunner {
             name: self.name.to_owned(),
-            store: self.store.clone(),
-            bindings: self.bindings.clone(),
+            export: self.description.name.to_owned(),
+            positional,
+            keyword,
+            instance: self.instance.clone(),
         }))
     }
 
@@ -67,30 +259,30 @@ impl ParseFilter for CustomFilterParser {
     }
 }
 
-const EMPTY: [liquid_core::parser::ParameterReflection; 0] = [];
-
 impl liquid_core::FilterReflection for CustomFilterParser {
     fn name(&self) -> &str {
         &self.name
     }
 
     fn description(&self) -> &str {
-        ""
+        &self.description.description
     }
 
     fn positional_parameters(&self) -> &'static [liquid_core::parser::ParameterReflection] {
-        &EMPTY
+        self.positional_reflection
     }
 
     fn keyword_parameters(&self) -> &'static [liquid_core::parser::ParameterReflection] {
-        &EMPTY
+        self.keyword_reflection
     }
 }
 
 struct CustomFilterRunner {
     name: String,
-    store: Arc<RwLock<wasmtime::Store<()>>>,
-    bindings: Arc<CustomFilter>,
+    export: String,
+    positional: Vec<Expression>,
+    keyword: Vec<(String, Expression)>,
+    instance: FilterInstance,
 }
 
 impl Debug for CustomFilterRunner {
@@ -103,7 +295,15 @@ impl Debug for CustomFilterRunner {
 
 impl Display for CustomFilterRunner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.name)
+        let args = self.positional.iter()
+            .map(|e| e.to_string())
+            .chain(self.keyword.iter().map(|(k, e)| format!("{k}: {e}")))
+            .collect::<Vec<_>>();
+        if args.is_empty() {
+            f.write_str(&self.name)
+        } else {
+            write!(f, "{}: {}", self.name, args.join(", "))
+        }
     }
 }
 
@@ -111,35 +311,189 @@ impl Filter for CustomFilterRunner {
     fn evaluate(
         &self,
         input: &dyn ValueView,
-        _runtime: &dyn Runtime,
+        runtime: &dyn Runtime,
     ) -> Result<liquid::model::Value, liquid_core::error::Error> {
-        let input_str = self.liquid_value_as_string(input)?;
-        let mut store = self.store.write().unwrap();
-        match self.bindings.fermyon_spin_template_filter_types().call_exec(&mut *store, &input_str) {
-            Ok(Ok(text)) => Ok(to_liquid_value(text)),
+        let input = to_wit_value(input);
+        let args = self.evaluate_arguments(runtime)?;
+        let value = self.instance.exec(&self.name, &self.export, input, args, variables(runtime))?;
+        to_liquid_value(&value).map_err(|e| e.context("filter", self.name.to_owned()))
+    }
+}
+
+impl FilterInstance {
+    fn exec(&self, name: &str, export: &str, input: Vec<ValueNode>, args: Arguments, variables: BTreeMap<String, String>) -> Result<Vec<ValueNode>, liquid::Error> {
+        let (result, limits) = match self {
+            Self::Own { store, bindings, .. } => {
+                let mut store = store.write().unwrap();
+                store.data_mut().variables = variables;
+                let limits = store.data().limits;
+                limits.refuel(&mut store).map_err(|e| liquid_err(e.to_string()))?;
+                (bindings.fermyon_spin_template_filter_types().call_exec(&mut *store, export, &input, &args), limits)
+            }
+            Self::Template { store, guest, .. } => {
+                let mut store = store.lock().unwrap();
+                store.data_mut().variables = variables;
+                let limits = store.data().limits;
+                limits.refuel(&mut store).map_err(|e| liquid_err(e.to_string()))?;
+                (guest.call_exec(&mut *store, export, &input, &args), limits)
+            }
+        };
+
+        match result {
+            Ok(Ok(value)) => Ok(value),
             Ok(Err(s)) => Err(liquid_err(s)),
-            Err(trap) => Err(liquid_err(format!("{:?}", trap))),
+            Err(trap) => match LimitExceeded::of(&trap) {
+                Some(limit) => Err(liquid_err(limit.message(&format!("Filter '{name}'"), &limits))),
+                None => Err(liquid_err(format!("{:?}", trap))),
+            },
         }
     }
 }
 
 impl CustomFilterRunner {
-    fn liquid_value_as_string(&self, input: &dyn ValueView) -> Result<String, liquid::Error> {
-        let str = input.as_scalar().map(|s| s.into_cow_str()).ok_or_else(|| {
-            liquid_err(format!(
-                "Filter '{}': no input or input is not a string",
-                self.name
-            ))
-        })?;
-        Ok(str.to_string())
+    fn evaluate_arguments(&self, runtime: &dyn Runtime) -> Result<Arguments, liquid::Error> {
+        let positional = self.positional.iter()
+            .map(|e| e.evaluate(runtime).map(|v| to_wit_value(v.as_view())))
+            .collect::<Result<_, _>>()?;
+        let keyword = self.keyword.iter()
+            .map(|(k, e)| e.evaluate(runtime).map(|v| (k.to_owned(), to_wit_value(v.as_view()))))
+            .collect::<Result<_, _>>()?;
+        Ok(Arguments { positional, keyword })
+    }
+}
+
+// The variables that the template is being rendered with, which are the execution
+// context's variables plus anything the template has assigned
+pub(crate) fn variables(runtime: &dyn Runtime) -> BTreeMap<String, String> {
+    runtime.roots().into_iter()
+        .filter_map(|name| {
+            let value = runtime.try_get(&[liquid_core::model::ScalarCow::new(name.clone())])?;
+            Some((name.to_string(), value.to_kstr().to_string()))
+        })
+        .collect()
+}
+
+fn to_wit_value(value: &dyn ValueView) -> Vec<ValueNode> {
+    let mut nodes = vec![];
+    push_wit_node(value, &mut nodes);
+    nodes
+}
+
+// Pushes the node for `value` followed by the nodes for any members, and returns
+// the index of the node for `value`
+fn push_wit_node(value: &dyn ValueView, nodes: &mut Vec<ValueNode>) -> u32 {
+    let index = nodes.len();
+    nodes.push(ValueNode::Nil);  // placeholder so that members come after it
+
+    let node = if let Some(scalar) = value.as_scalar() {
+        match scalar.type_name() {
+            "boolean" => ValueNode::Bool(scalar.to_bool().unwrap_or_default()),
+            "whole number" => ValueNode::Int(scalar.to_integer().unwrap_or_default()),
+            "fractional number" => ValueNode::Float(scalar.to_float().unwrap_or_default()),
+            _ => ValueNode::String(scalar.into_string().to_string()),
+        }
+    } else if let Some(array) = value.as_array() {
+        let members = array.values().map(|v| push_wit_node(v, nodes)).collect();
+        ValueNode::Array(members)
+    } else if let Some(object) = value.as_object() {
+        let members = object.iter().map(|(k, v)| (k.to_string(), push_wit_node(v, nodes))).collect();
+        ValueNode::Object(members)
+    } else {
+        ValueNode::Nil
+    };
+
+    nodes[index] = node;
+    index as u32
+}
+
+fn to_liquid_value(nodes: &[ValueNode]) -> Result<liquid::model::Value, liquid::Error> {
+    if nodes.is_empty() {
+        return Err(liquid_err("Filter returned an empty value (expected at least a root node)".to_owned()));
     }
+    let mut used = vec![false; nodes.len()];
+    used[0] = true;
+    to_liquid_value_at(nodes, 0, &mut used)
 }
 
-fn to_liquid_value(value: String) -> liquid::model::Value {
-    liquid::model::Value::Scalar(liquid::model::Scalar::from(value))
+// `used` marks the nodes that already have a place in the value. Each node may only
+// be a member of one container, so that the value built is no bigger than the list
+// the filter returned.
+fn to_liquid_value_at(nodes: &[ValueNode], index: usize, used: &mut [bool]) -> Result<liquid::model::Value, liquid::Error> {
+    let mut member = |member: u32| {
+        let member = member as usize;
+        // Requiring members to come after their container rules out cycles
+        if member <= index || member >= nodes.len() {
+            Err(liquid_err(format!("Filter returned an invalid value: node {index} refers to node {member}")))
+        } else if std::mem::replace(&mut used[member], true) {
+            Err(liquid_err(format!("Filter returned an invalid value: node {member} is a member of more than one container")))
+        } else {
+            to_liquid_value_at(nodes, member, used)
+        }
+    };
+
+    let value = match &nodes[index] {
+        ValueNode::Nil => liquid::model::Value::Nil,
+        ValueNode::Bool(b) => liquid::model::Value::scalar(*b),
+        ValueNode::Int(i) => liquid::model::Value::scalar(*i),
+        ValueNode::Float(f) => liquid::model::Value::scalar(*f),
+        ValueNode::String(s) => liquid::model::Value::scalar(s.to_owned()),
+        ValueNode::Array(members) => liquid::model::Value::Array(
+            members.iter().map(|m| member(*m)).collect::<Result<_, _>>()?
+        ),
+        ValueNode::Object(members) => liquid::model::Value::Object(
+            members.iter().map(|(k, m)| member(*m).map(|v| (k.to_owned().into(), v))).collect::<Result<_, _>>()?
+        ),
+    };
+    Ok(value)
 }
 
-fn liquid_err(text: String) -> liquid_core::error::Error {
+pub(crate) fn liquid_err(text: String) -> liquid_core::error::Error {
     liquid_core::error::Error::with_msg(text)
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values_round_trip() {
+        let value = liquid::model::value!({
+            "name": "spork",
+            "count": 3,
+            "ratio": 0.5,
+            "enabled": true,
+            "nothing": nil,
+            "routes": ["/api/...", { "private": true }],
+        });
+
+        let nodes = to_wit_value(&value);
+        assert!(matches!(nodes[0], ValueNode::Object(_)));
+        assert_eq!(value, to_liquid_value(&nodes).unwrap());
+    }
+
+    #[test]
+    fn filters_see_render_variables() {
+        let globals = liquid::object!({ "project-name": "pet-store", "count": 3 });
+        let runtime = liquid_core::runtime::RuntimeBuilder::new().set_globals(&globals).build();
+
+        let variables = variables(&runtime);
+        assert_eq!(Some("pet-store"), variables.get("project-name").map(|v| v.as_str()));
+        assert_eq!(Some("3"), variables.get("count").map(|v| v.as_str()));
+    }
+
+    #[test]
+    fn cyclic_values_are_rejected() {
+        let nodes = vec![ValueNode::Array(vec![1]), ValueNode::Array(vec![0])];
+        assert!(to_liquid_value(&nodes).is_err());
+        assert!(to_liquid_value(&[]).is_err());
+    }
+
+    #[test]
+    fn shared_members_are_rejected() {
+        // Each array lists the next one twice, which would double the value's size at every level
+        let mut nodes: Vec<_> = (1..40).map(|i| ValueNode::Array(vec![i, i])).collect();
+        nodes.push(ValueNode::Nil);
+        let err = to_liquid_value(&nodes).unwrap_err().to_string();
+        assert!(err.contains("node 39 is a member of more than one container"), "{err}");
+    }
+}
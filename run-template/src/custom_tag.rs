@@ -0,0 +1,290 @@
+use std::{
+    fmt::Debug,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use anyhow::Context;
+use liquid_core::{
+    parser::{BlockReflection, TagBlock, TagReflection, TagTokenIter},
+    runtime::{Renderable, Template},
+    error::ResultLiquidReplaceExt,
+    Expression, Language, ParseBlock, ParseTag, Runtime, ValueView,
+};
+
+use crate::component_cache::ComponentCache;
+use crate::custom_filter::{liquid_err, variables, FilterHost};
+use crate::limits::LimitExceeded;
+use crate::template_files::TemplateFiles;
+use crate::world::World;
+
+wasmtime::component::bindgen!({
+    path: "../wit/filter",
+    world: "custom-tag",
+    with: {
+        "fermyon:spin-template-filter/variables": crate::custom_filter::fermyon::spin_template_filter::variables,
+    },
+});
+
+use exports::fermyon::spin_template_filter::tags::TagDescription;
+
+/// The tags and blocks registered by `liquid::ParserBuilder::with_stdlib`, which
+/// tag components may not replace. Liquid doesn't let us ask a parser what it has
+/// registered, so this must be kept in step with the Liquid version.
+pub(crate) const STANDARD_TAGS: &[&str] = &[
+    "assign", "break", "continue", "cycle", "include", "increment", "decrement", "render",
+    "raw", "if", "unless", "ifchanged", "for", "tablerow", "comment", "capture", "case",
+];
+
+/// A tag or block provided by a tag component. Blocks are registered as blocks,
+/// with an end tag of `end` followed by their name, and everything else as tags.
+#[derive(Clone)]
+pub struct CustomTagParser {
+    name: String,
+    end_tag: String,
+    source: PathBuf,
+    description: TagDescription,
+    instance: TagInstance,
+}
+
+#[derive(Clone)]
+struct TagInstance {
+    store: Arc<RwLock<wasmtime::Store<FilterHost>>>,
+    bindings: Arc<CustomTag>,
+    _instance: wasmtime::component::Instance,
+}
+
+impl CustomTagParser {
+    /// Loads a component that provides any number of tags, each to be registered
+    /// as its described name with `prefix` in front. The tags share one instance.
+    pub fn load_pack(prefix: &str, files: &TemplateFiles, wasm_path: &Path, components: &ComponentCache) -> anyhow::Result<Vec<Self>> {
+        let engine = components.engine();
+
+        let component = World::TAG.load(files, wasm_path, components)?;
+
+        let mut linker = wasmtime::component::Linker::new(engine);
+        // The only import is shared with filter components, so it's linked from their bindings
+        crate::custom_filter::fermyon::spin_template_filter::variables::add_to_linker(&mut linker, |state: &mut FilterHost| state)?;
+
+        let mut store = FilterHost::store(engine)?;
+
+        let (bindings, instance) = CustomTag::instantiate(&mut store, &component, &linker)
+            .with_context(|| World::TAG.instantiate_context(files, wasm_path))?;
+
+        let descriptions = bindings.fermyon_spin_template_filter_tags().call_describe(&mut store)
+            .with_context(|| format!("Failed to get the tag descriptions from {}", files.display(wasm_path)))?;
+
+        let instance = TagInstance {
+            store: Arc::new(RwLock::new(store)),
+            bindings: Arc::new(bindings),
+            _instance: instance,
+        };
+        let tags = descriptions.into_iter()
+            .map(|description| {
+                let name = format!("{prefix}{}", description.name);
+                Self {
+                    end_tag: format!("end{name}"),
+                    name,
+                    source: wasm_path.to_owned(),
+                    description,
+                    instance: instance.clone(),
+                }
+            })
+            .collect();
+        Ok(tags)
+    }
+
+    /// Adds the tag to the parser, as a block if it is one.
+    pub fn register<P: liquid::partials::PartialCompiler>(self, builder: liquid::ParserBuilder<P>) -> liquid::ParserBuilder<P> {
+        if self.description.block {
+            builder.block(self)
+        } else {
+            builder.tag(self)
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name the tag describes itself as having, which may not be the
+    /// name that the template registered it under.
+    pub fn described_name(&self) -> &str {
+        &self.description.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description.description
+    }
+
+    pub fn is_block(&self) -> bool {
+        self.description.block
+    }
+
+    /// The Wasm file that provides the tag, relative to the template.
+    pub fn source(&self) -> &Path {
+        &self.source
+    }
+
+    fn renderer(&self, arguments: TagTokenIter, body: Option<Template>) -> liquid_core::Result<Box<dyn Renderable>> {
+        Ok(Box::new(CustomTagRenderer {
+            name: self.name.clone(),
+            export: self.description.name.clone(),
+            args: parse_arguments(arguments)?,
+            body,
+            instance: self.instance.clone(),
+        }))
+    }
+}
+
+// Arguments are values separated by commas, as in `{% cycle "a", "b" %}`
+fn parse_arguments(mut arguments: TagTokenIter) -> liquid_core::Result<Vec<Expression>> {
+    let mut args = vec![];
+    while let Some(token) = arguments.next() {
+        args.push(token.expect_value().into_result()?);
+        if let Some(separator) = arguments.next() {
+            if separator.as_str() != "," {
+                return Err(separator.raise_custom_error("\",\" expected."));
+            }
+        }
+    }
+    Ok(args)
+}
+
+impl Debug for CustomTagParser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomTagParser")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl ParseTag for CustomTagParser {
+    fn parse(&self, arguments: TagTokenIter, _options: &Language) -> liquid_core::Result<Box<dyn Renderable>> {
+        self.renderer(arguments, None)
+    }
+
+    fn reflection(&self) -> &dyn TagReflection {
+        self
+    }
+}
+
+impl TagReflection for CustomTagParser {
+    fn tag(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description.description
+    }
+}
+
+impl ParseBlock for CustomTagParser {
+    fn parse(&self, arguments: TagTokenIter, mut block: TagBlock, options: &Language) -> liquid_core::Result<Box<dyn Renderable>> {
+        let body = Template::new(block.parse_all(options)?);
+        block.assert_empty();
+        self.renderer(arguments, Some(body))
+    }
+
+    fn reflection(&self) -> &dyn BlockReflection {
+        self
+    }
+}
+
+impl BlockReflection for CustomTagParser {
+    fn start_tag(&self) -> &str {
+        &self.name
+    }
+
+    fn end_tag(&self) -> &str {
+        &self.end_tag
+    }
+
+    fn description(&self) -> &str {
+        &self.description.description
+    }
+}
+
+struct CustomTagRenderer {
+    name: String,
+    export: String,
+    args: Vec<Expression>,
+    body: Option<Template>,
+    instance: TagInstance,
+}
+
+impl Debug for CustomTagRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomTag")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl Renderable for CustomTagRenderer {
+    fn render_to(&self, writer: &mut dyn Write, runtime: &dyn Runtime) -> liquid_core::Result<()> {
+        let args = self.args.iter()
+            .map(|e| e.evaluate(runtime).map(|v| v.to_kstr().to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let body = self.body.as_ref()
+            .map(|body| body.render(runtime))
+            .transpose()?;
+
+        let mut store = self.instance.store.write().unwrap();
+        store.data_mut().variables = variables(runtime);
+        let limits = store.data().limits;
+        limits.refuel(&mut store).map_err(|e| liquid_err(e.to_string()))?;
+        let result = self.instance.bindings.fermyon_spin_template_filter_tags()
+            .call_render(&mut *store, &self.export, &args, body.as_deref());
+
+        let text = match result {
+            Ok(Ok(text)) => text,
+            Ok(Err(s)) => return Err(liquid_err(s).context("tag", self.name.clone())),
+            Err(trap) => return Err(match LimitExceeded::of(&trap) {
+                Some(limit) => liquid_err(limit.message(&format!("Tag '{}'", self.name), &limits)),
+                None => liquid_err(format!("{:?}", trap)),
+            }),
+        };
+        writer.write_all(text.as_bytes()).replace("Failed to render")
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample_tags(prefix: &str) -> Vec<CustomTagParser> {
+        let files = TemplateFiles::Dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("../sample-template/template"));
+        CustomTagParser::load_pack(prefix, &files, Path::new("tags/sample_tag.wasm"), &ComponentCache::with_cache_dir(None).unwrap()).unwrap()
+    }
+
+    fn render(tags: Vec<CustomTagParser>, template: &str, globals: &liquid::Object) -> liquid_core::Result<String> {
+        let parser = tags.into_iter()
+            .fold(liquid::ParserBuilder::with_stdlib(), |builder, tag| tag.register(builder))
+            .build()?;
+        parser.parse(template)?.render(globals)
+    }
+
+    #[test]
+    fn tags_get_their_arguments_and_the_variables() {
+        let tags = sample_tags("");
+        let globals = liquid::object!({ "authors": "Fermyon", "holder": "Spork Inc" });
+        assert_eq!("Copyright Fermyon. Licensed under MIT.", render(tags.clone(), r#"{% license "MIT" %}"#, &globals).unwrap());
+        assert_eq!("Copyright Spork Inc. Licensed under Apache-2.0.", render(tags.clone(), r#"{% license "Apache-2.0", holder %}"#, &globals).unwrap());
+
+        let err = render(tags, r#"{% license "MIT" "Apache-2.0" %}"#, &globals).unwrap_err();
+        assert!(err.to_string().contains("\",\" expected"), "{err}");
+    }
+
+    #[test]
+    fn blocks_get_their_rendered_body() {
+        let template = "{% sample_region \"deps\", \"//\" %}\nserde = \"{{ version }}\"\n{% endsample_region %}";
+        let globals = liquid::object!({ "version": "1.0" });
+        assert_eq!(
+            "// region: deps\nserde = \"1.0\"\n// endregion: deps",
+            render(sample_tags("sample_"), template, &globals).unwrap()
+        );
+    }
+}
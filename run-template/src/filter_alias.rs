@@ -0,0 +1,279 @@
+use std::{collections::HashMap, fmt::{Debug, Display}, sync::Arc};
+
+use liquid_core::{parser::Language, runtime::StackFrame, Filter, ParseFilter, Renderable, Runtime, ValueView};
+
+// Filter aliases are named pipelines defined in the `[filter_alias]` table of
+// `spin-template.toml`, e.g. `component_id = "kebab_case | truncate: 40"`, which
+// templates can then use like any other filter: `{{ project-name | component_id }}`.
+//
+// Each alias is compiled into a tiny template that pipes its input through the
+// pipeline, so aliases always produce text. An alias may use other aliases, so
+// they are compiled in dependency order, which is also where cycles are found.
+// The template is rendered on top of the caller's runtime, so filters in the
+// pipeline see the same variables as they would if the pipeline were written out.
+
+#[derive(Clone)]
+pub struct FilterAliasParser {
+    name: String,
+    description: String,
+    template: Arc<liquid_core::Template>,
+}
+
+/// Checks the aliases and compiles them into filters. `filters` are the filters
+/// that aliases can use, other than the Liquid standard library and each other.
+pub fn compile(aliases: &HashMap<String, String>, filters: &[Box<dyn ParseFilter>]) -> anyhow::Result<Vec<FilterAliasParser>> {
+    let known = crate::filters::standard_filters().iter()
+        .chain(filters)
+        .map(|f| f.reflection().name().to_owned())
+        .collect::<Vec<_>>();
+
+    for (name, pipeline) in aliases {
+        if known.contains(name) {
+            anyhow::bail!("Filter alias '{name}' has the same name as a filter");
+        }
+        for used in filter_names(pipeline) {
+            if !known.contains(&used) && !aliases.contains_key(&used) {
+                anyhow::bail!("Filter alias '{name}' uses '{used}', which is not a filter or filter alias");
+            }
+        }
+    }
+
+    let mut compiled: Vec<FilterAliasParser> = vec![];
+    for name in dependency_order(aliases)? {
+        let pipeline = &aliases[&name];
+        // The template is only an output of a filter chain, so it needs filters but no tags
+        let mut language = Language::empty();
+        for filter in crate::filters::standard_filters().into_iter()
+            .chain(filters.iter().cloned())
+            .chain(compiled.iter().map(|a| Box::new(a.clone()) as Box<dyn ParseFilter>))
+        {
+            language.filters.register(filter.reflection().name().to_owned(), filter);
+        }
+        let template = liquid_core::parser::parse(&format!("{{{{ input | {pipeline} }}}}"), &language)
+            .map(liquid_core::Template::new)
+            .map_err(|e| anyhow::anyhow!("Filter alias '{name}' (`{pipeline}`) is not valid: {e}"))?;
+        compiled.push(FilterAliasParser {
+            name: name.clone(),
+            description: format!("Alias for `{pipeline}`."),
+            template: Arc::new(template),
+        });
+    }
+
+    compiled.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(compiled)
+}
+
+// The names of the filters in a pipeline such as `kebab_case | truncate: 40, "|"`
+fn filter_names(pipeline: &str) -> Vec<String> {
+    let mut segments = vec![String::new()];
+    let mut quote = None;
+    for c in pipeline.chars() {
+        match (quote, c) {
+            (None, '|') => segments.push(String::new()),
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            _ => {}
+        }
+        if quote.is_some() || c != '|' {
+            segments.last_mut().unwrap().push(c);
+        }
+    }
+    segments.iter()
+        .map(|s| s.trim().split([':', ' ']).next().unwrap_or_default().to_owned())
+        .collect()
+}
+
+// Orders aliases so that each one comes after any aliases it uses
+fn dependency_order(aliases: &HashMap<String, String>) -> anyhow::Result<Vec<String>> {
+    fn visit(name: &str, aliases: &HashMap<String, String>, path: &mut Vec<String>, order: &mut Vec<String>) -> anyhow::Result<()> {
+        if order.iter().any(|n| n == name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|n| n == name) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(name.to_owned());
+            anyhow::bail!("Filter aliases refer to each other in a cycle: {}", cycle.join(" -> "));
+        }
+        path.push(name.to_owned());
+        for used in filter_names(&aliases[name]) {
+            if aliases.contains_key(&used) {
+                visit(&used, aliases, path, order)?;
+            }
+        }
+        path.pop();
+        order.push(name.to_owned());
+        Ok(())
+    }
+
+    let mut names = aliases.keys().collect::<Vec<_>>();
+    names.sort();
+
+    let mut order = vec![];
+    for name in names {
+        visit(name, aliases, &mut vec![], &mut order)?;
+    }
+    Ok(order)
+}
+
+impl Debug for FilterAliasParser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilterAliasParser")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl ParseFilter for FilterAliasParser {
+    fn parse(
+        &self,
+        mut arguments: liquid_core::parser::FilterArguments,
+    ) -> liquid_core::Result<Box<dyn Filter>> {
+        if arguments.positional.next().is_some() || arguments.keyword.next().is_some() {
+            return Err(liquid_core::Error::with_msg("Invalid number of arguments")
+                .context("cause", "filter aliases take no arguments"));
+        }
+        Ok(Box::new(FilterAlias {
+            name: self.name.clone(),
+            template: self.template.clone(),
+        }))
+    }
+
+    fn reflection(&self) -> &dyn liquid_core::FilterReflection {
+        self
+    }
+}
+
+impl liquid_core::FilterReflection for FilterAliasParser {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn positional_parameters(&self) -> &'static [liquid_core::parser::ParameterReflection] {
+        &[]
+    }
+
+    fn keyword_parameters(&self) -> &'static [liquid_core::parser::ParameterReflection] {
+        &[]
+    }
+}
+
+struct FilterAlias {
+    name: String,
+    template: Arc<liquid_core::Template>,
+}
+
+impl Debug for FilterAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilterAlias")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl Display for FilterAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl Filter for FilterAlias {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        runtime: &dyn Runtime,
+    ) -> Result<liquid::model::Value, liquid_core::error::Error> {
+        let globals = liquid::object!({ "input": input.to_value() });
+        let runtime = StackFrame::new(runtime, &globals);
+        let text = self.template.render(&runtime)
+            .map_err(|e| e.context("filter alias", self.name.clone()))?;
+        Ok(liquid::model::Value::scalar(text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn aliases(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn render(aliases: &HashMap<String, String>, template: &str) -> String {
+        render_with(aliases, template, &liquid::Object::new())
+    }
+
+    fn render_with(aliases: &HashMap<String, String>, template: &str, globals: &liquid::Object) -> String {
+        let mut filters = crate::filters::builtin_filters();
+        filters.push(Box::new(VariablesFilterParser));
+        let parser = compile(aliases, &filters).unwrap().into_iter()
+            .map(|alias| Box::new(alias) as Box<dyn ParseFilter>)
+            .chain(filters)
+            .fold(liquid::ParserBuilder::with_stdlib(), |builder, filter| builder.filter(filter))
+            .build()
+            .unwrap();
+        parser.parse(template).unwrap().render(globals).unwrap()
+    }
+
+    // Like a filter component, reads the variables of the file being rendered
+    #[derive(Clone, liquid_derive::ParseFilter, liquid_derive::FilterReflection)]
+    #[filter(name = "variables", description = "Lists the render variables.", parsed(VariablesFilter))]
+    struct VariablesFilterParser;
+
+    #[derive(Debug, Default, liquid_derive::Display_filter)]
+    #[name = "variables"]
+    struct VariablesFilter;
+
+    impl Filter for VariablesFilter {
+        fn evaluate(&self, input: &dyn ValueView, runtime: &dyn Runtime) -> Result<liquid::model::Value, liquid_core::error::Error> {
+            let variables = crate::custom_filter::variables(runtime).into_iter()
+                .filter(|(name, _)| name != "input")
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>();
+            Ok(liquid::model::Value::scalar(format!("{}: {}", input.to_kstr(), variables.join(", "))))
+        }
+    }
+
+    #[test]
+    fn aliases_run_their_pipelines() {
+        let aliases = aliases(&[
+            ("component_id", "kebab_case | truncate: 8, ''"),
+            ("shouty_id", "component_id | upcase"),
+        ]);
+        assert_eq!("pet-stor", render(&aliases, "{{ 'PetStoreApp' | component_id }}"));
+        assert_eq!("PET-STOR", render(&aliases, "{{ 'PetStoreApp' | shouty_id }}"));
+    }
+
+    #[test]
+    fn aliased_filters_see_render_variables() {
+        let aliases = aliases(&[("described", "upcase | variables")]);
+        let globals = liquid::object!({ "project-name": "spork" });
+        assert_eq!(
+            render_with(&aliases, "{{ 'x' | upcase | variables }}", &globals),
+            render_with(&aliases, "{{ 'x' | described }}", &globals),
+        );
+        assert_eq!("X: project-name=spork", render_with(&aliases, "{{ 'x' | described }}", &globals));
+    }
+
+    #[test]
+    fn pipelines_are_split_outside_quotes() {
+        assert_eq!(vec!["split", "join"], filter_names("split: '|' | join: \", \""));
+    }
+
+    #[test]
+    fn alias_cycles_are_rejected() {
+        let aliases = aliases(&[("a", "b | upcase"), ("b", "c"), ("c", "a")]);
+        let err = compile(&aliases, &[]).expect_err("cycle should be rejected");
+        assert_eq!("Filter aliases refer to each other in a cycle: a -> b -> c -> a", err.to_string());
+    }
+
+    #[test]
+    fn unknown_filters_are_rejected() {
+        let aliases = aliases(&[("a", "upcase | kebab")]);
+        let err = compile(&aliases, &crate::filters::builtin_filters()).expect_err("unknown filter should be rejected");
+        assert!(err.to_string().contains("'kebab'"), "{err}");
+    }
+}
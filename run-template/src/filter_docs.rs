@@ -0,0 +1,99 @@
+use liquid_core::{parser::ParameterReflection, FilterReflection};
+
+use crate::custom_tag::CustomTagParser;
+
+// Formats filter reflection for `run-template filters`, e.g.
+//
+//   truncate: length, [ellipsis]
+//       Shorten a string down to the number of characters passed as a parameter.
+//       length      The maximum length of the string, after which it will be truncated.
+//       ellipsis    (optional) The text appended to the end of the string if it is truncated.
+
+pub struct FilterDoc<'a> {
+    reflection: &'a dyn FilterReflection,
+    note: Option<String>,
+}
+
+impl<'a> FilterDoc<'a> {
+    pub fn new(reflection: &'a dyn FilterReflection) -> Self {
+        Self { reflection, note: None }
+    }
+
+    /// Adds a parenthesised note after the filter usage, e.g. where it came from.
+    pub fn with_note(self, note: impl Into<String>) -> Self {
+        Self { note: Some(note.into()), ..self }
+    }
+
+    fn usage(&self) -> String {
+        let positional = self.reflection.positional_parameters().iter()
+            .map(|p| optional_brackets(p, p.name.to_owned()));
+        let keyword = self.reflection.keyword_parameters().iter()
+            .map(|p| optional_brackets(p, format!("{0}: <{0}>", p.name)));
+        let args = positional.chain(keyword).collect::<Vec<_>>();
+
+        let name = self.reflection.name();
+        if args.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{name}: {}", args.join(", "))
+        }
+    }
+}
+
+pub fn print_section<'a>(title: &str, filters: impl Iterator<Item = FilterDoc<'a>>) {
+    let mut filters = filters.collect::<Vec<_>>();
+    filters.sort_by_key(|f| f.reflection.name().to_owned());
+
+    println!("{title}:");
+    if filters.is_empty() {
+        println!("  (none)");
+    }
+    for filter in &filters {
+        match &filter.note {
+            Some(note) => println!("  {} ({note})", filter.usage()),
+            None => println!("  {}", filter.usage()),
+        }
+        let description = filter.reflection.description();
+        if !description.is_empty() {
+            println!("      {description}");
+        }
+        let parameters = filter.reflection.positional_parameters().iter()
+            .chain(filter.reflection.keyword_parameters());
+        for p in parameters {
+            let optional = if p.is_optional { "(optional) " } else { "" };
+            println!("      {:<12}{optional}{}", p.name, p.description);
+        }
+    }
+    println!();
+}
+
+/// Prints custom tags in the same layout, e.g. `{% region ... %}...{% endregion %}`,
+/// each with a note saying where it came from.
+pub fn print_tag_section<'a>(title: &str, tags: impl Iterator<Item = (&'a CustomTagParser, String)>) {
+    println!("{title}:");
+    let mut tags = tags.collect::<Vec<_>>();
+    tags.sort_by_key(|(t, _)| t.name().to_owned());
+    if tags.is_empty() {
+        println!("  (none)");
+    }
+    for (tag, note) in &tags {
+        let usage = if tag.is_block() {
+            format!("{{% {0} ... %}}...{{% end{0} %}}", tag.name())
+        } else {
+            format!("{{% {} ... %}}", tag.name())
+        };
+        println!("  {usage} ({note})");
+        if !tag.description().is_empty() {
+            println!("      {}", tag.description());
+        }
+    }
+    println!();
+}
+
+fn optional_brackets(parameter: &ParameterReflection, text: String) -> String {
+    if parameter.is_optional {
+        format!("[{text}]")
+    } else {
+        text
+    }
+}
@@ -1,8 +1,8 @@
 // liquid_derive::FilterReflection violates this lint
 #![allow(clippy::box_default)]
 
-use heck::{ToKebabCase, ToSnakeCase, ToUpperCamelCase};
-use liquid_core::{Filter, ParseFilter, Runtime, ValueView};
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase, ToTrainCase, ToUpperCamelCase};
+use liquid_core::{Filter, FilterParameters, ParseFilter, Runtime, ValueView};
 use liquid_derive::FilterReflection;
 
 // Filters that are added to the Liquid parser and allow templates to specify
@@ -10,11 +10,58 @@ use liquid_derive::FilterReflection;
 
 // ADDING A FILTER HERE IS NOT ENOUGH.  You must also:
 //
-// * Register it with the template ParserBuilder in `Run::template_parser`.
-//   Otherwise Liquid won't know about it.
-// * Add a test using the registration name to `test_filters` in run.rs.
+// * Add it to `builtin_filters`. Otherwise Liquid won't know about it.
+// * Add a test using the registration name to `builtin_filters_are_registered`.
 //   This ensures that the name->parser->filter chain is hooked up correctly.
 
+/// The filters that run-template provides on top of the Liquid standard library.
+pub(crate) fn builtin_filters() -> Vec<Box<dyn ParseFilter>> {
+    vec![
+        Box::new(KebabCaseFilterParser),
+        Box::new(PascalCaseFilterParser),
+        Box::new(DottedPascalCaseFilterParser),
+        Box::new(SnakeCaseFilterParser),
+        Box::new(HttpWildcardFilterParser),
+        Box::new(SpinComponentIdFilterParser),
+        Box::new(SpinRouteFilterParser),
+        Box::new(SpinWasmArtifactFilterParser),
+        Box::new(CamelCaseFilterParser),
+        Box::new(ScreamingSnakeCaseFilterParser),
+        Box::new(TitleCaseFilterParser),
+        Box::new(TrainCaseFilterParser),
+        Box::new(RustIdentifierFilterParser),
+        Box::new(GoIdentifierFilterParser),
+        Box::new(JsIdentifierFilterParser),
+        Box::new(TomlEscapeFilterParser),
+        Box::new(JsonEscapeFilterParser),
+    ]
+}
+
+/// The filters registered by `liquid::ParserBuilder::with_stdlib`. Liquid doesn't
+/// let us ask a parser what it has registered, so this is only for documentation
+/// and must be kept in step with the Liquid version.
+pub(crate) fn standard_filters() -> Vec<Box<dyn ParseFilter>> {
+    use liquid_lib::stdlib;
+    vec![
+        Box::new(stdlib::Abs), Box::new(stdlib::Append), Box::new(stdlib::AtLeast),
+        Box::new(stdlib::AtMost), Box::new(stdlib::Capitalize), Box::new(stdlib::Ceil),
+        Box::new(stdlib::Compact), Box::new(stdlib::Concat), Box::new(stdlib::Date),
+        Box::new(stdlib::Default), Box::new(stdlib::DividedBy), Box::new(stdlib::Downcase),
+        Box::new(stdlib::Escape), Box::new(stdlib::EscapeOnce), Box::new(stdlib::First),
+        Box::new(stdlib::Floor), Box::new(stdlib::Join), Box::new(stdlib::Last),
+        Box::new(stdlib::Lstrip), Box::new(stdlib::Map), Box::new(stdlib::Minus),
+        Box::new(stdlib::Modulo), Box::new(stdlib::NewlineToBr), Box::new(stdlib::Plus),
+        Box::new(stdlib::Prepend), Box::new(stdlib::Remove), Box::new(stdlib::RemoveFirst),
+        Box::new(stdlib::Replace), Box::new(stdlib::ReplaceFirst), Box::new(stdlib::Reverse),
+        Box::new(stdlib::Round), Box::new(stdlib::Rstrip), Box::new(stdlib::Size),
+        Box::new(stdlib::Slice), Box::new(stdlib::Sort), Box::new(stdlib::SortNatural),
+        Box::new(stdlib::Split), Box::new(stdlib::Strip), Box::new(stdlib::StripHtml),
+        Box::new(stdlib::StripNewlines), Box::new(stdlib::Times), Box::new(stdlib::Truncate),
+        Box::new(stdlib::TruncateWords), Box::new(stdlib::Uniq), Box::new(stdlib::Upcase),
+        Box::new(stdlib::UrlDecode), Box::new(stdlib::UrlEncode), Box::new(stdlib::Where),
+    ]
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "kebab_case",
@@ -131,6 +178,159 @@ impl Filter for HttpWildcardFilter {
     }
 }
 
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "spin_component_id",
+    description = "Change text to a valid Spin component id (lowercase kebab-case words that start with letters).",
+    parsed(SpinComponentIdFilter)
+)]
+pub(crate) struct SpinComponentIdFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "spin_component_id"]
+struct SpinComponentIdFilter;
+
+impl Filter for SpinComponentIdFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(spin_component_id(&input).to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "spin_route",
+    description = "Change text to a Spin HTTP route as a TOML value, e.g. `\"/api/...\"`, or `{ private = true }` for `private`.",
+    parsed(SpinRouteFilter)
+)]
+pub(crate) struct SpinRouteFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "spin_route"]
+struct SpinRouteFilter;
+
+impl Filter for SpinRouteFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(spin_route(&input).to_value())
+    }
+}
+
+#[derive(Debug, liquid_derive::FilterParameters)]
+struct SpinWasmArtifactArgs {
+    #[parameter(
+        description = "The language the component is written in: rust, go, js, ts or python.",
+        arg_type = "str"
+    )]
+    language: liquid_core::Expression,
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "spin_wasm_artifact",
+    description = "Change a project name to the path of the Wasm file that the language's Spin tooling builds.",
+    parameters(SpinWasmArtifactArgs),
+    parsed(SpinWasmArtifactFilter)
+)]
+pub(crate) struct SpinWasmArtifactFilterParser;
+
+#[derive(Debug, liquid_derive::FromFilterParameters, liquid_derive::Display_filter)]
+#[name = "spin_wasm_artifact"]
+struct SpinWasmArtifactFilter {
+    #[parameters]
+    args: SpinWasmArtifactArgs,
+}
+
+impl Filter for SpinWasmArtifactFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        runtime: &dyn Runtime,
+    ) -> Result<liquid::model::Value, liquid_core::error::Error> {
+        let args = self.args.evaluate(runtime)?;
+
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        let artifact = spin_wasm_artifact(&input, args.language.as_str())
+            .ok_or_else(|| liquid_core::error::Error::with_msg("Unknown language")
+                .context("language", args.language.to_string())
+                .context("expected", "rust, go, js, ts or python"))?;
+        Ok(artifact.to_value())
+    }
+}
+
+// Spin component ids are kebab-case. To be safe with all versions of Spin, each
+// word must start with a letter, so a word starting with a digit is joined on to
+// the one before.
+fn spin_component_id(text: &str) -> String {
+    let kebab = text.to_kebab_case();
+    let words = kebab.split('-')
+        .map(|w| w.chars().filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit()).collect::<String>())
+        .filter(|w| !w.is_empty());
+
+    let mut id = String::new();
+    for word in words {
+        if word.starts_with(|c: char| c.is_ascii_digit()) {
+            if id.is_empty() {
+                id.push_str("component");
+            }
+        } else if !id.is_empty() {
+            id.push('-');
+        }
+        id.push_str(&word);
+    }
+
+    if id.is_empty() {
+        "component".to_owned()
+    } else {
+        id
+    }
+}
+
+fn spin_route(text: &str) -> String {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("private") {
+        return "{ private = true }".to_owned();
+    }
+
+    let segments = text.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+    let mut route = format!("/{}", segments.join("/"));
+    if text.ends_with('/') && route != "/" {
+        route.push('/');
+    }
+    format!("\"{}\"", toml_escape(&route))
+}
+
+fn spin_wasm_artifact(project_name: &str, language: &str) -> Option<String> {
+    let artifact = match language.to_ascii_lowercase().as_str() {
+        "rust" => format!("target/wasm32-wasi/release/{}.wasm", project_name.to_snake_case()),
+        "go" | "tinygo" => "main.wasm".to_owned(),
+        "js" | "javascript" | "ts" | "typescript" => format!("target/{}.wasm", project_name.to_kebab_case()),
+        "python" | "py" => "app.wasm".to_owned(),
+        _ => return None,
+    };
+    Some(artifact)
+}
+
 #[derive(Clone, ParseFilter, FilterReflection)]
 #[filter(
     name = "dotted_pascal_case",
@@ -165,6 +365,346 @@ impl Filter for DottedPascalCaseFilter {
     }
 }
 
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "camel_case",
+    description = "Change text to camelCase.",
+    parsed(CamelCaseFilter)
+)]
+pub(crate) struct CamelCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "camel_case"]
+struct CamelCaseFilter;
+
+impl Filter for CamelCaseFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(input.to_lower_camel_case().to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "screaming_snake_case",
+    description = "Change text to SCREAMING_SNAKE_CASE.",
+    parsed(ScreamingSnakeCaseFilter)
+)]
+pub(crate) struct ScreamingSnakeCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "screaming_snake_case"]
+struct ScreamingSnakeCaseFilter;
+
+impl Filter for ScreamingSnakeCaseFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(input.to_shouty_snake_case().to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "title_case",
+    description = "Change text to Title Case.",
+    parsed(TitleCaseFilter)
+)]
+pub(crate) struct TitleCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "title_case"]
+struct TitleCaseFilter;
+
+impl Filter for TitleCaseFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(input.to_title_case().to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "train_case",
+    description = "Change text to Train-Case.",
+    parsed(TrainCaseFilter)
+)]
+pub(crate) struct TrainCaseFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "train_case"]
+struct TrainCaseFilter;
+
+impl Filter for TrainCaseFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(input.to_train_case().to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "rust_identifier",
+    description = "Change text to a valid Rust identifier in snake_case, escaping keywords.",
+    parsed(RustIdentifierFilter)
+)]
+pub(crate) struct RustIdentifierFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "rust_identifier"]
+struct RustIdentifierFilter;
+
+impl Filter for RustIdentifierFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(rust_identifier(&input).to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "go_identifier",
+    description = "Change text to a valid Go identifier in camelCase, escaping keywords.",
+    parsed(GoIdentifierFilter)
+)]
+pub(crate) struct GoIdentifierFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "go_identifier"]
+struct GoIdentifierFilter;
+
+impl Filter for GoIdentifierFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(go_identifier(&input).to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "js_identifier",
+    description = "Change text to a valid JavaScript identifier in camelCase, escaping reserved words.",
+    parsed(JsIdentifierFilter)
+)]
+pub(crate) struct JsIdentifierFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "js_identifier"]
+struct JsIdentifierFilter;
+
+impl Filter for JsIdentifierFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(js_identifier(&input).to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "toml_escape",
+    description = "Escape text for use inside a TOML basic (double-quoted) string.",
+    parsed(TomlEscapeFilter)
+)]
+pub(crate) struct TomlEscapeFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "toml_escape"]
+struct TomlEscapeFilter;
+
+impl Filter for TomlEscapeFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(toml_escape(&input).to_value())
+    }
+}
+
+#[derive(Clone, ParseFilter, FilterReflection)]
+#[filter(
+    name = "json_escape",
+    description = "Escape text for use inside a JSON string.",
+    parsed(JsonEscapeFilter)
+)]
+pub(crate) struct JsonEscapeFilterParser;
+
+#[derive(Debug, Default, liquid_derive::Display_filter)]
+#[name = "json_escape"]
+struct JsonEscapeFilter;
+
+impl Filter for JsonEscapeFilter {
+    fn evaluate(
+        &self,
+        input: &dyn ValueView,
+        _runtime: &dyn Runtime,
+    ) -> Result<liquid_core::model::Value, liquid_core::error::Error> {
+        let input = input
+            .as_scalar()
+            .ok_or_else(|| liquid_core::error::Error::with_msg("String expected"))?;
+
+        let input = input.into_string().to_string();
+        Ok(json_escape(&input).to_value())
+    }
+}
+
+// Identifiers are converted to the language's usual case, then any characters that
+// still aren't allowed are dropped. A leading digit or a keyword gets an underscore.
+
+fn rust_identifier(text: &str) -> String {
+    let ident = identifier(&text.to_snake_case());
+    if RUST_NON_RAW_KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+fn go_identifier(text: &str) -> String {
+    escape_keyword(identifier(&text.to_lower_camel_case()), GO_KEYWORDS)
+}
+
+fn js_identifier(text: &str) -> String {
+    escape_keyword(identifier(&text.to_lower_camel_case()), JS_RESERVED_WORDS)
+}
+
+fn identifier(text: &str) -> String {
+    let ident = text.chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<String>();
+    match ident.chars().next() {
+        // `_` on its own is a pattern (Rust) or the blank identifier (Go), not a name
+        None => "_x".to_owned(),
+        Some(_) if ident.chars().all(|c| c == '_') => format!("{ident}x"),
+        Some(c) if c.is_numeric() => format!("_{ident}"),
+        Some(_) => ident,
+    }
+}
+
+fn escape_keyword(ident: String, keywords: &[&str]) -> String {
+    if keywords.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+// These can't be used even as raw identifiers
+const RUST_NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "super", "Self"];
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield",
+];
+
+const GO_KEYWORDS: &[&str] = &[
+    "break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough",
+    "for", "func", "go", "goto", "if", "import", "interface", "map", "package", "range",
+    "return", "select", "struct", "switch", "type", "var",
+];
+
+const JS_RESERVED_WORDS: &[&str] = &[
+    "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default",
+    "delete", "do", "else", "enum", "export", "extends", "false", "finally", "for", "function",
+    "if", "implements", "import", "in", "instanceof", "interface", "let", "new", "null",
+    "package", "private", "protected", "public", "return", "static", "super", "switch", "this",
+    "throw", "true", "try", "typeof", "var", "void", "while", "with", "yield",
+];
+
+// TOML basic strings and JSON strings have the same escapes, except that TOML
+// doesn't allow a literal DEL either.
+
+fn toml_escape(text: &str) -> String {
+    escape_string(text, |c| c.is_control())
+}
+
+fn json_escape(text: &str) -> String {
+    escape_string(text, |c| c < '\u{20}')
+}
+
+fn escape_string(text: &str, must_escape: impl Fn(char) -> bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{08}' => escaped.push_str("\\b"),
+            '\u{0c}' => escaped.push_str("\\f"),
+            c if must_escape(c) => escaped.push_str(&format!("\\u{:04X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod test {
     use liquid_core::{Filter, ValueView};
@@ -179,6 +719,78 @@ mod test {
             .unwrap()
     }
 
+    fn render(template: &str) -> String {
+        let parser = super::builtin_filters().into_iter()
+            .fold(liquid::ParserBuilder::with_stdlib(), |builder, filter| builder.filter(filter))
+            .build()
+            .unwrap();
+        parser.parse(template).unwrap().render(&liquid::Object::new()).unwrap()
+    }
+
+    #[test]
+    fn builtin_filters_are_registered() {
+        assert_eq!("pet-store", render("{{ 'PetStore' | kebab_case }}"));
+        assert_eq!("PetStore", render("{{ 'pet-store' | pascal_case }}"));
+        assert_eq!("Fermyon.PetStore", render("{{ 'fermyon.pet-store' | dotted_pascal_case }}"));
+        assert_eq!("pet_store", render("{{ 'PetStore' | snake_case }}"));
+        assert_eq!("/api/...", render("{{ '/api' | http_wildcard }}"));
+        assert_eq!("pet-store", render("{{ 'Pet Store!' | spin_component_id }}"));
+        assert_eq!(r#""/api/...""#, render("{{ 'api//...' | spin_route }}"));
+        assert_eq!("target/wasm32-wasi/release/pet_store.wasm", render("{{ 'pet-store' | spin_wasm_artifact: 'rust' }}"));
+        assert_eq!("petStore", render("{{ 'pet-store' | camel_case }}"));
+        assert_eq!("PET_STORE", render("{{ 'pet-store' | screaming_snake_case }}"));
+        assert_eq!("Pet Store", render("{{ 'pet-store' | title_case }}"));
+        assert_eq!("Pet-Store", render("{{ 'pet_store' | train_case }}"));
+        assert_eq!("r#type", render("{{ 'type' | rust_identifier }}"));
+        assert_eq!("type_", render("{{ 'type' | go_identifier }}"));
+        assert_eq!("class_", render("{{ 'class' | js_identifier }}"));
+        assert_eq!(r#"say \"hi\""#, render(r#"{{ 'say "hi"' | toml_escape }}"#));
+        assert_eq!(r#"say \"hi\""#, render(r#"{{ 'say "hi"' | json_escape }}"#));
+    }
+
+    #[test]
+    fn identifiers_are_valid() {
+        assert_eq!("pet_store", super::rust_identifier("Pet Store!"));
+        assert_eq!("self_", super::rust_identifier("self"));
+        assert_eq!("_2048_game", super::rust_identifier("2048-game"));
+        assert_eq!("_x", super::rust_identifier("!!!"));
+        assert_eq!("_x", super::go_identifier("_"));
+        assert_eq!("petStore", super::go_identifier("pet-store"));
+        assert_eq!("func_", super::go_identifier("func"));
+        assert_eq!("_3dViewer", super::js_identifier("3d viewer"));
+    }
+
+    #[test]
+    fn spin_component_ids_are_valid() {
+        assert_eq!("pet-store", super::spin_component_id("PetStore"));
+        assert_eq!("my-app", super::spin_component_id("my_app"));
+        assert_eq!("game2048", super::spin_component_id("game-2048"));
+        assert_eq!("component2048-game", super::spin_component_id("2048 game"));
+        assert_eq!("component", super::spin_component_id("!!!"));
+    }
+
+    #[test]
+    fn spin_routes_are_normalised() {
+        assert_eq!(r#""/""#, super::spin_route(""));
+        assert_eq!(r#""/api/...""#, super::spin_route(" /api/... "));
+        assert_eq!(r#""/api/v1/""#, super::spin_route("api//v1/"));
+        assert_eq!("{ private = true }", super::spin_route("Private"));
+    }
+
+    #[test]
+    fn spin_wasm_artifacts_depend_on_language() {
+        assert_eq!(Some("main.wasm"), super::spin_wasm_artifact("pet-store", "go").as_deref());
+        assert_eq!(Some("target/pet-store.wasm"), super::spin_wasm_artifact("PetStore", "ts").as_deref());
+        assert_eq!(None, super::spin_wasm_artifact("pet-store", "cobol"));
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        let text = "C:\\temp\n\t\u{7f}";
+        assert_eq!("C:\\\\temp\\n\\t\\u007F", super::toml_escape(text));
+        assert_eq!("C:\\\\temp\\n\\t\u{7f}", super::json_escape(text));
+    }
+
     #[test]
     fn test_dotted_pascal_case() {
         assert_eq!("Fermyon.PetStore", dotted_pascal("Fermyon.PetStore"));
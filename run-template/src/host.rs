@@ -1,12 +1,23 @@
-use std::{collections::HashMap, path::{Path, PathBuf}, sync::{Arc, RwLock}};
+use std::{collections::{BTreeMap, HashMap}, path::Path, sync::{Arc, RwLock}};
+
+use liquid_core::partials::PartialSource;
 
 use crate::bindings::fermyon;
+use crate::limits::Limits;
+use crate::partials::PartialsDir;
+use crate::render_error::{undefined_variable, RenderError, UndefinedVariable};
+use crate::template::CONTENT_DIR;
+use crate::template_files::TemplateFiles;
 
 pub struct Host {
-    content_root: PathBuf,
+    template_files: TemplateFiles,
     accept_defaults: bool,
     files: wasmtime::component::ResourceTable,
     pub(crate) execution_contexts: wasmtime::component::ResourceTable,
+    pub(crate) limits: Limits,
+    /// The variables of the render in progress, when this is the instance that
+    /// runs the filters the template component exports.
+    pub(crate) variables: BTreeMap<String, String>,
 }
 
 #[derive(Debug)]
@@ -42,12 +53,14 @@ impl std::fmt::Display for DialogueTrap {
 }
 
 impl Host {
-    pub fn new(root_dir: impl AsRef<Path>, accept_defaults: bool) -> Self {
+    pub fn new(template_files: TemplateFiles, accept_defaults: bool, limits: Limits) -> Self {
         Self {
-            content_root: root_dir.as_ref().to_owned(),
+            template_files,
             accept_defaults,
             files: wasmtime::component::ResourceTable::new(),
             execution_contexts: wasmtime::component::ResourceTable::new(),
+            limits,
+            variables: BTreeMap::new(),
         }
     }
 }
@@ -59,44 +72,137 @@ pub struct ExecutionContext {
 
 struct ExecutionContextInner {
     variables: HashMap<String, String>,
+    parent: Option<ExecutionContext>,
     parser: liquid::Parser,
+    /// The partials the parser includes, so that errors in them can be reported.
+    partials: Option<Arc<PartialsDir>>,
+    render_mode: RenderMode,
+}
+
+/// How to treat variables that a template uses but that have not been set.
+#[derive(Clone, Copy, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderMode {
+    /// Fail, listing every undefined variable.
+    #[default]
+    Strict,
+    /// Render undefined variables as empty strings.
+    Lenient,
 }
 
 impl ExecutionContext {
-    pub fn new(variables: HashMap<String, String>, parser: liquid::Parser) -> Self {
+    pub fn new(variables: HashMap<String, String>, parser: liquid::Parser, render_mode: RenderMode) -> Self {
         Self {
-            inner: Arc::new(RwLock::new(ExecutionContextInner::new(variables, parser))),
+            inner: Arc::new(RwLock::new(ExecutionContextInner::new(variables, parser, render_mode))),
         }
     }
 
-    pub fn evaluate_template(&self, template: &str) -> anyhow::Result<String> {
+    /// Gives the context the partials that its parser includes, so that undefined
+    /// variables in them are reported against the partial rather than the file including it.
+    pub fn with_partials(self, partials: PartialsDir) -> Self {
+        self.inner.write().unwrap().partials = Some(Arc::new(partials));
+        self
+    }
+
+    pub fn evaluate_template(&self, template: &str) -> Result<String, RenderError> {
         self.inner.read().unwrap().evaluate_template(template)
     }
+
+    /// Creates a child context which inherits this context's variables and parser.
+    pub fn fork(&self) -> Self {
+        let inner = self.inner.read().unwrap();
+        let child = ExecutionContextInner {
+            variables: HashMap::new(),
+            parent: Some(self.clone()),
+            parser: inner.parser.clone(),
+            partials: inner.partials.clone(),
+            render_mode: inner.render_mode,
+        };
+        Self {
+            inner: Arc::new(RwLock::new(child)),
+        }
+    }
+
+    /// Creates a context with a copy of this context's current variables. Unlike
+    /// a fork, later changes to this context are not seen by the snapshot.
+    pub fn snapshot(&self) -> Self {
+        let inner = self.inner.read().unwrap();
+        let mut snapshot = ExecutionContextInner::new(inner.variables(), inner.parser.clone(), inner.render_mode);
+        snapshot.partials = inner.partials.clone();
+        Self {
+            inner: Arc::new(RwLock::new(snapshot)),
+        }
+    }
+
+    fn variables(&self) -> HashMap<String, String> {
+        self.inner.read().unwrap().variables()
+    }
 }
 
 impl ExecutionContextInner {
-    fn new(variables: HashMap<String, String>, parser: liquid::Parser) -> Self {
+    fn new(variables: HashMap<String, String>, parser: liquid::Parser, render_mode: RenderMode) -> Self {
         Self {
             variables,
+            parent: None,
             parser,
+            partials: None,
+            render_mode,
         }
     }
 
-    fn evaluate_template(&self, template: &str) -> anyhow::Result<String> {
-        let template = self.parser.parse(template)?;
-        
+    // Variables set on this context override those inherited from the parent
+    fn variables(&self) -> HashMap<String, String> {
+        let mut variables = match &self.parent {
+            Some(parent) => parent.variables(),
+            None => HashMap::new(),
+        };
+        variables.extend(self.variables.iter().map(|(k, v)| (k.to_owned(), v.to_owned())));
+        variables
+    }
+
+    fn evaluate_template(&self, template_text: &str) -> Result<String, RenderError> {
+        let template = self.parser.parse(template_text)
+            .map_err(|e| RenderError::parse(template_text, e))?;
+
         let mut object = liquid::Object::new();
 
-        for (name, value) in &self.variables {
+        for (name, value) in self.variables() {
             object.insert(
-                name.to_owned().into(),
-                liquid_core::Value::Scalar(value.to_owned().into()),
+                name.into(),
+                liquid_core::Value::Scalar(value.into()),
             );
         }
-    
-        let res = template.render(&object).unwrap();
-        Ok(res)
-    
+
+        // Liquid stops at the first unknown variable, so to find all of them we
+        // keep stubbing in empty values and trying again.
+        let mut undefined = vec![];
+        let rendered = loop {
+            match template.render(&object) {
+                Ok(text) => break text,
+                Err(e) => match undefined_variable(&e) {
+                    Some((name, partial)) if !object.contains_key(name.as_str()) => {
+                        object.insert(name.clone().into(), liquid_core::Value::scalar(""));
+                        let partial = partial.map(|partial| {
+                            let text = self.partials.as_ref()
+                                .and_then(|partials| partials.try_get(&partial))
+                                .unwrap_or_default()
+                                .into_owned();
+                            (partial, text)
+                        });
+                        undefined.push(UndefinedVariable { name, partial });
+                    }
+                    _ => return Err(RenderError::render(template_text, e)),
+                }
+            }
+        };
+        // Liquid has no way to ask whether a variable is defined, so `default` is how
+        // templates give a variable a fallback, and the stub gets the fallback
+        undefined.retain(|variable| !variable.has_default(template_text));
+
+        match self.render_mode {
+            RenderMode::Strict if !undefined.is_empty() => Err(RenderError::undefined_variables(template_text, &undefined)),
+            _ => Ok(rendered),
+        }
     }
 }
 
@@ -112,7 +218,19 @@ impl fermyon::spin_template::types::HostExecutionContext for Host {
     fn evaluate_template(&mut self, self_: wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext>, template: String) -> Result<String, fermyon::spin_template::types::Error> {
         let context = self.execution_contexts.get_mut(&self_).unwrap();
         let inner = context.inner.read().unwrap();
-        inner.evaluate_template(&template).map_err(|e| fermyon::spin_template::types::Error::Other(e.to_string()))
+        inner.evaluate_template(&template).map_err(|e| e.into())
+    }
+
+    fn fork(&mut self, self_: wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext>) -> wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext> {
+        let context = self.execution_contexts.get(&self_).unwrap();
+        let child = context.fork();
+        self.execution_contexts.push(child).unwrap()
+    }
+
+    fn snapshot(&mut self, self_: wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext>) -> wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext> {
+        let context = self.execution_contexts.get(&self_).unwrap();
+        let snapshot = context.snapshot();
+        self.execution_contexts.push(snapshot).unwrap()
     }
 
     fn drop(&mut self, rep: wasmtime::component::Resource<fermyon::spin_template::types::ExecutionContext>) -> wasmtime::Result<()> {
@@ -165,11 +283,8 @@ impl fermyon::spin_template::ui::Host for Host {
 
 impl fermyon::spin_template::ui::HostFile for Host {
     fn list_all(&mut self) -> Vec<wasmtime::component::Resource<fermyon::spin_template::ui::File>> {
-        let w = walkdir::WalkDir::new(&self.content_root);
-        w.into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .map(|e| e.path().strip_prefix(&self.content_root).unwrap().to_owned())
+        self.template_files.files_in(Path::new(CONTENT_DIR))
+            .into_iter()
             .map(|p| {
                 // TODO: I am aware of all the crimes
                 self.files.push(p).unwrap()
@@ -184,15 +299,15 @@ impl fermyon::spin_template::ui::HostFile for Host {
 
     fn read(&mut self, self_: wasmtime::component::Resource<fermyon::spin_template::ui::File>) -> Result<String, fermyon::spin_template::types::Error> {
         let res = self.files.get(&self_).unwrap();
-        let path = self.content_root.join(res);
+        let path = Path::new(CONTENT_DIR).join(res);
         //println!("***READING {path:?}");
-        std::fs::read_to_string(&path).map_err(|e| fermyon::spin_template::types::Error::Other(format!("Error reading file {res:?}: {e:?}")))
+        self.template_files.read_to_string(&path).map_err(|e| fermyon::spin_template::types::Error::Other(format!("Error reading file {res:?}: {e:?}")))
     }
 
     fn read_binary(&mut self, self_: wasmtime::component::Resource<fermyon::spin_template::ui::File>) -> Result<Vec<u8>, fermyon::spin_template::types::Error> {
         let res = self.files.get(&self_).unwrap();
-        let path = self.content_root.join(res);
-        std::fs::read(&path).map_err(|e| fermyon::spin_template::types::Error::Other(format!("Error reading file {res:?}: {e:?}")))
+        let path = Path::new(CONTENT_DIR).join(res);
+        self.template_files.read(&path).map_err(|e| fermyon::spin_template::types::Error::Other(format!("Error reading file {res:?}: {e:?}")))
     }
 
     fn drop(&mut self, rep: wasmtime::component::Resource<fermyon::spin_template::ui::File>) -> wasmtime::Result<()> {
@@ -208,3 +323,66 @@ fn cancel<T>() -> Result<T, wasmtime::Error> {
 fn exit_with_error<T>(e: dialoguer::Error) -> Result<T, wasmtime::Error> {
     Err(wasmtime::Error::new(DialogueTrap::Error(e)))    
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn context(variables: &[(&str, &str)]) -> ExecutionContext {
+        let variables = variables.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        ExecutionContext::new(variables, parser, RenderMode::Strict)
+    }
+
+    #[test]
+    fn forked_contexts_inherit_but_do_not_leak() {
+        let parent = context(&[("project-name", "app"), ("fruit", "apple")]);
+        let frontend = parent.fork();
+        let api = parent.fork();
+        frontend.inner.write().unwrap().variables.insert("project-name".to_owned(), "frontend".to_owned());
+        parent.inner.write().unwrap().variables.insert("fruit".to_owned(), "banana".to_owned());
+
+        assert_eq!("frontend banana", frontend.evaluate_template("{{ project-name }} {{ fruit }}").unwrap());
+        assert_eq!("app banana", api.evaluate_template("{{ project-name }} {{ fruit }}").unwrap());
+        assert_eq!("app banana", parent.evaluate_template("{{ project-name }} {{ fruit }}").unwrap());
+    }
+
+    #[test]
+    fn variables_with_a_default_are_defined() {
+        let context = context(&[("project-name", "app")]);
+        assert_eq!("app /...", context.evaluate_template("{{ project-name }} {{ http-path | default: '/...' }}").unwrap());
+
+        let err = context.evaluate_template("{{ http-path | default: '/...' }}\n{% if http-path %}{% endif %}").unwrap_err();
+        assert_eq!("Undefined variables: http-path (1:1)", err.message);
+    }
+
+    #[test]
+    fn undefined_variables_in_partials_are_reported_in_the_partial() {
+        let dir = std::env::temp_dir().join(format!("run-template-host-partials-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("partials")).unwrap();
+        std::fs::write(dir.join("partials").join("component.toml"), "[component]\nsource = \"{{ source }}\"").unwrap();
+        let partials = || PartialsDir::new(TemplateFiles::Dir(dir.clone()), "partials");
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .partials(liquid::partials::LazyCompiler::new(partials()))
+            .build()
+            .unwrap();
+        let context = ExecutionContext::new(HashMap::new(), parser, RenderMode::Strict).with_partials(partials());
+
+        let err = context.evaluate_template("id = \"{{ id }}\"\n{% include 'component.toml' %}").unwrap_err();
+        assert_eq!("Undefined variables: id (1:7), source (partial component.toml 2:11)", err.message);
+        assert_eq!(Some(1), err.line);
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn snapshots_do_not_see_later_changes() {
+        let parent = context(&[("http-path", "/...")]);
+        let child = parent.fork();
+        let snapshot = child.snapshot();
+        parent.inner.write().unwrap().variables.insert("http-path".to_owned(), "/api/...".to_owned());
+
+        assert_eq!("/...", snapshot.evaluate_template("{{ http-path }}").unwrap());
+        assert_eq!("/api/...", child.evaluate_template("{{ http-path }}").unwrap());
+    }
+}
@@ -0,0 +1,244 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::manifest::Manifest;
+use crate::paths::resolve_template_path;
+use crate::template::MANIFEST_FILE;
+use crate::template_files::TemplateFiles;
+
+/// The templates the user has installed, so that they can be run by id. Each
+/// template is unpacked into a directory named for its id, with the template
+/// component copied in if the manifest refers to it from outside the template
+/// (see `TemplateFiles::self_contained`).
+pub struct Library {
+    dir: PathBuf,
+}
+
+impl Library {
+    /// The per-user library, e.g. `~/.local/share/run-template/templates` on Linux.
+    pub fn new() -> anyhow::Result<Self> {
+        let data_dir = dirs::data_dir().context("Can't find a directory to install templates into")?;
+        Ok(Self::at(data_dir.join("run-template").join("templates")))
+    }
+
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Copies the template (from a directory or a bundle) into the library. `manifest`
+    /// is its manifest, loaded from `manifest_path`, which the caller should already have validated.
+    pub fn install(&self, source: &TemplateFiles, manifest_path: &Path, manifest: &Manifest, replace: bool) -> anyhow::Result<PathBuf> {
+        let dest = self.dir.join(&manifest.id);
+        if dest.exists() && !replace {
+            anyhow::bail!("Template '{}' is already installed. Use --replace to replace it.", manifest.id);
+        }
+
+        // Copy to a staging directory first, so that a failed install leaves any existing copy alone
+        let staging = self.dir.join(format!(".installing-{}-{}", manifest.id, std::process::id()));
+        let result = copy_template(source, manifest_path, manifest, &staging).and_then(|_| {
+            if dest.exists() {
+                std::fs::remove_dir_all(&dest)?;
+            }
+            std::fs::rename(&staging, &dest)?;
+            Ok(())
+        });
+        if result.is_err() {
+            _ = std::fs::remove_dir_all(&staging);
+        }
+        result.with_context(|| format!("Failed to install template '{}' into {}", manifest.id, self.dir.display()))?;
+
+        Ok(dest)
+    }
+
+    /// The installed templates, sorted by id, optionally only those with `tag`,
+    /// and the errors for any installed templates whose manifests can't be read.
+    /// One broken install doesn't stop the others being listed.
+    pub fn list(&self, tag: Option<&str>) -> anyhow::Result<(Vec<Manifest>, Vec<anyhow::Error>)> {
+        if !self.dir.exists() {
+            return Ok((vec![], vec![]));
+        }
+        let mut manifests = vec![];
+        let mut broken = vec![];
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    broken.push(anyhow::Error::new(e).context(format!("Failed to read the library {}", self.dir.display())));
+                    continue;
+                }
+            };
+            if !path.is_dir() || path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')) {
+                continue;
+            }
+            match Manifest::load(&TemplateFiles::Dir(path), Path::new(MANIFEST_FILE)) {
+                Ok(manifest) if tag.is_none_or(|tag| manifest.tags.iter().any(|t| t == tag)) => manifests.push(manifest),
+                Ok(_) => (),
+                Err(e) => broken.push(e),
+            }
+        }
+        manifests.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok((manifests, broken))
+    }
+
+    pub fn remove(&self, id: &str) -> anyhow::Result<()> {
+        let dir = self.installed_dir(id)?;
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to remove template '{id}' from {}", dir.display()))
+    }
+
+    /// The directory of the installed template with the given id.
+    pub fn installed_dir(&self, id: &str) -> anyhow::Result<PathBuf> {
+        // Ids can't contain path separators, but the user could type anything
+        let dir = resolve_template_path(&self.dir, id).ok().filter(|_| !id.contains(['/', '\\']));
+        match dir {
+            Some(dir) if dir.join(MANIFEST_FILE).is_file() => Ok(dir),
+            _ => anyhow::bail!("No template with id '{id}' is installed. Run `run-template list` to see the installed templates."),
+        }
+    }
+}
+
+fn copy_template(source: &TemplateFiles, manifest_path: &Path, manifest: &Manifest, dest: &Path) -> anyhow::Result<()> {
+    for (path, contents) in source.self_contained(manifest_path, manifest)? {
+        let target = dest.join(path);
+        if let Some(dir) = target.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&target, contents)?;
+    }
+    Ok(())
+}
+
+/// Checks out `git_ref` of the Git repository containing `path` into a temporary
+/// directory, and returns the directory and the equivalent of `path` within it.
+/// Only local repositories are supported, so this works offline.
+pub fn git_checkout(path: &Path, git_ref: &str) -> anyhow::Result<(TempDir, PathBuf)> {
+    // Git would take it as an option, and `--` would make it a path rather than a ref
+    if git_ref.starts_with('-') {
+        anyhow::bail!("'{git_ref}' is not a valid Git ref");
+    }
+
+    let git = |dir: &Path, args: &[&str]| -> anyhow::Result<String> {
+        let output = std::process::Command::new("git")
+            .arg("-C").arg(dir)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+
+    let dir = if path.is_dir() { path } else { path.parent().unwrap_or(Path::new(".")) };
+    let repo = git(dir, &["rev-parse", "--show-toplevel"])
+        .with_context(|| format!("{} is not in a Git repository", path.display()))?;
+    let prefix = git(dir, &["rev-parse", "--show-prefix"])?;
+
+    let checkout = TempDir(std::env::temp_dir().join(format!("run-template-checkout-{}", std::process::id())));
+    git(Path::new("."), &["clone", "--quiet", "--shared", "--no-checkout", &repo, &checkout.0.to_string_lossy()])?;
+    git(&checkout.0, &["checkout", "--quiet", git_ref])
+        .with_context(|| format!("Failed to check out '{git_ref}' from {repo}"))?;
+
+    let template_dir = checkout.0.join(prefix);
+    let template_path = match path.file_name() {
+        Some(file_name) if !path.is_dir() => template_dir.join(file_name),
+        _ => template_dir,
+    };
+    Ok((checkout, template_path))
+}
+
+/// A directory that is deleted when dropped.
+pub struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn write_template(dir: &Path, id: &str, tags: &[&str], template: &str) {
+        std::fs::create_dir_all(dir.join("content")).unwrap();
+        std::fs::write(dir.join("content").join("hello.txt"), "Hello {{ project-name }}").unwrap();
+        let manifest = format!(
+            "# The {id} template\nid = \"{id}\"\nname = \"Test\"\ndescription = \"Test\"\ntags = {tags:?}\nwit_version = \"0.0.2\"\ntemplate = \"{template}\"\n"
+        );
+        std::fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+    }
+
+    fn install(library: &Library, dir: &Path) -> anyhow::Result<PathBuf> {
+        let files = TemplateFiles::Dir(dir.to_owned());
+        let manifest = Manifest::load(&files, Path::new(MANIFEST_FILE)).unwrap();
+        library.install(&files, Path::new(MANIFEST_FILE), &manifest, false)
+    }
+
+    #[test]
+    fn templates_are_installed_listed_and_removed() {
+        let root = std::env::temp_dir().join(format!("run-template-library-test-{}", std::process::id()));
+        let library = Library::at(root.join("library"));
+
+        // The component is outside the template directory, e.g. in the build output
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target").join("http.wasm"), "(component)").unwrap();
+        write_template(&root.join("http").join("template"), "http", &["http", "rust"], "../../target/http.wasm");
+        write_template(&root.join("redis"), "redis", &["redis"], "redis.wasm");
+
+        let installed = install(&library, &root.join("http").join("template")).unwrap();
+        install(&library, &root.join("redis")).unwrap();
+        assert!(installed.join("content").join("hello.txt").is_file());
+        assert!(installed.join("http.wasm").is_file());
+        let manifest = std::fs::read_to_string(installed.join(MANIFEST_FILE)).unwrap();
+        assert!(manifest.starts_with("# The http template"));
+        assert!(manifest.contains("template = \"http.wasm\""), "{manifest}");
+
+        let ids = |tag| library.list(tag).unwrap().0.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(vec!["http", "redis"], ids(None));
+        assert_eq!(vec!["http"], ids(Some("rust")));
+
+        // A broken install is reported, but doesn't hide the others
+        std::fs::create_dir_all(root.join("library").join("broken")).unwrap();
+        std::fs::write(root.join("library").join("broken").join(MANIFEST_FILE), "id = ").unwrap();
+        let (manifests, broken) = library.list(None).unwrap();
+        assert_eq!(2, manifests.len());
+        assert_eq!(1, broken.len());
+        std::fs::remove_dir_all(root.join("library").join("broken")).unwrap();
+
+        assert!(install(&library, &root.join("redis")).is_err());
+        library.remove("redis").unwrap();
+        assert_eq!(vec!["http"], ids(None));
+        assert!(library.installed_dir("redis").is_err());
+        assert!(library.installed_dir("../http").is_err());
+
+        _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn manifests_with_other_names_are_installed_as_the_manifest() {
+        let root = std::env::temp_dir().join(format!("run-template-library-rename-test-{}", std::process::id()));
+        let library = Library::at(root.join("library"));
+
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target").join("http.wasm"), "(component)").unwrap();
+        write_template(&root.join("http"), "http", &[], "../target/http.wasm");
+        std::fs::rename(root.join("http").join(MANIFEST_FILE), root.join("http").join("http-template.toml")).unwrap();
+
+        let files = TemplateFiles::Dir(root.join("http"));
+        let manifest = Manifest::load(&files, Path::new("http-template.toml")).unwrap();
+        let installed = library.install(&files, Path::new("http-template.toml"), &manifest, false).unwrap();
+        let manifest = std::fs::read_to_string(installed.join(MANIFEST_FILE)).unwrap();
+        assert!(manifest.contains("template = \"http.wasm\""), "{manifest}");
+        assert!(!installed.join("http-template.toml").exists());
+
+        _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn refs_that_look_like_options_are_refused() {
+        let err = git_checkout(Path::new("."), "--upload-pack=touch pwned").err().unwrap();
+        assert!(err.to_string().contains("not a valid Git ref"), "{err}");
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+/// CPU and memory budgets for a component. Fuel is roughly one unit per Wasm
+/// instruction and is topped up for every call into the component. Stores
+/// enforce the memory budget by using their `Limits` as their `ResourceLimiter`,
+/// so it applies per store: it caps the size of each linear memory in the store,
+/// and every component instance has a store of its own.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub fuel: u64,
+    pub memory_bytes: usize,
+}
+
+impl Limits {
+    /// For filter components, which should do very little work.
+    pub const FILTER: Self = Self {
+        fuel: 1_000_000_000,
+        memory_bytes: 64 * MIB,
+    };
+
+    /// For template components, unless the manifest or command line says otherwise.
+    pub const TEMPLATE: Self = Self {
+        fuel: 20_000_000_000,
+        memory_bytes: 256 * MIB,
+    };
+
+    /// Applies overrides from the manifest, then from the command line.
+    pub fn overridden(self, overrides: &[&LimitsConfig]) -> anyhow::Result<Self> {
+        overrides.iter().try_fold(self, |limits, config| Ok(Self {
+            fuel: config.fuel.unwrap_or(limits.fuel),
+            memory_bytes: config.memory_bytes()?.unwrap_or(limits.memory_bytes),
+        }))
+    }
+
+    /// Gives the store a full fuel budget for the next call into the component.
+    pub fn refuel<T>(&self, store: &mut wasmtime::Store<T>) -> anyhow::Result<()> {
+        store.set_fuel(self.fuel)
+    }
+}
+
+const MIB: usize = 1024 * 1024;
+
+impl wasmtime::ResourceLimiter for Limits {
+    fn memory_growing(&mut self, _current: usize, desired: usize, _maximum: Option<usize>) -> anyhow::Result<bool> {
+        if desired > self.memory_bytes {
+            // Erroring rather than refusing means the guest traps here, instead of
+            // aborting somewhere less informative when its allocator fails
+            return Err(LimitExceeded::Memory.into());
+        }
+        Ok(true)
+    }
+
+    fn table_growing(&mut self, _current: u32, _desired: u32, _maximum: Option<u32>) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+}
+
+/// The `[limits]` table in `spin-template.toml`, or the equivalent command line options.
+#[derive(Debug, Default, Deserialize, clap::Args)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// The fuel (roughly, Wasm instructions) the template component may use in each call.
+    #[clap(long = "fuel")]
+    pub fuel: Option<u64>,
+
+    /// The memory the template component may use, in MiB.
+    #[clap(long = "memory-mib")]
+    pub memory_mib: Option<usize>,
+}
+
+impl LimitsConfig {
+    pub fn memory_bytes(&self) -> anyhow::Result<Option<usize>> {
+        self.memory_mib
+            .map(|mib| mib.checked_mul(MIB).with_context(|| format!("`memory_mib` is {mib}, which is too large: the most is {}", usize::MAX / MIB)))
+            .transpose()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum LimitExceeded {
+    Fuel,
+    Memory,
+}
+
+impl LimitExceeded {
+    /// If a call into a component failed because it hit a limit, says which one.
+    pub fn of(error: &anyhow::Error) -> Option<Self> {
+        if let Some(wasmtime::Trap::OutOfFuel) = error.downcast_ref::<wasmtime::Trap>() {
+            return Some(Self::Fuel);
+        }
+        error.downcast_ref::<Self>().copied()
+    }
+
+    /// Describes the limit that `what` hit, e.g. "Filter 'spork' exceeded its memory limit (64 MiB)"
+    pub fn message(&self, what: &str, limits: &Limits) -> String {
+        match self {
+            Self::Fuel => format!("{what} ran out of fuel (limit {} per call): it may be stuck in a loop", limits.fuel),
+            Self::Memory => format!("{what} exceeded its memory limit ({} MiB)", limits.memory_bytes / MIB),
+        }
+    }
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fuel => f.write_str("fuel limit exceeded"),
+            Self::Memory => f.write_str("memory limit exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Turns a trap from the template component into a clear error if it was
+/// caused by hitting a limit, and says how to raise the limit.
+pub fn template_error(error: anyhow::Error, limits: &Limits) -> anyhow::Error {
+    match LimitExceeded::of(&error) {
+        Some(limit) => anyhow::anyhow!(
+            "{}. You can raise this with --fuel or --memory-mib, or in the [limits] table of the template manifest.",
+            limit.message("The template", limits)
+        ),
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exceeded_limits_are_recognised() {
+        let fuel = anyhow::Error::from(wasmtime::Trap::OutOfFuel).context("error while executing at wasm backtrace");
+        assert!(matches!(LimitExceeded::of(&fuel), Some(LimitExceeded::Fuel)));
+
+        let memory = anyhow::Error::from(LimitExceeded::Memory).context("error while executing at wasm backtrace");
+        let message = LimitExceeded::of(&memory).unwrap().message("Filter 'spork'", &Limits::FILTER);
+        assert_eq!("Filter 'spork' exceeded its memory limit (64 MiB)", message);
+
+        assert!(LimitExceeded::of(&anyhow::anyhow!("unreachable")).is_none());
+    }
+
+    #[test]
+    fn command_line_overrides_manifest() {
+        let manifest = LimitsConfig { fuel: Some(5), memory_mib: Some(10) };
+        let command_line = LimitsConfig { fuel: Some(7), memory_mib: None };
+        let limits = Limits::TEMPLATE.overridden(&[&manifest, &command_line]).unwrap();
+        assert_eq!(7, limits.fuel);
+        assert_eq!(10 * MIB, limits.memory_bytes);
+    }
+
+    #[test]
+    fn oversized_memory_limits_are_rejected() {
+        let config = LimitsConfig { fuel: None, memory_mib: Some(usize::MAX) };
+        let err = Limits::TEMPLATE.overridden(&[&config]).unwrap_err();
+        assert!(err.to_string().contains("`memory_mib`"), "{err}");
+    }
+}
@@ -1,31 +1,158 @@
-use std::{collections::HashMap, path::PathBuf, sync::{Arc, RwLock}};
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
-use serde::Deserialize;
+use liquid_core::ParseFilter;
 
 mod actions;
 mod bindings;
+mod bundle;
+mod component_cache;
 mod custom_filter;
+mod custom_tag;
+mod filter_alias;
+mod filter_docs;
 mod filters;
 mod host;
+mod library;
+mod limits;
+mod manifest;
+mod partials;
+mod paths;
+mod render_error;
+mod signing;
+mod template;
+mod template_files;
+mod world;
 
-use bindings::{fermyon, exports, RunTemplate};
-use host::{DialogueTrap, ExecutionContext, Host};
+use bindings::{fermyon, exports};
+use host::{DialogueTrap, ExecutionContext};
+use manifest::Mode;
+use template::Template;
 
 #[derive(clap::Parser)]
 struct Args {
-    /// The template file e.g. ../sample-template/template/spin-template.toml
-    template_manifest: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+
+    /// Load template bundles even if they aren't signed by a trusted key. Only
+    /// use this for templates you trust.
+    #[clap(long = "allow-unsigned", global = true)]
+    allow_unsigned: bool,
+
+    /// The trusted keys file to check bundle signatures against. Defaults to
+    /// `trusted-keys.toml` in the user config directory.
+    #[clap(long = "trusted-keys", global = true)]
+    trusted_keys: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Create a new app from a template.
+    New {
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Add to an existing app from a template.
+    Add {
+        #[command(flatten)]
+        run: RunArgs,
+
+        /// The directory of the app to add to, which must contain `spin.toml`.
+        #[clap(long = "app", default_value = ".")]
+        app: PathBuf,
+    },
+    /// Show what a template is and what it supports.
+    Info {
+        /// The template id, directory, manifest or bundle, e.g. ../sample-template/template
+        template: String,
+    },
+    /// Check that a template's manifest is valid and that its components load.
+    Validate {
+        /// The template id, directory, manifest or bundle, e.g. ../sample-template/template
+        template: String,
+    },
+    /// List the filters available to a template, with their documentation.
+    Filters {
+        /// The template id, directory, manifest or bundle, e.g. ../sample-template/template
+        template: String,
+    },
+    /// Install a template so that it can be run by id.
+    Install {
+        /// The template directory, manifest or bundle, e.g. ../http-rust/template
+        source: PathBuf,
+
+        /// Install the template as it is at this commit, branch or tag of the
+        /// local Git repository that contains it, rather than as it is on disk.
+        #[clap(long = "git-ref")]
+        git_ref: Option<String>,
+
+        /// Replace the template if one with the same id is already installed.
+        #[clap(long = "replace")]
+        replace: bool,
+    },
+    /// List the installed templates.
+    List {
+        /// Only list templates with this tag.
+        #[clap(long = "tag")]
+        tag: Option<String>,
+    },
+    /// Remove an installed template.
+    Remove {
+        /// The id of the template to remove.
+        id: String,
+    },
+    /// Pack a template into a single-file bundle, which can be run or installed
+    /// without unpacking it.
+    Pack {
+        /// The template id, directory or manifest, e.g. ../http-rust/template
+        template: String,
+
+        /// The file to write the bundle to. Defaults to the template id with
+        /// the extension `.rtb`, in the current directory.
+        #[clap(long = "output", short = 'o')]
+        output: Option<PathBuf>,
+    },
+    /// Generate a key for signing template bundles.
+    Keygen {
+        /// Where to write the key. Defaults to `signing-key` in the user config directory.
+        #[clap(long = "key")]
+        key: Option<PathBuf>,
+
+        /// Replace the key if it already exists. Bundles signed with the old key
+        /// will no longer be trusted by anyone who trusted it.
+        #[clap(long = "replace")]
+        replace: bool,
+    },
+    /// Sign a template bundle, replacing any existing signature.
+    Sign {
+        /// The bundle to sign, e.g. http-rust.rtb
+        bundle: PathBuf,
+
+        /// The signing key. Defaults to `signing-key` in the user config directory.
+        #[clap(long = "key")]
+        key: Option<PathBuf>,
+    },
+    /// Trust bundles signed by a key.
+    Trust {
+        /// The public key, as printed by `run-template keygen`.
+        public_key: String,
+
+        /// Who holds the key, e.g. "Fermyon templates".
+        #[clap(long = "name")]
+        name: String,
+    },
+}
+
+#[derive(clap::Args)]
+struct RunArgs {
+    /// The template id, directory, manifest or bundle, e.g. http-rust or ../http-rust/template
+    template: String,
 
     /// The name for the item being generated. This will also be used as the
     /// directory to generate into (for new apps relative to current directory,
-    /// for additions relative to the directory containing `spin.toml`).
+    /// for additions relative to the app directory).
     name: String,
 
-    /// The spin.toml file to add the component to.
-    #[clap(long = "add-to")]
-    add_to: Option<PathBuf>,
-
     /// Print what would be done but don't do it.
     #[clap(long = "dry-run")]
     dry_run: bool,
@@ -33,58 +160,91 @@ struct Args {
     /// Accept defaults where available.
     #[clap(long = "accept-defaults", short = 'a')]
     accept_defaults: bool,
+
+    /// Grant the capabilities the template declares without asking.
+    #[clap(long = "allow-capabilities")]
+    allow_capabilities: bool,
+
+    #[command(flatten)]
+    limits: limits::LimitsConfig,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    // Only commands that load templates need the trusted keys
+    let trust = || signing::TrustPolicy::new(args.trusted_keys.clone(), args.allow_unsigned);
 
-    let tpl_dir = args.template_manifest.parent().expect("shouldna passed the root dir");
-    let content_dir = tpl_dir.join("content");
-
-    let manifest: Manifest = toml::from_str(&std::fs::read_to_string(&args.template_manifest).unwrap()).unwrap();
-    let file = tpl_dir.join(manifest.template);
+    match args.command {
+        Command::New { run: run_args } => run(run_args, None, &trust()?),
+        Command::Add { run: run_args, app } => run(run_args, Some(app), &trust()?),
+        Command::Info { template } => info(&template, &find_template(&template)?, &trust()?),
+        Command::Validate { template } => validate(&find_template(&template)?, &trust()?),
+        Command::Filters { template } => list_filters(&find_template(&template)?, &trust()?),
+        Command::Install { source, git_ref, replace } => install(&source, git_ref.as_deref(), replace, &trust()?),
+        Command::List { tag } => list(tag.as_deref()),
+        Command::Remove { id } => library::Library::new()?.remove(&id),
+        Command::Pack { template, output } => pack(&find_template(&template)?, output, &trust()?),
+        Command::Keygen { key, replace } => keygen(key, replace),
+        Command::Sign { bundle, key } => sign(&bundle, key),
+        Command::Trust { public_key, name } => {
+            let path = match args.trusted_keys {
+                Some(path) => path,
+                None => signing::TrustedKeys::default_path()?,
+            };
+            signing::TrustedKeys::add(&path, &name, &public_key)?;
+            println!("Trusted bundles signed by '{name}'");
+            Ok(())
+        }
+    }
+}
 
-    let name = safeify(&args.name);
+// A template (or bundle) on disk, or else an installed template
+fn find_template(template: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(template);
+    if path.exists() {
+        return Ok(path);
+    }
+    library::Library::new()?.installed_dir(template)
+}
 
-    let mut parser_builder = liquid::ParserBuilder::with_stdlib()
-        .filter(crate::filters::KebabCaseFilterParser)
-        .filter(crate::filters::PascalCaseFilterParser)
-        .filter(crate::filters::DottedPascalCaseFilterParser)
-        .filter(crate::filters::SnakeCaseFilterParser)
-        .filter(crate::filters::HttpWildcardFilterParser);
+fn run(args: RunArgs, existing_app_dir: Option<PathBuf>, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    let components = component_cache::ComponentCache::new()?;
+    let template = Template::load(&find_template(&args.template)?, args.accept_defaults, &args.limits, trust, &components)?;
+    let manifest = &template.manifest;
+    manifest.check_supports(if existing_app_dir.is_some() { Mode::Add } else { Mode::New })?;
 
-    for (name, filter_path) in &manifest.filter {
-        let wasm_path = tpl_dir.join("filters").join(filter_path);
-        parser_builder = parser_builder.filter(custom_filter::CustomFilterParser::load(name, &wasm_path)?);
+    let app_manifest_file = "spin.toml";
+    if let Some(app_dir) = &existing_app_dir {
+        if !app_dir.join(app_manifest_file).is_file() {
+            anyhow::bail!("{} is not an app directory: it doesn't contain {app_manifest_file}", app_dir.display());
+        }
+        // Only an existing app can be affected by capabilities, so a new app needs no consent
+        if !consent(manifest, &args)? {
+            println!("The template was not run");
+            return Ok(());
+        }
     }
 
-    let parser = parser_builder.build()?;
+    let name = safeify(&args.name);
+    let template_files = template.files.clone();
+    let limits = template.limits;
+    let store = &template.store;
+
+    let parser = template.parser()?;
 
     let initial_variables = [
         ("project-name", name.as_str()),
         ("authors", "merlin-the-happy-pig"),  // This would come from the git environment etc.
     ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
 
-    let execution_context = ExecutionContext::new(initial_variables, parser);
-
-    let mut config = wasmtime::Config::new();
-    config.wasm_component_model(true);
-    let engine = wasmtime::Engine::new(&config).expect("shoulda engined");
+    let execution_context = ExecutionContext::new(initial_variables, parser, manifest.rendering)
+        .with_partials(template.partials());
 
-    let component = wasmtime::component::Component::from_file(&engine, &file).expect("shoulda loaded a component");
-
-    let mut linker = wasmtime::component::Linker::new(&engine);
-    RunTemplate::add_to_linker(&mut linker, |state: &mut Host| state).expect("shoulda added to linker");
-
-    let mut host = Host::new(&content_dir, args.accept_defaults);
-    let execution_context_rsrc = host.execution_contexts.push(execution_context.clone())?;
+    let execution_context_rsrc = store.lock().unwrap().data_mut().execution_contexts.push(execution_context.clone())?;
     let execution_context_rsrc_rep = execution_context_rsrc.rep();
 
-    let store = wasmtime::Store::new(&engine, host);
-    let store = Arc::new(RwLock::new(store));
-
-    let mode = match args.add_to.as_ref() {
-        Some(manifest) => fermyon::spin_template::types::CreateMode::AddTo(manifest.file_name().expect("shoulda had a file name").to_string_lossy().to_string()),
+    let mode = match &existing_app_dir {
+        Some(_) => fermyon::spin_template::types::CreateMode::AddTo(app_manifest_file.to_owned()),
         None => fermyon::spin_template::types::CreateMode::CreateNew,
     };
     let options = fermyon::spin_template::types::RunOptions {
@@ -92,46 +252,38 @@ fn main() -> anyhow::Result<()> {
         use_default_values: false,
     };
 
-    let (bindings, _instance, actions) = {
+    let actions = {
         // scope the unlock of the store
         use std::ops::DerefMut;
-        let mut store = store.write().unwrap();
-        let (bindings, _instance) = RunTemplate::instantiate(store.deref_mut(), &component, &linker).expect("shoulda instantiated");
-        let actions = bindings.fermyon_spin_template_template().call_run(store.deref_mut(), execution_context_rsrc, &options);
-        (bindings, _instance, actions)
+        let mut store = store.lock().unwrap();
+        limits.refuel(store.deref_mut())?;
+        template.bindings.fermyon_spin_template_template().call_run(store.deref_mut(), execution_context_rsrc, &options)
     };
 
     let action_executor = if args.dry_run {
         actions::dry_run()
     } else {
-        let output_dir = PathBuf::from(&name);
-        let existing_app_dir = args.add_to.map(|f| f.parent().expect("stop passing the root directory, I have warned you before").to_owned());
-        let (output_dir, edit_dir_base) = match &existing_app_dir {
-            None => (output_dir.clone(), output_dir),
-            Some(ead) => {
-                let outdir = if manifest.create_own_subdirs_for_add {
-                    ead.to_owned()
-                } else {
-                    ead.join(output_dir)
-                };
-                (outdir, ead.to_owned())
-            }
+        let destination = match &existing_app_dir {
+            None => actions::Destination::new_app(&name),
+            Some(ead) => actions::Destination::add_to(ead, app_manifest_file, &name, manifest),
         };
-        // println!("***EXISTING APP DIR {existing_app_dir:?}, OUTPUT DIR {output_dir:?}, EDIT DIR {edit_dir_base:?}");
-        actions::apply(&store, bindings, &execution_context, execution_context_rsrc_rep, &content_dir, &output_dir, &edit_dir_base)
+        actions::apply(store, template.bindings, &execution_context, execution_context_rsrc_rep, template_files, destination)
     };
 
     let actions = match actions {
         Ok(Ok(actions)) => actions,
         Ok(Err(exports::fermyon::spin_template::template::Error::Cancel)) => return Ok(()),
+        Ok(Err(exports::fermyon::spin_template::template::Error::Template(e))) => return Err(render_error::RenderError::from(e).into()),
         Ok(Err(e)) => return Err(e.into()),
         Err(e) => return match e.downcast::<DialogueTrap>() {
                 Ok(DialogueTrap::Cancel) => Ok(()),
                 Ok(DialogueTrap::Error(e)) => Err(e.into()),
-                Err(e) => return Err(e),
+                Err(e) => return Err(limits::template_error(e, &limits)),
             }
     };
 
+    let _bound_contexts = actions::BoundContexts::new(store, &actions);
+    action_executor.check(&actions)?;
     for action in &actions {
         action_executor.execute(action)?;
     };
@@ -140,13 +292,180 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-#[derive(Deserialize)]
-struct Manifest {
-    template: String,
-    #[serde(default)]
-    create_own_subdirs_for_add: bool,
-    #[serde(default)]
-    filter: HashMap<String, PathBuf>,
+// Shows what the template may do to the existing app beyond generating its own
+// files, and asks the user whether to allow it
+fn consent(manifest: &manifest::Manifest, args: &RunArgs) -> anyhow::Result<bool> {
+    if manifest.capabilities.is_empty() {
+        return Ok(true);
+    }
+    println!("Template '{}' asks to:", manifest.id);
+    for capability in &manifest.capabilities {
+        println!("  - {} ({capability})", capability.description());
+    }
+    if args.dry_run || args.allow_capabilities {
+        return Ok(true);
+    }
+    let answer = dialoguer::Confirm::new()
+        .with_prompt("Allow this?")
+        .default(false)
+        .interact_opt()
+        .map_err(|e| anyhow::anyhow!("Can't ask whether to allow this ({e}). Use --allow-capabilities to allow it without asking."))?;
+    Ok(answer.unwrap_or(false))
+}
+
+// `template` is what the user asked for, which may not be an installed template's id
+fn info(template_arg: &str, path: &Path, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    let components = component_cache::ComponentCache::new()?;
+    let template = Template::load(path, false, &Default::default(), trust, &components)?;
+    let manifest = &template.manifest;
+
+    let list = |items: &[String]| if items.is_empty() { "(none)".to_owned() } else { items.join(", ") };
+
+    println!("{}: {}", manifest.id, manifest.name);
+    println!("  {}", manifest.description);
+    println!();
+    println!("Tags:          {}", list(&manifest.tags));
+    println!("Modes:         {}", list(&manifest.modes.iter().map(|m| m.to_string()).collect::<Vec<_>>()));
+    println!("WIT version:   {}", manifest.wit_version);
+    if let Some(version) = &manifest.min_host_version {
+        println!("Needs host:    {version} or later");
+    }
+    println!("Component:     {}", manifest.template);
+    println!("Capabilities:  {}", list(&manifest.capabilities.iter().map(|c| c.to_string()).collect::<Vec<_>>()));
+    if let template_files::TemplateFiles::Bundle(_) = &template.files {
+        match &template.signer {
+            Some(signer) => println!("Signed by:     {} ({})", signer.name, signer.public_key),
+            None => println!("Signed by:     (not signed by a trusted key)"),
+        }
+    }
+    println!();
+
+    print_template_extensions(&template);
+    println!("Run `run-template filters {template_arg}` to see every filter available to the template.");
+    Ok(())
+}
+
+fn validate(path: &Path, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    let components = component_cache::ComponentCache::new()?;
+    // Loading checks the manifest, and that each component loads and targets the right world
+    let template = Template::load(path, false, &Default::default(), trust, &components)?;
+    template.parser()?;
+
+    let content_dir = Path::new(template::CONTENT_DIR);
+    if !template.files.is_dir(content_dir) {
+        anyhow::bail!("The template has no content directory (expected {})", template.files.display(content_dir));
+    }
+
+    println!(
+        "Template '{}' is valid: {} template filters, {} filter aliases, {} tags",
+        template.manifest.id,
+        template.filters.len(),
+        template.aliases.len(),
+        template.tags.len()
+    );
+    Ok(())
+}
+
+fn install(source: &Path, git_ref: Option<&str>, replace: bool, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    let checkout = git_ref.map(|git_ref| library::git_checkout(source, git_ref)).transpose()?;
+    let source = checkout.as_ref().map_or(source, |(_, path)| path);
+
+    // Check everything loads before installing, rather than when the template is first run.
+    // This is also when a bundle's signature is checked, as the installed copy isn't signed.
+    let components = component_cache::ComponentCache::new()?;
+    let template = Template::load(source, false, &Default::default(), trust, &components)?;
+
+    let dir = library::Library::new()?.install(&template.files, &template.manifest_path, &template.manifest, replace)?;
+    println!("Installed template '{}' into {}", template.manifest.id, dir.display());
+    Ok(())
+}
+
+fn pack(path: &Path, output: Option<PathBuf>, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    // As with installing, check everything loads so that a broken template isn't shared
+    let components = component_cache::ComponentCache::new()?;
+    let template = Template::load(path, false, &Default::default(), trust, &components)?;
+
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("{}.{}", template.manifest.id, bundle::EXTENSION)));
+    let files = template.files.self_contained(&template.manifest_path, &template.manifest)?;
+    bundle::Bundle::write(&files, &output, None)?;
+    println!("Packed template '{}' ({} files) into {}", template.manifest.id, files.len(), output.display());
+    Ok(())
+}
+
+fn keygen(path: Option<PathBuf>, replace: bool) -> anyhow::Result<()> {
+    let path = match path {
+        Some(path) => path,
+        None => signing::SigningKey::default_path()?,
+    };
+    let key = signing::SigningKey::generate(&path, replace)?;
+    println!("Wrote signing key to {}. Keep it secret.", path.display());
+    println!("Public key: {}", key.public_key());
+    println!("To trust bundles signed with it, run `run-template trust {} --name <your name>`", key.public_key());
+    Ok(())
+}
+
+fn sign(path: &Path, key: Option<PathBuf>) -> anyhow::Result<()> {
+    let key_path = match key {
+        Some(path) => path,
+        None => signing::SigningKey::default_path()?,
+    };
+    let key = signing::SigningKey::load(&key_path)?;
+    // Opening checks the files against the index, so this can't sign a damaged bundle
+    let bundle = bundle::Bundle::open(path)?;
+    bundle::Bundle::write(&bundle.files, path, Some(&key))?;
+    println!("Signed {} with key {}", path.display(), key.public_key());
+    Ok(())
+}
+
+fn list(tag: Option<&str>) -> anyhow::Result<()> {
+    let (manifests, broken) = library::Library::new()?.list(tag)?;
+    for error in &broken {
+        eprintln!("Warning: skipping an installed template: {error:#}");
+    }
+    if manifests.is_empty() {
+        match tag {
+            Some(tag) => println!("No installed templates have the tag '{tag}'"),
+            None => println!("No templates are installed. Install one with `run-template install <template>`."),
+        }
+    }
+    for manifest in &manifests {
+        let modes = manifest.modes.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", ");
+        println!("{:<20}{} ({modes})", manifest.id, manifest.name);
+        if !manifest.tags.is_empty() {
+            println!("{:<20}tags: {}", "", manifest.tags.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn list_filters(path: &Path, trust: &signing::TrustPolicy) -> anyhow::Result<()> {
+    let components = component_cache::ComponentCache::new()?;
+    let template = Template::load(path, false, &Default::default(), trust, &components)?;
+
+    filter_docs::print_section("Liquid standard filters", filters::standard_filters().iter().map(|f| filter_docs::FilterDoc::new(f.reflection())));
+    filter_docs::print_section("Built-in filters", filters::builtin_filters().iter().map(|f| filter_docs::FilterDoc::new(f.reflection())));
+    print_template_extensions(&template);
+
+    Ok(())
+}
+
+// The filters, aliases and tags that the template adds to Liquid
+fn print_template_extensions(template: &Template) {
+    let source_note = |source: &Path, registered: &str, described: &str| {
+        let wasm_file = source.display().to_string();
+        if described == registered {
+            wasm_file
+        } else {
+            format!("{wasm_file}, which calls it `{described}`")
+        }
+    };
+
+    filter_docs::print_section("Template filters", template.filters.iter().map(|f| {
+        let note = source_note(f.source(), f.reflection().name(), f.described_name());
+        filter_docs::FilterDoc::new(f.reflection()).with_note(note)
+    }));
+    filter_docs::print_section("Template filter aliases", template.aliases.iter().map(|f| filter_docs::FilterDoc::new(f.reflection())));
+    filter_docs::print_tag_section("Template tags", template.tags.iter().map(|t| (t, source_note(t.source(), t.name(), t.described_name()))));
 }
 
 fn safeify(text: &str) -> String {
@@ -154,3 +473,50 @@ fn safeify(text: &str) -> String {
     let s = unsafe_chars.replace_all(text, "-");
     s.to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add_takes_the_app_and_the_run_options() {
+        let args = Args::try_parse_from(["run-template", "add", "http-rust", "api", "--app", "myapp", "--dry-run", "--fuel", "1000"]).unwrap();
+        let Command::Add { run, app } = args.command else { panic!("expected the add command") };
+        assert_eq!(("http-rust", "api"), (run.template.as_str(), run.name.as_str()));
+        assert_eq!(Path::new("myapp"), app);
+        assert!(run.dry_run);
+        assert_eq!(Some(1000), run.limits.fuel);
+
+        let args = Args::try_parse_from(["run-template", "add", "http-rust", "api"]).unwrap();
+        let Command::Add { app, .. } = args.command else { panic!("expected the add command") };
+        assert_eq!(Path::new("."), app);
+    }
+
+    #[test]
+    fn install_takes_a_git_ref() {
+        let args = Args::try_parse_from(["run-template", "install", "../http-rust/template", "--git-ref", "v1.0", "--replace"]).unwrap();
+        let Command::Install { source, git_ref, replace } = args.command else { panic!("expected the install command") };
+        assert_eq!(Path::new("../http-rust/template"), source);
+        assert_eq!(Some("v1.0"), git_ref.as_deref());
+        assert!(replace);
+
+        assert!(Args::try_parse_from(["run-template", "install", "../http-rust/template", "--git-ref"]).is_err());
+    }
+
+    #[test]
+    fn trust_options_go_before_or_after_the_command() {
+        for argv in [
+            ["run-template", "--allow-unsigned", "--trusted-keys", "keys.toml", "info", "http-rust.rtb"],
+            ["run-template", "info", "http-rust.rtb", "--allow-unsigned", "--trusted-keys", "keys.toml"],
+        ] {
+            let args = Args::try_parse_from(argv).unwrap();
+            assert!(args.allow_unsigned);
+            assert_eq!(Some(PathBuf::from("keys.toml")), args.trusted_keys);
+            assert!(matches!(args.command, Command::Info { template } if template == "http-rust.rtb"));
+        }
+
+        let args = Args::try_parse_from(["run-template", "list"]).unwrap();
+        assert!(!args.allow_unsigned);
+        assert_eq!(None, args.trusted_keys);
+    }
+}
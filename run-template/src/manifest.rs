@@ -0,0 +1,280 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::host::RenderMode;
+use crate::limits::LimitsConfig;
+use crate::template_files::TemplateFiles;
+
+/// `spin-template.toml`. Unknown keys are an error, so that typos don't
+/// silently change what a template does.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    /// Identifies the template, e.g. `http-rust`: lower case letters, digits and hyphens.
+    pub id: String,
+    /// A human-friendly name, e.g. "HTTP handler (Rust)".
+    pub name: String,
+    pub description: String,
+    /// Keywords for finding the template, in the same form as ids.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether the template can create new apps, add to existing ones, or both (the default).
+    #[serde(default = "Mode::all")]
+    pub modes: Vec<Mode>,
+    /// The oldest version of `run-template` that can run the template.
+    pub min_host_version: Option<semver::Version>,
+    /// The version of the template WIT that the component was built against, e.g. "0.0.2".
+    pub wit_version: String,
+
+    /// The template component, relative to the manifest.
+    pub template: String,
+    #[serde(default)]
+    pub create_own_subdirs_for_add: bool,
+    #[serde(default)]
+    pub filter: HashMap<String, PathBuf>,
+    #[serde(default)]
+    pub filter_pack: Vec<Pack>,
+    /// Components providing custom tags and blocks, from the `tags` directory.
+    #[serde(default)]
+    pub tag_pack: Vec<Pack>,
+    /// Named pipelines of filters, e.g. `component_id = "kebab_case | truncate: 40"`.
+    #[serde(default)]
+    pub filter_alias: HashMap<String, String>,
+    /// Whether undefined variables are an error ("strict", the default) or
+    /// render as empty text ("lenient").
+    #[serde(default)]
+    pub rendering: RenderMode,
+    /// Overrides the CPU and memory budgets for the template component.
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    /// What the template does to an existing app beyond generating into its own
+    /// output directory. The user is asked to grant these before it runs.
+    #[serde(default)]
+    pub capabilities: Vec<Capability>,
+}
+
+/// A component providing several filters (or tags), all of which are registered
+/// under the names they describe themselves with, plus the prefix if there is one.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pack {
+    pub path: PathBuf,
+    pub prefix: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Creating a new app.
+    New,
+    /// Adding to an existing app.
+    Add,
+}
+
+impl Mode {
+    fn all() -> Vec<Self> {
+        vec![Self::New, Self::Add]
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::New => f.write_str("new"),
+            Self::Add => f.write_str("add"),
+        }
+    }
+}
+
+/// Something a template can do to an existing app besides generating into its
+/// output directory. A template that tries to do one of these without declaring
+/// it is stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    /// Change the app manifest, e.g. to add a component.
+    EditAppManifest,
+    /// Create or change files in the app outside the template's output directory.
+    EditOutsideOutput,
+    /// Read files that were in the app before the template ran, which it must
+    /// do to change them.
+    ReadAppFiles,
+    /// Replace files that were in the app before the template ran.
+    OverwriteAppFiles,
+}
+
+impl Capability {
+    /// What the capability lets a template do, for asking the user.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::EditAppManifest => "change the app manifest",
+            Self::EditOutsideOutput => "create or change files outside the directory it generates into",
+            Self::ReadAppFiles => "read (and change) files that are already in the app",
+            Self::OverwriteAppFiles => "replace files that are already in the app",
+        }
+    }
+}
+
+impl Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EditAppManifest => f.write_str("edit-app-manifest"),
+            Self::EditOutsideOutput => f.write_str("edit-outside-output"),
+            Self::ReadAppFiles => f.write_str("read-app-files"),
+            Self::OverwriteAppFiles => f.write_str("overwrite-app-files"),
+        }
+    }
+}
+
+impl Manifest {
+    /// Reads and validates a template manifest.
+    pub fn load(files: &TemplateFiles, path: &Path) -> anyhow::Result<Self> {
+        let text = files.read_to_string(path)
+            .with_context(|| format!("Failed to read template manifest {}", files.display(path)))?;
+        Self::parse(&text)
+            .with_context(|| format!("{} is not a valid template manifest", files.display(path)))
+    }
+
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let manifest: Self = toml::from_str(text)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    // Checks the things that the TOML structure can't express, and reports them all at once
+    fn validate(&self) -> anyhow::Result<()> {
+        let mut problems = vec![];
+
+        if !is_id(&self.id) {
+            problems.push(format!("`id` is '{}', but must be lower case letters, digits and hyphens, e.g. 'http-rust'", self.id));
+        }
+        if self.name.trim().is_empty() {
+            problems.push("`name` is empty".to_owned());
+        }
+        if self.description.trim().is_empty() {
+            problems.push("`description` is empty".to_owned());
+        }
+        for (index, tag) in self.tags.iter().enumerate() {
+            if !is_id(tag) {
+                problems.push(format!("tag '{tag}' must be lower case letters, digits and hyphens"));
+            } else if self.tags[..index].contains(tag) {
+                problems.push(format!("tag '{tag}' is listed more than once"));
+            }
+        }
+
+        if self.modes.is_empty() {
+            problems.push("`modes` is empty, so the template can't be used: list \"new\", \"add\" or both".to_owned());
+        }
+        for (index, mode) in self.modes.iter().enumerate() {
+            if self.modes[..index].contains(mode) {
+                problems.push(format!("mode '{mode}' is listed more than once"));
+            }
+        }
+        if self.create_own_subdirs_for_add && !self.modes.contains(&Mode::Add) {
+            problems.push("`create_own_subdirs_for_add` is set, but the template doesn't support the 'add' mode".to_owned());
+        }
+        for (index, capability) in self.capabilities.iter().enumerate() {
+            if self.capabilities[..index].contains(capability) {
+                problems.push(format!("capability '{capability}' is listed more than once"));
+            }
+        }
+
+        if let Err(e) = self.limits.memory_bytes() {
+            problems.push(format!("in `[limits]`, {e}"));
+        }
+
+        let host_version = host_version();
+        if let Some(min_version) = &self.min_host_version {
+            if min_version > &host_version {
+                problems.push(format!("the template needs run-template {min_version} or later, but this is {host_version}"));
+            }
+        }
+        if self.wit_version != crate::world::WIT_VERSION {
+            problems.push(format!(
+                "the template was built for version {} of the template WIT, but this host supports version {}",
+                self.wit_version,
+                crate::world::WIT_VERSION
+            ));
+        }
+
+        if !problems.is_empty() {
+            anyhow::bail!("{}", problems.join("\n"));
+        }
+        Ok(())
+    }
+
+    /// Checks that the template can be run in `mode`.
+    pub fn check_supports(&self, mode: Mode) -> anyhow::Result<()> {
+        if !self.modes.contains(&mode) {
+            let supported = self.modes.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", ");
+            anyhow::bail!("Template '{}' doesn't support the '{mode}' mode (it supports: {supported})", self.id);
+        }
+        Ok(())
+    }
+}
+
+fn host_version() -> semver::Version {
+    semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("package version should be semver")
+}
+
+fn is_id(text: &str) -> bool {
+    !text.is_empty()
+        && text.split('-').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+        id = "http-rust"
+        name = "HTTP handler (Rust)"
+        description = "An HTTP handler written in Rust"
+        wit_version = "0.0.2"
+        template = "template.wasm"
+    "#;
+
+    #[test]
+    fn minimal_manifest_supports_all_modes() {
+        let manifest = Manifest::parse(MINIMAL).unwrap();
+        assert_eq!(vec![Mode::New, Mode::Add], manifest.modes);
+        manifest.check_supports(Mode::Add).unwrap();
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = Manifest::parse(&format!("{MINIMAL}\ncreate_own_subdir_for_add = true")).unwrap_err();
+        assert!(err.to_string().contains("unknown field `create_own_subdir_for_add`"), "{err}");
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let text = MINIMAL
+            .replace("http-rust", "HTTP Rust")
+            .replace("0.0.2", "0.0.3")
+            + "tags = [\"http\", \"http\"]\nmodes = [\"new\"]\nmin_host_version = \"999.0.0\"\ncreate_own_subdirs_for_add = true\n"
+            + "capabilities = [\"read-app-files\", \"read-app-files\"]\n"
+            + "[limits]\nmemory_mib = 9223372036854775807";
+        let err = Manifest::parse(&text).unwrap_err().to_string();
+        assert_eq!(7, err.lines().count(), "{err}");
+        assert!(err.contains("`id` is 'HTTP Rust'"), "{err}");
+        assert!(err.contains("tag 'http' is listed more than once"), "{err}");
+        assert!(err.contains("run-template 999.0.0 or later"), "{err}");
+        assert!(err.contains("version 0.0.3 of the template WIT"), "{err}");
+        assert!(err.contains("capability 'read-app-files' is listed more than once"), "{err}");
+        assert!(err.contains("`memory_mib` is 9223372036854775807, which is too large"), "{err}");
+    }
+
+    #[test]
+    fn unsupported_modes_are_refused() {
+        let manifest = Manifest::parse(&format!("{MINIMAL}\nmodes = [\"new\"]")).unwrap();
+        let err = manifest.check_supports(Mode::Add).unwrap_err();
+        assert_eq!("Template 'http-rust' doesn't support the 'add' mode (it supports: new)", err.to_string());
+    }
+}
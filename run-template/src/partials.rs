@@ -0,0 +1,86 @@
+use std::{borrow::Cow, path::{Path, PathBuf}};
+
+use liquid_core::partials::PartialSource;
+
+use crate::paths::resolve_template_path;
+use crate::template_files::TemplateFiles;
+
+/// Serves Liquid partials (`{% include "name" %}`) from the template's
+/// `partials` directory. Partial names are paths relative to that directory.
+#[derive(Debug)]
+pub struct PartialsDir {
+    files: TemplateFiles,
+    root: PathBuf,
+    names: Vec<String>,
+}
+
+impl PartialsDir {
+    /// `root` is the partials directory, relative to the template.
+    pub fn new(files: TemplateFiles, root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref().to_owned();
+        let names = files.files_in(&root).iter().map(|p| partial_name(p)).collect();
+        Self { files, root, names }
+    }
+
+    fn resolve(&self, name: &str) -> Option<PathBuf> {
+        resolve_template_path(&self.root, name).ok().filter(|p| self.files.is_file(p))
+    }
+}
+
+impl PartialSource for PartialsDir {
+    fn contains(&self, name: &str) -> bool {
+        self.resolve(name).is_some()
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.names.iter().map(|s| s.as_str()).collect()
+    }
+
+    fn try_get<'a>(&'a self, name: &str) -> Option<Cow<'a, str>> {
+        let path = self.resolve(name)?;
+        self.files.read_to_string(&path).ok().map(Cow::Owned)
+    }
+}
+
+fn partial_name(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn partials_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("run-template-partials-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("partials").join("spin")).unwrap();
+        std::fs::write(dir.join("partials").join("spin").join("component.toml"), "[component.{{ name }}]").unwrap();
+        dir
+    }
+
+    fn render(dir: &Path, template: &str) -> liquid_core::Result<String> {
+        let partials = PartialsDir::new(TemplateFiles::Dir(dir.to_owned()), "partials");
+        let parser = liquid::ParserBuilder::with_stdlib()
+            .partials(liquid::partials::LazyCompiler::new(partials))
+            .build()?;
+        parser.parse(template)?.render(&liquid::object!({ "name": "spork" }))
+    }
+
+    #[test]
+    fn includes_are_served_from_the_partials_directory() {
+        let dir = partials_dir("include");
+        let text = render(&dir, "# app\n{% include \"spin/component.toml\" %}").unwrap();
+        assert_eq!("# app\n[component.spork]", text);
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_partials_are_errors() {
+        let dir = partials_dir("missing");
+        let err = render(&dir, "{% include \"spin/trigger.toml\" %}").unwrap_err();
+        assert!(err.to_string().contains("spin/trigger.toml"), "{err}");
+        _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Resolves a path that a template refers to (a content file, a partial, etc.)
+/// against the template directory it belongs in. Paths must be relative and
+/// must not climb out of that directory.
+pub fn resolve_template_path(root: impl AsRef<Path>, relative: impl AsRef<Path>) -> anyhow::Result<PathBuf> {
+    let relative = relative.as_ref();
+
+    if relative.as_os_str().is_empty() {
+        anyhow::bail!("Template path is empty");
+    }
+
+    for component in relative.components() {
+        match component {
+            Component::Normal(_) | Component::CurDir => (),
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!("Template path '{}' must be relative and may not contain '..'", relative.display());
+            }
+        }
+    }
+
+    Ok(root.as_ref().join(relative))
+}
+
+/// Resolves `.` and `..` in a path that a template writes to, which is relative
+/// to the app directory, without touching the filesystem, so that it can be
+/// compared with other paths. The path must not be absolute or climb out of the app.
+pub fn normalise_app_path(path: impl AsRef<Path>) -> anyhow::Result<PathBuf> {
+    let path = path.as_ref();
+    let mut normalised = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalised.push(part),
+            Component::CurDir => (),
+            Component::ParentDir if normalised.pop() => (),
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                anyhow::bail!("'{}' is outside the app directory", path.display());
+            }
+        }
+    }
+    Ok(normalised)
+}
@@ -0,0 +1,370 @@
+use std::fmt::Display;
+
+use crate::bindings::fermyon::spin_template::types::{Error, TemplateError, TemplateErrorKind};
+
+// Liquid doesn't expose the structure of its errors, only a Display impl, so
+// we pick that apart to find out where in the template the problem was.  Parse
+// errors from pest carry a line and column; render errors only carry context
+// such as the variable or filter that failed, so for those we go looking for
+// the first tag or output block that mentions it.
+
+#[derive(Debug, Clone)]
+pub struct RenderError {
+    pub kind: TemplateErrorKind,
+    pub message: String,
+    pub source_file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub snippet: Option<String>,
+}
+
+impl RenderError {
+    pub fn parse(template: &str, error: liquid::Error) -> Self {
+        Self::from_liquid(TemplateErrorKind::Parse, template, error)
+    }
+
+    pub fn render(template: &str, error: liquid::Error) -> Self {
+        Self::from_liquid(TemplateErrorKind::Render, template, error)
+    }
+
+    /// Reports all the variables that a template used but that had not been set.
+    /// Variables used in partials are listed with the partial's name and their
+    /// position in it; the error's own position is that of the first variable
+    /// used in the template itself.
+    pub fn undefined_variables(template: &str, variables: &[UndefinedVariable]) -> Self {
+        let listed = variables.iter()
+            .map(|variable| {
+                let position = locate_variable(variable.text(template), &variable.name);
+                match (&variable.partial, position) {
+                    (None, Some((line, column))) => format!("{} ({line}:{column})", variable.name),
+                    (None, None) => variable.name.clone(),
+                    (Some((partial, _)), Some((line, column))) => format!("{} (partial {partial} {line}:{column})", variable.name),
+                    (Some((partial, _)), None) => format!("{} (partial {partial})", variable.name),
+                }
+            })
+            .collect::<Vec<_>>();
+        let position = variables.iter()
+            .filter(|variable| variable.partial.is_none())
+            .find_map(|variable| locate_variable(template, &variable.name));
+
+        Self {
+            kind: TemplateErrorKind::UndefinedVariables,
+            message: format!("Undefined variables: {}", listed.join(", ")),
+            source_file: None,
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            snippet: position.and_then(|(line, _)| snippet(template, line)),
+        }
+    }
+
+    /// Records the content file that the template came from.
+    pub fn in_file(mut self, source_file: impl Into<String>) -> Self {
+        self.source_file = Some(source_file.into());
+        self
+    }
+
+    fn from_liquid(kind: TemplateErrorKind, template: &str, error: liquid::Error) -> Self {
+        let details = LiquidErrorDetails::from_display(&error.to_string());
+
+        let position = details.position.or_else(|| details.locate(template));
+
+        Self {
+            kind,
+            message: details.message(),
+            source_file: None,
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            snippet: position.and_then(|(line, _)| snippet(template, line)),
+        }
+    }
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            TemplateErrorKind::Parse => "parse",
+            TemplateErrorKind::Render => "render",
+            TemplateErrorKind::UndefinedVariables => "render",
+        };
+        let file = self.source_file.as_deref().unwrap_or("<template>");
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{file}:{line}:{column}: {kind} error: {}", self.message)?,
+            _ => write!(f, "{file}: {kind} error: {}", self.message)?,
+        }
+        if let (Some(line), Some(snippet)) = (self.line, &self.snippet) {
+            write!(f, "\n  {line} | {snippet}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<RenderError> for TemplateError {
+    fn from(value: RenderError) -> Self {
+        Self {
+            kind: value.kind,
+            message: value.message,
+            source_file: value.source_file,
+            line: value.line,
+            column: value.column,
+            snippet: value.snippet,
+        }
+    }
+}
+
+impl From<TemplateError> for RenderError {
+    fn from(value: TemplateError) -> Self {
+        Self {
+            kind: value.kind,
+            message: value.message,
+            source_file: value.source_file,
+            line: value.line,
+            column: value.column,
+            snippet: value.snippet,
+        }
+    }
+}
+
+impl From<RenderError> for Error {
+    fn from(value: RenderError) -> Self {
+        Self::Template(value.into())
+    }
+}
+
+/// A variable that a template used but that had not been set.
+pub struct UndefinedVariable {
+    pub name: String,
+    /// The name and text of the partial it was used in, if it wasn't used in the
+    /// template itself. The text is empty if the partial can't be read.
+    pub partial: Option<(String, String)>,
+}
+
+impl UndefinedVariable {
+    /// The text of the template or partial that the variable was used in.
+    pub fn text<'a>(&'a self, template: &'a str) -> &'a str {
+        self.partial.as_ref().map_or(template, |(_, text)| text)
+    }
+
+    /// Whether every use of the variable is as the input to `default`, as in
+    /// `{{ name | default: "x" }}`, which gives it a fallback.
+    pub fn has_default(&self, template: &str) -> bool {
+        let name = regex::escape(&self.name);
+        let (Ok(used), Ok(defaulted)) = (
+            regex::Regex::new(&format!(r"(^|[^-\w.]){name}($|[^-\w])")),
+            regex::Regex::new(&format!(r"(^|[^-\w.]){name}\s*\|\s*default\b")),
+        ) else {
+            return false;
+        };
+
+        let blocks = regex::Regex::new(r"(?s)\{[{%].*?[}%]\}").expect("invalid block regex");
+        let (uses, defaults) = blocks.find_iter(self.text(template))
+            .fold((0, 0), |(uses, defaults), block| {
+                (uses + used.find_iter(block.as_str()).count(), defaults + defaulted.find_iter(block.as_str()).count())
+            });
+        defaults > 0 && uses == defaults
+    }
+}
+
+#[derive(Default)]
+struct LiquidErrorDetails {
+    message: String,
+    position: Option<(u32, u32)>,
+    context: Vec<(String, String)>,
+    /// The innermost partial that the error happened in.
+    partial: Option<String>,
+}
+
+impl LiquidErrorDetails {
+    // The Display format is:
+    //
+    // liquid: <message, or a pest report starting ` --> line:col`>
+    //   with:
+    //     key=value
+    // from: <trace>
+    //   with:
+    //     key=value
+    fn from_display(text: &str) -> Self {
+        let mut details = Self::default();
+        let text = text.strip_prefix("liquid: ").unwrap_or(text);
+
+        let mut lines = text.lines().peekable();
+        let first = lines.next().unwrap_or_default();
+
+        if let Some(pos) = first.trim().strip_prefix("--> ") {
+            details.position = parse_line_col(pos);
+            // The pest report runs until the `= message` line
+            for line in lines.by_ref() {
+                if let Some(msg) = line.trim().strip_prefix("= ") {
+                    details.message = msg.to_owned();
+                    break;
+                }
+            }
+        } else {
+            details.message = first.to_owned();
+        }
+
+        // An include's trace is followed by the name of the partial it included
+        let mut in_include = false;
+        for line in lines {
+            let line = line.trim();
+            if line.starts_with("from: ") {
+                in_include = details.partial.is_none() && line.starts_with("from: {% include ");
+                continue;
+            }
+            if line == "with:" {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                if in_include {
+                    details.partial = Some(value.trim().to_owned());
+                    in_include = false;
+                }
+                details.context.push((key.to_owned(), value.trim().to_owned()));
+            }
+        }
+
+        details
+    }
+
+    fn message(&self) -> String {
+        let salient = self.context.iter()
+            .filter(|(key, _)| !key.starts_with("available") && key != "input")
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect::<Vec<_>>();
+        if salient.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} ({})", self.message, salient.join(", "))
+        }
+    }
+
+    fn context_value(&self, key: &str) -> Option<&str> {
+        self.context.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn locate(&self, template: &str) -> Option<(u32, u32)> {
+        if let Some(filter) = self.context_value("requested filter") {
+            return locate_filter(template, filter);
+        }
+        if let Some(filter) = self.context_value("filter") {
+            // This is the filter with its arguments, so prefer an exact match, but Liquid's
+            // rendering of the arguments may not be quite how the author wrote them
+            let name = filter.split([' ', ':']).next().unwrap_or(filter);
+            return locate_filter_with_arguments(template, filter).or_else(|| locate_filter(template, name));
+        }
+        locate_variable(template, self.context_value("requested variable")?)
+    }
+}
+
+/// If the error is Liquid complaining about a variable that isn't in the
+/// globals, returns the name of the variable and the name of the partial it
+/// was used in, if it wasn't used in the template itself.
+pub fn undefined_variable(error: &liquid::Error) -> Option<(String, Option<String>)> {
+    let details = LiquidErrorDetails::from_display(&error.to_string());
+    if details.message != "Unknown variable" {
+        return None;
+    }
+    let name = details.context_value("requested variable")?.to_owned();
+    Some((name, details.partial))
+}
+
+fn locate_filter(template: &str, filter: &str) -> Option<(u32, u32)> {
+    locate_in_blocks(template, &format!(r"\|\s*{}\b", regex::escape(filter)))
+}
+
+fn locate_filter_with_arguments(template: &str, filter: &str) -> Option<(u32, u32)> {
+    let words = filter.split_whitespace().map(regex::escape).collect::<Vec<_>>();
+    locate_in_blocks(template, &format!(r"\|\s*{}\s*(\||-?[}}%]\}})", words.join(r"\s*")))
+}
+
+fn locate_variable(template: &str, variable: &str) -> Option<(u32, u32)> {
+    locate_in_blocks(template, &format!(r"(^|[^-\w.]){}($|[^-\w])", regex::escape(variable)))
+}
+
+// Finds the first Liquid tag or output block matching the pattern
+fn locate_in_blocks(template: &str, pattern: &str) -> Option<(u32, u32)> {
+    let needle = regex::Regex::new(pattern).ok()?;
+
+    let blocks = regex::Regex::new(r"(?s)\{[{%].*?[}%]\}").expect("invalid block regex");
+    let offset = blocks.find_iter(template)
+        .find(|block| needle.is_match(block.as_str()))?
+        .start();
+
+    let before = &template[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    Some((line as u32, column as u32))
+}
+
+fn snippet(template: &str, line: u32) -> Option<String> {
+    template.lines().nth((line as usize).saturating_sub(1)).map(|s| s.to_owned())
+}
+
+fn parse_line_col(text: &str) -> Option<(u32, u32)> {
+    let (line, column) = text.trim().split_once(':')?;
+    Some((line.parse().ok()?, column.parse().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_error(template: &str) -> RenderError {
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let err = parser.parse(template).err().expect("template should fail to parse");
+        RenderError::parse(template, err)
+    }
+
+    fn render_error(template: &str) -> RenderError {
+        let parser = liquid::ParserBuilder::with_stdlib().build().unwrap();
+        let tpl = parser.parse(template).unwrap();
+        let err = tpl.render(&liquid::Object::new()).expect_err("template should fail to render");
+        RenderError::render(template, err)
+    }
+
+    #[test]
+    fn parse_errors_carry_pest_position() {
+        let err = parse_error("name = 1\n{% if x %}\nstuff");
+        assert!(matches!(err.kind, TemplateErrorKind::Parse));
+        assert_eq!(Some(3), err.line);
+        assert!(err.message.contains("endif"), "{}", err.message);
+    }
+
+    #[test]
+    fn unknown_filter_is_located() {
+        let err = parse_error("first\nsecond {{ name | nosuch }}");
+        assert_eq!(Some(2), err.line);
+        assert_eq!(Some(8), err.column);
+        assert_eq!(Some("second {{ name | nosuch }}"), err.snippet.as_deref());
+        assert!(err.message.contains("nosuch"), "{}", err.message);
+        assert!(!err.message.contains("available"), "{}", err.message);
+    }
+
+    #[test]
+    fn bad_filter_arguments_are_located() {
+        let err = parse_error("a = \"{{ name | append: 'x' }}\"\nb = \"{{ name | append }}\"");
+        assert_eq!(Some(2), err.line);
+        assert_eq!(Some(6), err.column);
+    }
+
+    #[test]
+    fn unknown_variable_is_located() {
+        let err = render_error("route = \"/\"\nhttp = \"{{ http-path }}\"").in_file("spin.toml");
+        assert!(matches!(err.kind, TemplateErrorKind::Render));
+        assert_eq!(Some(2), err.line);
+        assert_eq!(Some(9), err.column);
+        assert_eq!(Some("spin.toml"), err.source_file.as_deref());
+        assert!(err.to_string().starts_with("spin.toml:2:9: render error: Unknown variable"), "{err}");
+    }
+
+    #[test]
+    fn undefined_variables_are_listed_with_positions() {
+        let template = "a = \"{{ http-path }}\"\nb = \"{{ project-name }}-{{ fruit }}\"";
+        let variables = ["http-path", "fruit"].map(|name| UndefinedVariable { name: name.to_owned(), partial: None });
+        let err = RenderError::undefined_variables(template, &variables);
+        assert!(matches!(err.kind, TemplateErrorKind::UndefinedVariables));
+        assert_eq!("Undefined variables: http-path (1:6), fruit (2:25)", err.message);
+        assert_eq!(Some(1), err.line);
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::bundle::Bundle;
+
+/// A bundle's signature: an Ed25519 signature of the bundle's index, which holds
+/// the hash of every file, together with the public key that made it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Signature {
+    pub public_key: String,
+    pub signature: String,
+}
+
+impl Signature {
+    fn verify(&self, index: &[u8]) -> anyhow::Result<()> {
+        let public_key = parse_public_key(&self.public_key)?;
+        let signature: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .context("the signature is not 64 bytes of hex")?;
+        public_key.verify_strict(index, &ed25519_dalek::Signature::from_bytes(&signature))?;
+        Ok(())
+    }
+}
+
+/// The user's key for signing bundles. The file holds the hex of the secret key,
+/// and is only readable by the user.
+pub struct SigningKey(ed25519_dalek::SigningKey);
+
+impl SigningKey {
+    /// `signing-key` in the user config directory, e.g. `~/.config/run-template` on Linux.
+    pub fn default_path() -> anyhow::Result<PathBuf> {
+        Ok(config_dir()?.join("signing-key"))
+    }
+
+    pub fn generate(path: &Path, replace: bool) -> anyhow::Result<Self> {
+        let mut secret = [0u8; 32];
+        getrandom::getrandom(&mut secret).context("Failed to generate a signing key")?;
+        let key = Self(ed25519_dalek::SigningKey::from_bytes(&secret));
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true);
+        if replace {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => anyhow::anyhow!("{} already exists. Use --replace to replace it.", path.display()),
+            _ => anyhow::Error::new(e).context(format!("Failed to write signing key {}", path.display())),
+        })?;
+        // The mode only applies to a new file, so a replaced key file may still be readable by others
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))
+            .with_context(|| format!("Failed to make signing key {} private", path.display()))?;
+        writeln!(file, "{}", hex::encode(key.0.to_bytes()))?;
+
+        Ok(key)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signing key {}. Run `run-template keygen` to create one.", path.display()))?;
+        let secret: [u8; 32] = hex::decode(text.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .with_context(|| format!("{} is not a signing key", path.display()))?;
+        Ok(Self(ed25519_dalek::SigningKey::from_bytes(&secret)))
+    }
+
+    pub fn public_key(&self) -> String {
+        hex::encode(self.0.verifying_key().to_bytes())
+    }
+
+    pub fn sign(&self, index: &[u8]) -> Signature {
+        use ed25519_dalek::Signer;
+        Signature {
+            public_key: self.public_key(),
+            signature: hex::encode(self.0.sign(index).to_bytes()),
+        }
+    }
+}
+
+/// The public keys whose signatures the user trusts, each with the name of whoever
+/// holds it. The file is TOML with a `[[key]]` table per key.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrustedKeys {
+    #[serde(default)]
+    key: Vec<TrustedKey>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrustedKey {
+    pub name: String,
+    pub public_key: String,
+}
+
+impl TrustedKeys {
+    /// `trusted-keys.toml` in the user config directory, e.g. `~/.config/run-template` on Linux.
+    pub fn default_path() -> anyhow::Result<PathBuf> {
+        Ok(config_dir()?.join("trusted-keys.toml"))
+    }
+
+    /// Loads the trusted keys. If the file doesn't exist, no keys are trusted.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trusted keys {}", path.display()))?;
+        let keys: Self = toml::from_str(&text)
+            .with_context(|| format!("{} is not a valid trusted keys file", path.display()))?;
+        for key in &keys.key {
+            parse_public_key(&key.public_key)
+                .with_context(|| format!("The key for '{}' in {} is not valid", key.name, path.display()))?;
+        }
+        Ok(keys)
+    }
+
+    /// Adds a key to the trusted keys file, creating the file if need be.
+    pub fn add(path: &Path, name: &str, public_key: &str) -> anyhow::Result<()> {
+        parse_public_key(public_key)?;
+        let mut keys = Self::load(path)?;
+        if let Some(existing) = keys.find(public_key) {
+            anyhow::bail!("That key is already trusted, as '{}'", existing.name);
+        }
+        keys.key.push(TrustedKey { name: name.to_owned(), public_key: public_key.to_owned() });
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, toml::to_string(&keys)?)
+            .with_context(|| format!("Failed to write trusted keys {}", path.display()))
+    }
+
+    fn find(&self, public_key: &str) -> Option<&TrustedKey> {
+        self.key.iter().find(|k| k.public_key.eq_ignore_ascii_case(public_key))
+    }
+}
+
+/// Which templates may be loaded. Bundles must be signed by a trusted key unless
+/// `allow_unsigned` is set. Template directories, including installed templates,
+/// are files the user already has, so they aren't signed; a bundle's signature
+/// is checked when it is installed.
+pub struct TrustPolicy {
+    keys: TrustedKeys,
+    keys_path: PathBuf,
+    allow_unsigned: bool,
+}
+
+impl TrustPolicy {
+    pub fn new(keys_path: Option<PathBuf>, allow_unsigned: bool) -> anyhow::Result<Self> {
+        let keys_path = match keys_path {
+            Some(path) => path,
+            None => TrustedKeys::default_path()?,
+        };
+        let keys = TrustedKeys::load(&keys_path)?;
+        Ok(Self { keys, keys_path, allow_unsigned })
+    }
+
+    /// Checks a bundle's signature before anything in it is loaded. Returns who
+    /// signed it, or `None` if it isn't signed by a trusted key but `allow_unsigned`
+    /// lets it through. A signature that doesn't match is an error regardless.
+    pub fn check(&self, bundle: &Bundle) -> anyhow::Result<Option<TrustedKey>> {
+        let path = bundle.path.display();
+        let Some(signature) = &bundle.signature else {
+            if self.allow_unsigned {
+                return Ok(None);
+            }
+            anyhow::bail!("{path} is not signed, so there's no telling who built it. If you trust where it came from, run again with --allow-unsigned.");
+        };
+
+        signature.verify(&bundle.index)
+            .with_context(|| format!("{path} has an invalid signature: it is damaged or has been tampered with since it was signed"))?;
+
+        match self.keys.find(&signature.public_key) {
+            Some(key) => Ok(Some(key.clone())),
+            None if self.allow_unsigned => Ok(None),
+            None => anyhow::bail!(
+                "{path} is signed by key {}, which is not in the trusted keys file {}. \
+                    If you trust whoever holds the key, add it with `run-template trust {} --name <name>`, or run again with --allow-unsigned.",
+                signature.public_key,
+                self.keys_path.display(),
+                signature.public_key
+            ),
+        }
+    }
+}
+
+fn parse_public_key(text: &str) -> anyhow::Result<ed25519_dalek::VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(text)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .with_context(|| format!("'{text}' is not a public key: it should be 32 bytes of hex"))?;
+    Ok(ed25519_dalek::VerifyingKey::from_bytes(&bytes)?)
+}
+
+fn config_dir() -> anyhow::Result<PathBuf> {
+    let dir = dirs::config_dir().context("Can't find the user config directory")?;
+    Ok(dir.join("run-template"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bundles_are_only_trusted_if_signed_by_a_trusted_key() {
+        let dir = std::env::temp_dir().join(format!("run-template-signing-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [("spin-template.toml".to_owned(), b"id = \"test\"".to_vec())].into_iter().collect();
+        let key = SigningKey::generate(&dir.join("signing-key"), false).unwrap();
+        assert!(SigningKey::generate(&dir.join("signing-key"), false).is_err());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let replaced = dir.join("replaced-key");
+            std::fs::write(&replaced, "").unwrap();
+            std::fs::set_permissions(&replaced, std::fs::Permissions::from_mode(0o644)).unwrap();
+            SigningKey::generate(&replaced, true).unwrap();
+            assert_eq!(0o600, std::fs::metadata(&replaced).unwrap().permissions().mode() & 0o777);
+        }
+
+        let keys_path = dir.join("trusted-keys.toml");
+        let policy = |allow_unsigned| TrustPolicy::new(Some(keys_path.clone()), allow_unsigned).unwrap();
+        let write = |name: &str, key: Option<&SigningKey>| {
+            Bundle::write(&files, &dir.join(name), key).unwrap();
+            Bundle::open(&dir.join(name)).unwrap()
+        };
+
+        let unsigned = write("unsigned.rtb", None);
+        let signed = write("signed.rtb", Some(&key));
+        assert!(policy(false).check(&unsigned).unwrap_err().to_string().contains("is not signed"));
+        assert!(policy(false).check(&signed).unwrap_err().to_string().contains("not in the trusted keys file"));
+        assert!(policy(true).check(&unsigned).unwrap().is_none());
+
+        TrustedKeys::add(&keys_path, "Test", &key.public_key()).unwrap();
+        assert_eq!("Test", policy(false).check(&signed).unwrap().unwrap().name);
+
+        let mut forged = write("forged.rtb", Some(&key));
+        forged.index.push(b'\n');
+        assert!(policy(true).check(&forged).unwrap_err().to_string().contains("invalid signature"));
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+}
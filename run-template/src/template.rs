@@ -0,0 +1,376 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+
+use crate::bindings::RunTemplate;
+use crate::bundle::Bundle;
+use crate::component_cache::ComponentCache;
+use crate::custom_filter::CustomFilterParser;
+use crate::custom_tag::CustomTagParser;
+use crate::filter_alias::FilterAliasParser;
+use crate::host::Host;
+use crate::limits::{self, Limits, LimitsConfig};
+use crate::manifest::Manifest;
+use crate::paths::resolve_template_path;
+use crate::signing::{TrustPolicy, TrustedKey};
+use crate::template_files::TemplateFiles;
+use crate::world::World;
+
+pub const MANIFEST_FILE: &str = "spin-template.toml";
+/// The directory of files that the template component copies and renders.
+pub const CONTENT_DIR: &str = "content";
+
+pub type TemplateStore = Arc<Mutex<wasmtime::Store<Host>>>;
+
+/// A template whose manifest has been validated and whose components have
+/// been loaded and checked against their worlds, ready to run or describe.
+pub struct Template {
+    pub files: TemplateFiles,
+    /// Who signed the bundle the template was loaded from, if it was signed by a trusted key.
+    pub signer: Option<TrustedKey>,
+    /// The manifest file, relative to `files`. This is `MANIFEST_FILE` unless the
+    /// template was loaded from a manifest file with another name.
+    pub manifest_path: PathBuf,
+    pub manifest: Manifest,
+    pub limits: Limits,
+    pub store: TemplateStore,
+    pub bindings: RunTemplate,
+    /// Filters from filter components and from the template component.
+    pub filters: Vec<CustomFilterParser>,
+    pub aliases: Vec<FilterAliasParser>,
+    pub tags: Vec<CustomTagParser>,
+}
+
+impl Template {
+    /// Loads the template from `path`, which is its manifest, the directory
+    /// containing it, or a bundle. Bundles are checked against `trust` first.
+    pub fn load(path: &Path, accept_defaults: bool, limits: &LimitsConfig, trust: &TrustPolicy, components: &ComponentCache) -> anyhow::Result<Self> {
+        let mut signer = None;
+        let (files, manifest_path) = if path.is_dir() {
+            (TemplateFiles::Dir(path.to_owned()), PathBuf::from(MANIFEST_FILE))
+        } else if Bundle::is_bundle(path) {
+            let bundle = Bundle::open(path)?;
+            signer = trust.check(&bundle)?;
+            (TemplateFiles::Bundle(Arc::new(bundle)), PathBuf::from(MANIFEST_FILE))
+        } else {
+            let dir = path.parent()
+                .with_context(|| format!("{} is not a template manifest file", path.display()))?;
+            let file_name = path.file_name()
+                .with_context(|| format!("{} is not a template manifest file", path.display()))?;
+            (TemplateFiles::Dir(dir.to_owned()), PathBuf::from(file_name))
+        };
+
+        let manifest = Manifest::load(&files, &manifest_path)?;
+
+        let mut filters = load_custom_filters(&files, &manifest, components)?;
+
+        let limits = Limits::TEMPLATE.overridden(&[&manifest.limits, limits])?;
+        let component_path = PathBuf::from(&manifest.template);
+        let host = Host::new(files.clone(), accept_defaults, limits);
+        let (store, bindings, _) = instantiate(&files, &component_path, host, components)?;
+
+        // Filters the template component exports run in an instance of their own, so
+        // they can be used while the template's instance is running
+        let filter_host = Host::new(files.clone(), accept_defaults, limits);
+        let (filter_store, _, filter_instance) = instantiate(&files, &component_path, filter_host, components)?;
+        filters.extend(CustomFilterParser::load_from_template(&files, &component_path, filter_store, filter_instance)?);
+        check_filter_names(&files, &filters)?;
+
+        let alias_base = crate::filters::builtin_filters().into_iter()
+            .chain(filters.iter().cloned().map(Into::into))
+            .collect::<Vec<_>>();
+        let aliases = crate::filter_alias::compile(&manifest.filter_alias, &alias_base)?;
+
+        let tags = load_custom_tags(&files, &manifest, components)?;
+
+        Ok(Self { files, signer, manifest_path, manifest, limits, store, bindings, filters, aliases, tags })
+    }
+
+    /// Makes the Liquid parser for the template's content files, with the built-in
+    /// and template filters, the template's tags and its partials.
+    pub fn parser(&self) -> anyhow::Result<liquid::Parser> {
+        let builder = crate::filters::builtin_filters().into_iter()
+            .chain(self.filters.iter().cloned().map(Into::into))
+            .chain(self.aliases.iter().cloned().map(Into::into))
+            .fold(liquid::ParserBuilder::with_stdlib(), |builder, filter| builder.filter(filter));
+        let parser = self.tags.iter().cloned()
+            .fold(builder, |builder, tag| tag.register(builder))
+            .partials(liquid::partials::LazyCompiler::new(self.partials()))
+            .build()?;
+        Ok(parser)
+    }
+
+    /// The partials that content files can include, from the template's `partials` directory.
+    pub fn partials(&self) -> crate::partials::PartialsDir {
+        crate::partials::PartialsDir::new(self.files.clone(), "partials")
+    }
+}
+
+fn instantiate(files: &TemplateFiles, file: &Path, host: Host, components: &ComponentCache) -> anyhow::Result<(TemplateStore, RunTemplate, wasmtime::component::Instance)> {
+    let engine = components.engine();
+
+    let component = World::TEMPLATE.load(files, file, components)?;
+
+    let mut linker = wasmtime::component::Linker::new(engine);
+    RunTemplate::add_to_linker(&mut linker, |state: &mut Host| state)?;
+    crate::custom_filter::fermyon::spin_template_filter::variables::add_to_linker(&mut linker, |state: &mut Host| state)?;
+
+    let mut store = wasmtime::Store::new(engine, host);
+    store.limiter(|host| &mut host.limits);
+    let limits = store.data().limits;
+    limits.refuel(&mut store)?;
+    // Instantiating can hit the limits too, e.g. if the initial memory is too big
+    let (bindings, instance) = RunTemplate::instantiate(&mut store, &component, &linker)
+        .map_err(|e| match limits::LimitExceeded::of(&e) {
+            Some(_) => limits::template_error(e, &limits),
+            None => e.context(World::TEMPLATE.instantiate_context(files, file)),
+        })?;
+
+    Ok((Arc::new(Mutex::new(store)), bindings, instance))
+}
+
+fn load_custom_filters(files: &TemplateFiles, manifest: &Manifest, components: &ComponentCache) -> anyhow::Result<Vec<CustomFilterParser>> {
+    let filters_dir = Path::new("filters");
+
+    let mut names = manifest.filter.keys().collect::<Vec<_>>();
+    names.sort();
+    let mut filters = names.into_iter()
+        .map(|name| {
+            let path = resolve_template_path(filters_dir, &manifest.filter[name]).with_context(|| format!("Filter '{name}' is not in the template"))?;
+            CustomFilterParser::load(name, files, &path, components)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    for pack in &manifest.filter_pack {
+        let prefix = pack.prefix.as_deref().unwrap_or_default();
+        let path = resolve_template_path(filters_dir, &pack.path).context("A filter pack is not in the template")?;
+        filters.extend(CustomFilterParser::load_pack(prefix, files, &path, components)?);
+    }
+
+    check_filter_names(files, &filters)?;
+    Ok(filters)
+}
+
+// Liquid lets a later filter silently replace an earlier one of the same name,
+// so a template's filters must not share names with each other or with the
+// filters that every template has
+fn check_filter_names(files: &TemplateFiles, filters: &[CustomFilterParser]) -> anyhow::Result<()> {
+    use liquid_core::ParseFilter;
+
+    let names = |filters: Vec<Box<dyn ParseFilter>>| filters.iter().map(|f| f.reflection().name().to_owned()).collect::<Vec<_>>();
+    let standard = names(crate::filters::standard_filters());
+    let builtin = names(crate::filters::builtin_filters());
+
+    let mut seen = std::collections::HashMap::new();
+    for filter in filters {
+        let name = filter.reflection().name();
+        let source = files.display(filter.source());
+        if standard.iter().any(|n| n == name) {
+            anyhow::bail!("Filter '{name}' from {source} has the same name as a Liquid filter");
+        }
+        if builtin.iter().any(|n| n == name) {
+            anyhow::bail!("Filter '{name}' from {source} has the same name as a built-in filter");
+        }
+        if let Some(other) = seen.insert(name, source.clone()) {
+            anyhow::bail!("Filter '{name}' is provided by both {other} and {source}. Give one of them a different name, or its `[[filter_pack]]` a `prefix`.");
+        }
+    }
+    Ok(())
+}
+
+fn load_custom_tags(files: &TemplateFiles, manifest: &Manifest, components: &ComponentCache) -> anyhow::Result<Vec<CustomTagParser>> {
+    let tags_dir = Path::new("tags");
+
+    let mut tags = vec![];
+    for pack in &manifest.tag_pack {
+        let prefix = pack.prefix.as_deref().unwrap_or_default();
+        let path = resolve_template_path(tags_dir, &pack.path).context("A tag pack is not in the template")?;
+        tags.extend(CustomTagParser::load_pack(prefix, files, &path, components)?);
+    }
+
+    // Liquid lets a later tag silently replace an earlier one of the same name
+    let mut seen = std::collections::HashMap::new();
+    for tag in &tags {
+        let name = tag.name();
+        let source = files.display(tag.source());
+        if crate::custom_tag::STANDARD_TAGS.contains(&name) {
+            anyhow::bail!("Tag '{name}' from {source} has the same name as a Liquid tag");
+        }
+        if let Some(other) = seen.insert(name, source.clone()) {
+            anyhow::bail!("Tag '{name}' is provided by both {other} and {source}. Give one of their `[[tag_pack]]`s a `prefix`.");
+        }
+    }
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::bindings::exports::fermyon::spin_template::template::Action;
+    use crate::host::{ExecutionContext, RenderMode};
+
+    fn load_sample() -> Template {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../sample-template/template");
+        let trust = TrustPolicy::new(Some(std::env::temp_dir().join("run-template-no-trusted-keys.toml")), false).unwrap();
+        Template::load(&path, true, &Default::default(), &trust, &ComponentCache::with_cache_dir(None).unwrap()).unwrap()
+    }
+
+    fn context(template: &Template, fruit: &str) -> ExecutionContext {
+        let variables = HashMap::from([("fruit".to_owned(), fruit.to_owned())]);
+        ExecutionContext::new(variables, template.parser().unwrap(), RenderMode::Strict)
+    }
+
+    // A tag component that provides a tag called `raw`, which Liquid already has
+    const RAW_TAG: &str = r#"
+        (component
+          (core module $m
+            (memory (export "memory") 1)
+            (func (export "realloc") (param i32 i32 i32 i32) (result i32) unreachable)
+            ;; One description, of a tag called `raw`
+            (data (i32.const 0) "\08\00\00\00\01\00\00\00")
+            (data (i32.const 8) "\20\00\00\00\03\00\00\00\20\00\00\00\00\00\00\00\00")
+            (data (i32.const 32) "raw")
+            (func (export "describe") (result i32) (i32.const 0))
+            (func (export "render") (param i32 i32 i32 i32 i32 i32 i32) (result i32) unreachable)
+          )
+          (core instance $i (instantiate $m))
+          (type $description (record (field "name" string) (field "description" string) (field "block" bool)))
+          (func $describe (result (list $description)) (canon lift (core func $i "describe") (memory $i "memory")))
+          (func $render (param "name" string) (param "args" (list string)) (param "body" (option string)) (result (result string (error string)))
+            (canon lift (core func $i "render") (memory $i "memory") (realloc (func $i "realloc"))))
+          (component $tags
+            (type $record (record (field "name" string) (field "description" string) (field "block" bool)))
+            (import "description" (type $imported (eq $record)))
+            (import "describe" (func $describe (result (list $imported))))
+            (import "render" (func $render (param "name" string) (param "args" (list string)) (param "body" (option string)) (result (result string (error string)))))
+            (export $description "tag-description" (type $imported))
+            (export "describe" (func $describe) (func (result (list $description))))
+            (export "render" (func $render))
+          )
+          (instance $instance (instantiate $tags (with "description" (type $description)) (with "describe" (func $describe)) (with "render" (func $render))))
+          (export "fermyon:spin-template-filter/tags@0.0.2" (instance $instance))
+        )
+    "#;
+
+    // A template directory with the sample's filter and tag components, the `raw` tag
+    // component, and a manifest that ends with `registrations`
+    fn write_template(test: &str, registrations: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("run-template-{test}-test-{}", std::process::id()));
+        let sample_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../sample-template/template");
+        for (subdir, file) in [("filters", "sample_filter.wasm"), ("tags", "sample_tag.wasm")] {
+            std::fs::create_dir_all(dir.join(subdir)).unwrap();
+            std::fs::copy(sample_dir.join(subdir).join(file), dir.join(subdir).join(file)).unwrap();
+        }
+        std::fs::write(dir.join("tags").join("raw.wat"), RAW_TAG).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), format!(
+            "id = \"test\"\nname = \"Test\"\ndescription = \"Test\"\nwit_version = \"0.0.2\"\ntemplate = \"template.wasm\"\n{registrations}"
+        )).unwrap();
+        dir
+    }
+
+    fn load_filters(dir: &Path) -> anyhow::Result<Vec<CustomFilterParser>> {
+        let files = TemplateFiles::Dir(dir.to_owned());
+        let manifest = Manifest::load(&files, Path::new(MANIFEST_FILE))?;
+        load_custom_filters(&files, &manifest, &ComponentCache::with_cache_dir(None)?)
+    }
+
+    fn load_tags(dir: &Path) -> anyhow::Result<Vec<CustomTagParser>> {
+        let files = TemplateFiles::Dir(dir.to_owned());
+        let manifest = Manifest::load(&files, Path::new(MANIFEST_FILE))?;
+        load_custom_tags(&files, &manifest, &ComponentCache::with_cache_dir(None)?)
+    }
+
+    #[test]
+    fn filters_must_be_in_the_template() {
+        let dir = write_template("filter-path", "[filter]\nx = \"../../elsewhere.wasm\"\n");
+
+        let trust = TrustPolicy::new(Some(std::env::temp_dir().join("run-template-no-trusted-keys.toml")), false).unwrap();
+        let err = Template::load(&dir, true, &Default::default(), &trust, &ComponentCache::with_cache_dir(None).unwrap()).err().unwrap();
+        assert!(format!("{err:#}").contains("Filter 'x' is not in the template"), "{err:#}");
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filter_packs_can_be_prefixed() {
+        use liquid_core::ParseFilter;
+
+        let dir = write_template("filter-prefix", "[[filter_pack]]\npath = \"sample_filter.wasm\"\nprefix = \"cutlery_\"\n");
+        let filters = load_filters(&dir).unwrap();
+        let names = filters.iter().map(|f| (f.reflection().name(), f.described_name())).collect::<Vec<_>>();
+        assert_eq!(vec![("cutlery_spork", "spork"), ("cutlery_tines", "tines"), ("cutlery_signed", "signed")], names);
+
+        let parser = filters.into_iter()
+            .fold(liquid::ParserBuilder::with_stdlib(), |builder, filter| builder.filter(filter))
+            .build()
+            .unwrap();
+        let rendered = parser.parse("{{ 'pet-store' | cutlery_tines }}").unwrap().render(&liquid::object!({})).unwrap();
+        assert_eq!("2", rendered);
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filter_packs_must_not_clash() {
+        let dir = write_template("filter-clash", "[[filter_pack]]\npath = \"sample_filter.wasm\"\n[[filter_pack]]\npath = \"sample_filter.wasm\"\n");
+        let err = load_filters(&dir).err().unwrap();
+        assert!(err.to_string().starts_with("Filter 'spork' is provided by both"), "{err}");
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filter_entries_must_provide_one_filter() {
+        let dir = write_template("filter-single", "[filter]\ncutlery = \"sample_filter.wasm\"\n");
+        let err = load_filters(&dir).err().unwrap();
+        assert!(err.to_string().ends_with("which provides 3 filters: register it as a filter pack instead"), "{err}");
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tags_must_not_clash() {
+        let dir = write_template("tag-clash", "[[tag_pack]]\npath = \"sample_tag.wasm\"\n[[tag_pack]]\npath = \"sample_tag.wasm\"\n");
+        let err = load_tags(&dir).err().unwrap();
+        assert!(err.to_string().starts_with("Tag 'license' is provided by both"), "{err}");
+        _ = std::fs::remove_dir_all(&dir);
+
+        let dir = write_template("tag-prefix", "[[tag_pack]]\npath = \"sample_tag.wasm\"\n[[tag_pack]]\npath = \"sample_tag.wasm\"\nprefix = \"x_\"\n");
+        let names = load_tags(&dir).unwrap().iter().map(|t| t.name().to_owned()).collect::<Vec<_>>();
+        assert_eq!(vec!["license", "region", "x_license", "x_region"], names);
+        _ = std::fs::remove_dir_all(&dir);
+
+        let dir = write_template("tag-standard", "[[tag_pack]]\npath = \"raw.wat\"\n");
+        let err = load_tags(&dir).err().unwrap();
+        assert!(err.to_string().starts_with("Tag 'raw' from"), "{err}");
+        assert!(err.to_string().ends_with("has the same name as a Liquid tag"), "{err}");
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn template_filters_work_in_copies() {
+        let template = load_sample();
+        let context = context(&template, "Banana");
+        let app_dir = std::env::temp_dir().join(format!("run-template-template-filter-test-{}", std::process::id()));
+        let destination = crate::actions::Destination { app_dir: app_dir.clone(), ..crate::actions::Destination::new_app("") };
+        let executor = crate::actions::apply(&template.store, template.bindings, &context, 0, template.files.clone(), destination);
+
+        executor.execute(&Action::CopyFileSubstituted("fruit.txt".to_owned())).unwrap();
+        assert_eq!("Hello, Banana 🍌", std::fs::read_to_string(app_dir.join("fruit.txt")).unwrap().trim_end());
+
+        _ = std::fs::remove_dir_all(&app_dir);
+    }
+
+    #[test]
+    fn template_filters_work_while_the_template_is_running() {
+        let template = load_sample();
+        let context = context(&template, "Cherry");
+
+        // As it is during `evaluate-template` or an edit
+        let _running = template.store.lock().unwrap();
+        assert_eq!("🍎 🍒", context.evaluate_template("{{ 'apple' | fruit_emoji }} {{ nil | fruit_emoji }}").unwrap());
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
+
+use crate::bundle::Bundle;
+use crate::manifest::Manifest;
+use crate::paths::resolve_template_path;
+use crate::template::MANIFEST_FILE;
+
+/// Where a template's files come from: a directory on disk, or a bundle that
+/// has been read into memory. Paths are relative to the template's root, which
+/// is the directory containing the manifest.
+#[derive(Clone, Debug)]
+pub enum TemplateFiles {
+    Dir(PathBuf),
+    Bundle(Arc<Bundle>),
+}
+
+impl TemplateFiles {
+    pub fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Self::Dir(root) => Ok(std::fs::read(root.join(path))?),
+            Self::Bundle(bundle) => bundle_key(path)
+                .and_then(|key| bundle.files.get(&key))
+                .cloned()
+                .with_context(|| format!("The bundle doesn't contain '{}'", path.display())),
+        }
+    }
+
+    pub fn read_to_string(&self, path: &Path) -> anyhow::Result<String> {
+        let bytes = self.read(path)?;
+        String::from_utf8(bytes).with_context(|| format!("'{}' is not UTF-8 text", path.display()))
+    }
+
+    pub fn is_file(&self, path: &Path) -> bool {
+        match self {
+            Self::Dir(root) => root.join(path).is_file(),
+            Self::Bundle(bundle) => bundle_key(path).is_some_and(|key| bundle.files.contains_key(&key)),
+        }
+    }
+
+    pub fn is_dir(&self, path: &Path) -> bool {
+        match self {
+            Self::Dir(root) => root.join(path).is_dir(),
+            Self::Bundle(_) => !self.files_in(path).is_empty(),
+        }
+    }
+
+    /// The files under `dir` (recursively), relative to `dir`, sorted. There are
+    /// none if `dir` doesn't exist. Git metadata is skipped.
+    pub fn files_in(&self, dir: &Path) -> Vec<PathBuf> {
+        match self {
+            Self::Dir(root) => {
+                let dir = root.join(dir);
+                let not_git = |entry: &walkdir::DirEntry| entry.file_name() != ".git";
+                walkdir::WalkDir::new(&dir)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_entry(not_git)
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                    .filter_map(|e| e.path().strip_prefix(&dir).ok().map(|p| p.to_owned()))
+                    .collect()
+            }
+            Self::Bundle(bundle) => {
+                let Some(prefix) = bundle_key(dir) else {
+                    return vec![];
+                };
+                let prefix = if prefix.is_empty() { prefix } else { format!("{prefix}/") };
+                bundle.files.keys()
+                    .filter_map(|key| key.strip_prefix(&prefix))
+                    .map(PathBuf::from)
+                    .collect()
+            }
+        }
+    }
+
+    /// `path` as the user would recognise it, for messages.
+    pub fn display(&self, path: &Path) -> String {
+        match self {
+            Self::Dir(root) => root.join(path).display().to_string(),
+            Self::Bundle(bundle) => format!("{}:{}", bundle.path.display(), path.display()),
+        }
+    }
+
+    /// All the template's files, keyed by their paths with `/` separators. `manifest`
+    /// was loaded from `manifest_path`, which becomes `MANIFEST_FILE` whatever it was
+    /// called. If the manifest refers to the template component from outside the template
+    /// (e.g. to pick it up from the build output), the component is included next to
+    /// the manifest, and the manifest is rewritten to refer to it there.
+    pub fn self_contained(&self, manifest_path: &Path, manifest: &Manifest) -> anyhow::Result<BTreeMap<String, Vec<u8>>> {
+        let mut files = BTreeMap::new();
+        for path in self.files_in(Path::new("")) {
+            let contents = self.read(&path)
+                .with_context(|| format!("Failed to read {}", self.display(&path)))?;
+            let key = bundle_key(&path).with_context(|| format!("Can't store {}", self.display(&path)))?;
+            files.insert(key, contents);
+        }
+
+        let manifest_key = bundle_key(manifest_path)
+            .with_context(|| format!("Can't store {}", self.display(manifest_path)))?;
+        let mut manifest_text = files.remove(&manifest_key)
+            .with_context(|| format!("{} is not in the template", self.display(manifest_path)))?;
+
+        if resolve_template_path("", &manifest.template).is_err() {
+            let component = Path::new(&manifest.template);
+            let file_name = component.file_name()
+                .with_context(|| format!("Template component '{}' is not a file", manifest.template))?
+                .to_string_lossy()
+                .into_owned();
+            let wasm = self.read(component)
+                .with_context(|| format!("Failed to read template component {}", self.display(component)))?;
+            files.insert(file_name.clone(), wasm);
+
+            // Edit rather than reserialise the manifest so that the author's comments and layout survive
+            let mut doc = String::from_utf8(manifest_text)?.parse::<toml_edit::DocumentMut>()?;
+            doc["template"] = toml_edit::value(file_name);
+            manifest_text = doc.to_string().into_bytes();
+        }
+        files.insert(MANIFEST_FILE.to_owned(), manifest_text);
+
+        Ok(files)
+    }
+}
+
+// Bundles key files by their relative paths, separated by `/` whatever the platform.
+// Paths that climb out of the template can't be in a bundle.
+fn bundle_key(path: &Path) -> Option<String> {
+    let mut parts = vec![];
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?),
+            Component::CurDir => (),
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(parts.join("/"))
+}
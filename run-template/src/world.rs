@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use anyhow::Context;
+use wasmtime::component::Component;
+
+use crate::component_cache::ComponentCache;
+use crate::template_files::TemplateFiles;
+
+/// The version of the WIT packages that the host implements, which is the
+/// version in all the interface and world names below.
+pub const WIT_VERSION: &str = "0.0.2";
+
+pub const TEMPLATE_INTERFACE: &str = "fermyon:spin-template/template@0.0.2";
+pub const FILTER_INTERFACE: &str = "fermyon:spin-template-filter/types@0.0.2";
+pub const TAG_INTERFACE: &str = "fermyon:spin-template-filter/tags@0.0.2";
+
+const TEMPLATE_TYPES: &str = "fermyon:spin-template/types@0.0.2";
+const UI: &str = "fermyon:spin-template/ui@0.0.2";
+const FILTER_VARIABLES: &str = "fermyon:spin-template-filter/variables@0.0.2";
+
+/// A WIT world that the host runs components of: what they may import, and
+/// what they must export. Components are checked against their world when they
+/// are loaded, so that a component built against the wrong world or version of
+/// the WIT gets an error saying what's wrong rather than a linker error.
+pub struct World {
+    pub name: &'static str,
+    imports: &'static [&'static str],
+    exports: &'static [&'static str],
+    optional_exports: &'static [&'static str],
+}
+
+impl World {
+    /// Template components. These may also target `run-template-with-filters`,
+    /// which adds the filter interface as an export and filter variables as an import.
+    pub const TEMPLATE: Self = Self {
+        name: "fermyon:spin-template/run-template@0.0.2",
+        imports: &[TEMPLATE_TYPES, UI, FILTER_VARIABLES],
+        exports: &[TEMPLATE_INTERFACE],
+        optional_exports: &[FILTER_INTERFACE],
+    };
+
+    pub const FILTER: Self = Self {
+        name: "fermyon:spin-template-filter/custom-filter@0.0.2",
+        imports: &[FILTER_VARIABLES],
+        exports: &[FILTER_INTERFACE],
+        optional_exports: &[],
+    };
+
+    pub const TAG: Self = Self {
+        name: "fermyon:spin-template-filter/custom-tag@0.0.2",
+        imports: &[FILTER_VARIABLES],
+        exports: &[TAG_INTERFACE],
+        optional_exports: &[],
+    };
+
+    /// Loads the component at `wasm_path` and checks that it targets this world.
+    pub fn load(&self, files: &TemplateFiles, wasm_path: &Path, components: &ComponentCache) -> anyhow::Result<Component> {
+        let component = components.load(files, wasm_path)?;
+        self.check(&component, components.engine())
+            .with_context(|| format!("{} is not a component for the {} world", files.display(wasm_path), self.name))?;
+        Ok(component)
+    }
+
+    /// The context for errors instantiating a component that passed `check`, which
+    /// are usually because an interface has the right name but the wrong contents.
+    pub fn instantiate_context(&self, files: &TemplateFiles, wasm_path: &Path) -> String {
+        format!(
+            "Failed to instantiate {} as a component for the {} world. It may have been built against a different version of the WIT files.",
+            files.display(wasm_path),
+            self.name
+        )
+    }
+
+    fn check(&self, component: &Component, engine: &wasmtime::Engine) -> anyhow::Result<()> {
+        let ty = component.component_type();
+        let imports = ty.imports(engine).map(|(name, _)| name).collect::<Vec<_>>();
+        let exports = ty.exports(engine).map(|(name, _)| name).collect::<Vec<_>>();
+
+        let mut problems = vec![];
+        for import in imports {
+            if !self.imports.contains(&import) {
+                problems.push(match same_interface(import, self.imports) {
+                    Some(provided) => format!("it imports {import}, but the host provides {provided}"),
+                    None => format!("it imports {import}, which the host doesn't provide"),
+                });
+            }
+        }
+        for export in self.exports {
+            if !exports.contains(export) {
+                problems.push(match same_interface(export, &exports) {
+                    Some(actual) => format!("it exports {actual}, but the host expects {export}"),
+                    None => format!("it doesn't export {export}"),
+                });
+            }
+        }
+        for export in self.optional_exports {
+            if !exports.contains(export) {
+                if let Some(actual) = same_interface(export, &exports) {
+                    problems.push(format!("it exports {actual}, but the host expects {export}"));
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            anyhow::bail!("{}", problems.join("; "));
+        }
+        Ok(())
+    }
+}
+
+// Finds another version of the interface `name` in `names`, e.g. `a:b/c@0.0.3` for `a:b/c@0.0.2`
+fn same_interface<'a>(name: &str, names: &[&'a str]) -> Option<&'a str> {
+    let unversioned = |n: &str| n.split('@').next().unwrap_or_default().to_owned();
+    names.iter().find(|n| unversioned(n) == unversioned(name)).copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(world: &World, wat: &str) -> anyhow::Result<()> {
+        let engine = wasmtime::Engine::new(wasmtime::Config::new().wasm_component_model(true))?;
+        world.check(&Component::new(&engine, wat)?, &engine)
+    }
+
+    #[test]
+    fn mismatched_interfaces_are_named() {
+        let err = check(&World::FILTER, r#"(component
+            (import "fermyon:spin-template-filter/variables@0.0.3" (instance))
+            (import "wasi:cli/stdout@0.2.0" (instance))
+        )"#).expect_err("component should not match the world");
+        assert_eq!(
+            "it imports fermyon:spin-template-filter/variables@0.0.3, but the host provides fermyon:spin-template-filter/variables@0.0.2; \
+                it imports wasi:cli/stdout@0.2.0, which the host doesn't provide; \
+                it doesn't export fermyon:spin-template-filter/types@0.0.2",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn components_need_not_use_every_import() {
+        let component = r#"(component
+            (instance $types)
+            (export "fermyon:spin-template-filter/types@0.0.2" (instance $types))
+        )"#;
+        check(&World::FILTER, component).unwrap();
+        assert!(check(&World::TAG, component).is_err());
+    }
+}
@@ -1,6 +1,101 @@
 // Generated by `wit-bindgen` 0.25.0. DO NOT EDIT!
 // Options used:
 #[allow(dead_code)]
+pub mod fermyon {
+    #[allow(dead_code)]
+    pub mod spin_template_filter {
+        #[allow(dead_code, clippy::all)]
+        pub mod variables {
+            #[used]
+            #[doc(hidden)]
+            #[cfg(target_arch = "wasm32")]
+            static __FORCE_SECTION_REF: fn() =
+                super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn get(name: &str) -> Option<_rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "fermyon:spin-template-filter/variables@0.0.2")]
+                    extern "C" {
+                        #[link_name = "get"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l3 = *ptr1.add(4).cast::<*mut u8>();
+                                let l4 = *ptr1.add(8).cast::<usize>();
+                                let len5 = l4;
+                                let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+
+                                _rt::string_lift(bytes5)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn names() -> _rt::Vec<_rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "fermyon:spin-template-filter/variables@0.0.2")]
+                    extern "C" {
+                        #[link_name = "names"]
+                        fn wit_import(_: *mut u8);
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base6 = l1;
+                    let len6 = l2;
+                    let mut result6 = _rt::Vec::with_capacity(len6);
+                    for i in 0..len6 {
+                        let base = base6.add(i * 8);
+                        let e6 = {
+                            let l3 = *base.add(0).cast::<*mut u8>();
+                            let l4 = *base.add(4).cast::<usize>();
+                            let len5 = l4;
+                            let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+
+                            _rt::string_lift(bytes5)
+                        };
+                        result6.push(e6);
+                    }
+                    _rt::cabi_dealloc(base6, len6 * 8, 4);
+                    result6
+                }
+            }
+        }
+    }
+}
+#[allow(dead_code)]
 pub mod exports {
     #[allow(dead_code)]
     pub mod fermyon {
@@ -14,36 +109,740 @@ pub mod exports {
                 static __FORCE_SECTION_REF: fn() =
                     super::super::super::super::__link_custom_section_describing_imports;
                 use super::super::super::super::_rt;
+                /// Mirrors the Liquid data model. WIT doesn't allow recursive types, so a value is a
+                /// tree flattened into a list of nodes: the first node is the root, and arrays and
+                /// objects refer to their members by index. Members always come after the array or
+                /// object that contains them.
+                #[derive(Clone)]
+                pub enum ValueNode {
+                    Nil,
+                    Bool(bool),
+                    Int(i64),
+                    Float(f64),
+                    String(_rt::String),
+                    Array(_rt::Vec<u32>),
+                    Object(_rt::Vec<(_rt::String, u32)>),
+                }
+                impl ::core::fmt::Debug for ValueNode {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            ValueNode::Nil => f.debug_tuple("ValueNode::Nil").finish(),
+                            ValueNode::Bool(e) => {
+                                f.debug_tuple("ValueNode::Bool").field(e).finish()
+                            }
+                            ValueNode::Int(e) => f.debug_tuple("ValueNode::Int").field(e).finish(),
+                            ValueNode::Float(e) => {
+                                f.debug_tuple("ValueNode::Float").field(e).finish()
+                            }
+                            ValueNode::String(e) => {
+                                f.debug_tuple("ValueNode::String").field(e).finish()
+                            }
+                            ValueNode::Array(e) => {
+                                f.debug_tuple("ValueNode::Array").field(e).finish()
+                            }
+                            ValueNode::Object(e) => {
+                                f.debug_tuple("ValueNode::Object").field(e).finish()
+                            }
+                        }
+                    }
+                }
+                pub type Value = _rt::Vec<ValueNode>;
+                #[derive(Clone)]
+                pub struct Parameter {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    pub required: bool,
+                }
+                impl ::core::fmt::Debug for Parameter {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("Parameter")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("required", &self.required)
+                            .finish()
+                    }
+                }
+                /// Documents the filter and the arguments it accepts, e.g. `{{ name | truncate_id: 20, sep: "-" }}`
+                /// has one positional parameter and a keyword parameter called `sep`.  `name` identifies the
+                /// filter within the component, and is the name it is used under unless the template chooses
+                /// a name or prefix of its own in `spin-template.toml`.
+                #[derive(Clone)]
+                pub struct FilterDescription {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    pub positional: _rt::Vec<Parameter>,
+                    pub keyword: _rt::Vec<Parameter>,
+                }
+                impl ::core::fmt::Debug for FilterDescription {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("FilterDescription")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("positional", &self.positional)
+                            .field("keyword", &self.keyword)
+                            .finish()
+                    }
+                }
+                /// Optional positional arguments that were not supplied are omitted from the end of the
+                /// list; keyword arguments that were not supplied are omitted altogether.
+                #[derive(Clone)]
+                pub struct Arguments {
+                    pub positional: _rt::Vec<Value>,
+                    pub keyword: _rt::Vec<(_rt::String, Value)>,
+                }
+                impl ::core::fmt::Debug for Arguments {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("Arguments")
+                            .field("positional", &self.positional)
+                            .field("keyword", &self.keyword)
+                            .finish()
+                    }
+                }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_exec_cabi<T: Guest>(arg0: *mut u8, arg1: usize) -> *mut u8 {
+                pub unsafe fn _export_describe_cabi<T: Guest>() -> *mut u8 {
                     #[cfg(target_arch = "wasm32")]
                     _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let result1 = T::exec(_rt::string_lift(bytes0));
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    match result1 {
-                        Ok(e) => {
-                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                    let result0 = T::describe();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec13 = result0;
+                    let len13 = vec13.len();
+                    let layout13 =
+                        _rt::alloc::Layout::from_size_align_unchecked(vec13.len() * 32, 4);
+                    let result13 = if layout13.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout13);
+                        }
+                        ptr
+                    } else {
+                        {
+                            ::core::ptr::null_mut()
+                        }
+                    };
+                    for (i, e) in vec13.into_iter().enumerate() {
+                        let base = result13.add(i * 32);
+                        {
+                            let FilterDescription {
+                                name: name2,
+                                description: description2,
+                                positional: positional2,
+                                keyword: keyword2,
+                            } = e;
+                            let vec3 = (name2.into_bytes()).into_boxed_slice();
                             let ptr3 = vec3.as_ptr().cast::<u8>();
                             let len3 = vec3.len();
                             ::core::mem::forget(vec3);
-                            *ptr2.add(8).cast::<usize>() = len3;
-                            *ptr2.add(4).cast::<*mut u8>() = ptr3.cast_mut();
-                        }
-                        Err(e) => {
-                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec4 = (e.into_bytes()).into_boxed_slice();
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = (description2.into_bytes()).into_boxed_slice();
                             let ptr4 = vec4.as_ptr().cast::<u8>();
                             let len4 = vec4.len();
                             ::core::mem::forget(vec4);
-                            *ptr2.add(8).cast::<usize>() = len4;
-                            *ptr2.add(4).cast::<*mut u8>() = ptr4.cast_mut();
+                            *base.add(12).cast::<usize>() = len4;
+                            *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+                            let vec8 = positional2;
+                            let len8 = vec8.len();
+                            let layout8 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec8.len() * 20, 4);
+                            let result8 = if layout8.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout8);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec8.into_iter().enumerate() {
+                                let base = result8.add(i * 20);
+                                {
+                                    let Parameter {
+                                        name: name5,
+                                        description: description5,
+                                        required: required5,
+                                    } = e;
+                                    let vec6 = (name5.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *base.add(4).cast::<usize>() = len6;
+                                    *base.add(0).cast::<*mut u8>() = ptr6.cast_mut();
+                                    let vec7 = (description5.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *base.add(12).cast::<usize>() = len7;
+                                    *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                    *base.add(16).cast::<u8>() = (match required5 {
+                                        true => 1,
+                                        false => 0,
+                                    })
+                                        as u8;
+                                }
+                            }
+                            *base.add(20).cast::<usize>() = len8;
+                            *base.add(16).cast::<*mut u8>() = result8;
+                            let vec12 = keyword2;
+                            let len12 = vec12.len();
+                            let layout12 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 20, 4);
+                            let result12 = if layout12.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout12);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec12.into_iter().enumerate() {
+                                let base = result12.add(i * 20);
+                                {
+                                    let Parameter {
+                                        name: name9,
+                                        description: description9,
+                                        required: required9,
+                                    } = e;
+                                    let vec10 = (name9.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *base.add(4).cast::<usize>() = len10;
+                                    *base.add(0).cast::<*mut u8>() = ptr10.cast_mut();
+                                    let vec11 = (description9.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *base.add(12).cast::<usize>() = len11;
+                                    *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                    *base.add(16).cast::<u8>() = (match required9 {
+                                        true => 1,
+                                        false => 0,
+                                    })
+                                        as u8;
+                                }
+                            }
+                            *base.add(28).cast::<usize>() = len12;
+                            *base.add(24).cast::<*mut u8>() = result12;
+                        }
+                    }
+                    *ptr1.add(4).cast::<usize>() = len13;
+                    *ptr1.add(0).cast::<*mut u8>() = result13;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_describe<T: Guest>(arg0: *mut u8) {
+                    let l18 = *arg0.add(0).cast::<*mut u8>();
+                    let l19 = *arg0.add(4).cast::<usize>();
+                    let base20 = l18;
+                    let len20 = l19;
+                    for i in 0..len20 {
+                        let base = base20.add(i * 32);
+                        {
+                            let l0 = *base.add(0).cast::<*mut u8>();
+                            let l1 = *base.add(4).cast::<usize>();
+                            _rt::cabi_dealloc(l0, l1, 1);
+                            let l2 = *base.add(8).cast::<*mut u8>();
+                            let l3 = *base.add(12).cast::<usize>();
+                            _rt::cabi_dealloc(l2, l3, 1);
+                            let l8 = *base.add(16).cast::<*mut u8>();
+                            let l9 = *base.add(20).cast::<usize>();
+                            let base10 = l8;
+                            let len10 = l9;
+                            for i in 0..len10 {
+                                let base = base10.add(i * 20);
+                                {
+                                    let l4 = *base.add(0).cast::<*mut u8>();
+                                    let l5 = *base.add(4).cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                    let l6 = *base.add(8).cast::<*mut u8>();
+                                    let l7 = *base.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(base10, len10 * 20, 4);
+                            let l15 = *base.add(24).cast::<*mut u8>();
+                            let l16 = *base.add(28).cast::<usize>();
+                            let base17 = l15;
+                            let len17 = l16;
+                            for i in 0..len17 {
+                                let base = base17.add(i * 20);
+                                {
+                                    let l11 = *base.add(0).cast::<*mut u8>();
+                                    let l12 = *base.add(4).cast::<usize>();
+                                    _rt::cabi_dealloc(l11, l12, 1);
+                                    let l13 = *base.add(8).cast::<*mut u8>();
+                                    let l14 = *base.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l13, l14, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(base17, len17 * 20, 4);
+                        }
+                    }
+                    _rt::cabi_dealloc(base20, len20 * 32, 4);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_exec_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                    arg4: *mut u8,
+                    arg5: usize,
+                    arg6: *mut u8,
+                    arg7: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")]
+                    _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base19 = arg2;
+                    let len19 = arg3;
+                    let mut result19 = _rt::Vec::with_capacity(len19);
+                    for i in 0..len19 {
+                        let base = base19.add(i * 16);
+                        let e19 = {
+                            let l1 = i32::from(*base.add(0).cast::<u8>());
+                            let v18 = match l1 {
+                                0 => ValueNode::Nil,
+                                1 => {
+                                    let e18 = {
+                                        let l2 = i32::from(*base.add(8).cast::<u8>());
+
+                                        _rt::bool_lift(l2 as u8)
+                                    };
+                                    ValueNode::Bool(e18)
+                                }
+                                2 => {
+                                    let e18 = {
+                                        let l3 = *base.add(8).cast::<i64>();
+
+                                        l3
+                                    };
+                                    ValueNode::Int(e18)
+                                }
+                                3 => {
+                                    let e18 = {
+                                        let l4 = *base.add(8).cast::<f64>();
+
+                                        l4
+                                    };
+                                    ValueNode::Float(e18)
+                                }
+                                4 => {
+                                    let e18 = {
+                                        let l5 = *base.add(8).cast::<*mut u8>();
+                                        let l6 = *base.add(12).cast::<usize>();
+                                        let len7 = l6;
+                                        let bytes7 =
+                                            _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                                        _rt::string_lift(bytes7)
+                                    };
+                                    ValueNode::String(e18)
+                                }
+                                5 => {
+                                    let e18 = {
+                                        let l8 = *base.add(8).cast::<*mut u8>();
+                                        let l9 = *base.add(12).cast::<usize>();
+                                        let len10 = l9;
+
+                                        _rt::Vec::from_raw_parts(l8.cast(), len10, len10)
+                                    };
+                                    ValueNode::Array(e18)
+                                }
+                                n => {
+                                    debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                    let e18 = {
+                                        let l11 = *base.add(8).cast::<*mut u8>();
+                                        let l12 = *base.add(12).cast::<usize>();
+                                        let base17 = l11;
+                                        let len17 = l12;
+                                        let mut result17 = _rt::Vec::with_capacity(len17);
+                                        for i in 0..len17 {
+                                            let base = base17.add(i * 12);
+                                            let e17 = {
+                                                let l13 = *base.add(0).cast::<*mut u8>();
+                                                let l14 = *base.add(4).cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                let l16 = *base.add(8).cast::<i32>();
+
+                                                (_rt::string_lift(bytes15), l16 as u32)
+                                            };
+                                            result17.push(e17);
+                                        }
+                                        _rt::cabi_dealloc(base17, len17 * 12, 4);
+
+                                        result17
+                                    };
+                                    ValueNode::Object(e18)
+                                }
+                            };
+
+                            v18
+                        };
+                        result19.push(e19);
+                    }
+                    _rt::cabi_dealloc(base19, len19 * 16, 8);
+                    let base41 = arg4;
+                    let len41 = arg5;
+                    let mut result41 = _rt::Vec::with_capacity(len41);
+                    for i in 0..len41 {
+                        let base = base41.add(i * 8);
+                        let e41 = {
+                            let l20 = *base.add(0).cast::<*mut u8>();
+                            let l21 = *base.add(4).cast::<usize>();
+                            let base40 = l20;
+                            let len40 = l21;
+                            let mut result40 = _rt::Vec::with_capacity(len40);
+                            for i in 0..len40 {
+                                let base = base40.add(i * 16);
+                                let e40 = {
+                                    let l22 = i32::from(*base.add(0).cast::<u8>());
+                                    let v39 = match l22 {
+                                        0 => ValueNode::Nil,
+                                        1 => {
+                                            let e39 = {
+                                                let l23 = i32::from(*base.add(8).cast::<u8>());
+
+                                                _rt::bool_lift(l23 as u8)
+                                            };
+                                            ValueNode::Bool(e39)
+                                        }
+                                        2 => {
+                                            let e39 = {
+                                                let l24 = *base.add(8).cast::<i64>();
+
+                                                l24
+                                            };
+                                            ValueNode::Int(e39)
+                                        }
+                                        3 => {
+                                            let e39 = {
+                                                let l25 = *base.add(8).cast::<f64>();
+
+                                                l25
+                                            };
+                                            ValueNode::Float(e39)
+                                        }
+                                        4 => {
+                                            let e39 = {
+                                                let l26 = *base.add(8).cast::<*mut u8>();
+                                                let l27 = *base.add(12).cast::<usize>();
+                                                let len28 = l27;
+                                                let bytes28 = _rt::Vec::from_raw_parts(
+                                                    l26.cast(),
+                                                    len28,
+                                                    len28,
+                                                );
+
+                                                _rt::string_lift(bytes28)
+                                            };
+                                            ValueNode::String(e39)
+                                        }
+                                        5 => {
+                                            let e39 = {
+                                                let l29 = *base.add(8).cast::<*mut u8>();
+                                                let l30 = *base.add(12).cast::<usize>();
+                                                let len31 = l30;
+
+                                                _rt::Vec::from_raw_parts(l29.cast(), len31, len31)
+                                            };
+                                            ValueNode::Array(e39)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                            let e39 = {
+                                                let l32 = *base.add(8).cast::<*mut u8>();
+                                                let l33 = *base.add(12).cast::<usize>();
+                                                let base38 = l32;
+                                                let len38 = l33;
+                                                let mut result38 = _rt::Vec::with_capacity(len38);
+                                                for i in 0..len38 {
+                                                    let base = base38.add(i * 12);
+                                                    let e38 = {
+                                                        let l34 = *base.add(0).cast::<*mut u8>();
+                                                        let l35 = *base.add(4).cast::<usize>();
+                                                        let len36 = l35;
+                                                        let bytes36 = _rt::Vec::from_raw_parts(
+                                                            l34.cast(),
+                                                            len36,
+                                                            len36,
+                                                        );
+                                                        let l37 = *base.add(8).cast::<i32>();
+
+                                                        (_rt::string_lift(bytes36), l37 as u32)
+                                                    };
+                                                    result38.push(e38);
+                                                }
+                                                _rt::cabi_dealloc(base38, len38 * 12, 4);
+
+                                                result38
+                                            };
+                                            ValueNode::Object(e39)
+                                        }
+                                    };
+
+                                    v39
+                                };
+                                result40.push(e40);
+                            }
+                            _rt::cabi_dealloc(base40, len40 * 16, 8);
+
+                            result40
+                        };
+                        result41.push(e41);
+                    }
+                    _rt::cabi_dealloc(base41, len41 * 8, 4);
+                    let base66 = arg6;
+                    let len66 = arg7;
+                    let mut result66 = _rt::Vec::with_capacity(len66);
+                    for i in 0..len66 {
+                        let base = base66.add(i * 16);
+                        let e66 = {
+                            let l42 = *base.add(0).cast::<*mut u8>();
+                            let l43 = *base.add(4).cast::<usize>();
+                            let len44 = l43;
+                            let bytes44 = _rt::Vec::from_raw_parts(l42.cast(), len44, len44);
+                            let l45 = *base.add(8).cast::<*mut u8>();
+                            let l46 = *base.add(12).cast::<usize>();
+                            let base65 = l45;
+                            let len65 = l46;
+                            let mut result65 = _rt::Vec::with_capacity(len65);
+                            for i in 0..len65 {
+                                let base = base65.add(i * 16);
+                                let e65 = {
+                                    let l47 = i32::from(*base.add(0).cast::<u8>());
+                                    let v64 = match l47 {
+                                        0 => ValueNode::Nil,
+                                        1 => {
+                                            let e64 = {
+                                                let l48 = i32::from(*base.add(8).cast::<u8>());
+
+                                                _rt::bool_lift(l48 as u8)
+                                            };
+                                            ValueNode::Bool(e64)
+                                        }
+                                        2 => {
+                                            let e64 = {
+                                                let l49 = *base.add(8).cast::<i64>();
+
+                                                l49
+                                            };
+                                            ValueNode::Int(e64)
+                                        }
+                                        3 => {
+                                            let e64 = {
+                                                let l50 = *base.add(8).cast::<f64>();
+
+                                                l50
+                                            };
+                                            ValueNode::Float(e64)
+                                        }
+                                        4 => {
+                                            let e64 = {
+                                                let l51 = *base.add(8).cast::<*mut u8>();
+                                                let l52 = *base.add(12).cast::<usize>();
+                                                let len53 = l52;
+                                                let bytes53 = _rt::Vec::from_raw_parts(
+                                                    l51.cast(),
+                                                    len53,
+                                                    len53,
+                                                );
+
+                                                _rt::string_lift(bytes53)
+                                            };
+                                            ValueNode::String(e64)
+                                        }
+                                        5 => {
+                                            let e64 = {
+                                                let l54 = *base.add(8).cast::<*mut u8>();
+                                                let l55 = *base.add(12).cast::<usize>();
+                                                let len56 = l55;
+
+                                                _rt::Vec::from_raw_parts(l54.cast(), len56, len56)
+                                            };
+                                            ValueNode::Array(e64)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                            let e64 = {
+                                                let l57 = *base.add(8).cast::<*mut u8>();
+                                                let l58 = *base.add(12).cast::<usize>();
+                                                let base63 = l57;
+                                                let len63 = l58;
+                                                let mut result63 = _rt::Vec::with_capacity(len63);
+                                                for i in 0..len63 {
+                                                    let base = base63.add(i * 12);
+                                                    let e63 = {
+                                                        let l59 = *base.add(0).cast::<*mut u8>();
+                                                        let l60 = *base.add(4).cast::<usize>();
+                                                        let len61 = l60;
+                                                        let bytes61 = _rt::Vec::from_raw_parts(
+                                                            l59.cast(),
+                                                            len61,
+                                                            len61,
+                                                        );
+                                                        let l62 = *base.add(8).cast::<i32>();
+
+                                                        (_rt::string_lift(bytes61), l62 as u32)
+                                                    };
+                                                    result63.push(e63);
+                                                }
+                                                _rt::cabi_dealloc(base63, len63 * 12, 4);
+
+                                                result63
+                                            };
+                                            ValueNode::Object(e64)
+                                        }
+                                    };
+
+                                    v64
+                                };
+                                result65.push(e65);
+                            }
+                            _rt::cabi_dealloc(base65, len65 * 16, 8);
+
+                            (_rt::string_lift(bytes44), result65)
+                        };
+                        result66.push(e66);
+                    }
+                    _rt::cabi_dealloc(base66, len66 * 16, 4);
+                    let result67 = T::exec(
+                        _rt::string_lift(bytes0),
+                        result19,
+                        Arguments {
+                            positional: result41,
+                            keyword: result66,
+                        },
+                    );
+                    let ptr68 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    match result67 {
+                        Ok(e) => {
+                            *ptr68.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec74 = e;
+                            let len74 = vec74.len();
+                            let layout74 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec74.len() * 16, 8);
+                            let result74 = if layout74.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout74).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout74);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec74.into_iter().enumerate() {
+                                let base = result74.add(i * 16);
+                                {
+                                    match e {
+                                        ValueNode::Nil => {
+                                            *base.add(0).cast::<u8>() = (0i32) as u8;
+                                        }
+                                        ValueNode::Bool(e) => {
+                                            *base.add(0).cast::<u8>() = (1i32) as u8;
+                                            *base.add(8).cast::<u8>() = (match e {
+                                                true => 1,
+                                                false => 0,
+                                            })
+                                                as u8;
+                                        }
+                                        ValueNode::Int(e) => {
+                                            *base.add(0).cast::<u8>() = (2i32) as u8;
+                                            *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                        }
+                                        ValueNode::Float(e) => {
+                                            *base.add(0).cast::<u8>() = (3i32) as u8;
+                                            *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                        }
+                                        ValueNode::String(e) => {
+                                            *base.add(0).cast::<u8>() = (4i32) as u8;
+                                            let vec69 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr69 = vec69.as_ptr().cast::<u8>();
+                                            let len69 = vec69.len();
+                                            ::core::mem::forget(vec69);
+                                            *base.add(12).cast::<usize>() = len69;
+                                            *base.add(8).cast::<*mut u8>() = ptr69.cast_mut();
+                                        }
+                                        ValueNode::Array(e) => {
+                                            *base.add(0).cast::<u8>() = (5i32) as u8;
+                                            let vec70 = (e).into_boxed_slice();
+                                            let ptr70 = vec70.as_ptr().cast::<u8>();
+                                            let len70 = vec70.len();
+                                            ::core::mem::forget(vec70);
+                                            *base.add(12).cast::<usize>() = len70;
+                                            *base.add(8).cast::<*mut u8>() = ptr70.cast_mut();
+                                        }
+                                        ValueNode::Object(e) => {
+                                            *base.add(0).cast::<u8>() = (6i32) as u8;
+                                            let vec73 = e;
+                                            let len73 = vec73.len();
+                                            let layout73 =
+                                                _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec73.len() * 12,
+                                                    4,
+                                                );
+                                            let result73 = if layout73.size() != 0 {
+                                                let ptr = _rt::alloc::alloc(layout73).cast::<u8>();
+                                                if ptr.is_null() {
+                                                    _rt::alloc::handle_alloc_error(layout73);
+                                                }
+                                                ptr
+                                            } else {
+                                                {
+                                                    ::core::ptr::null_mut()
+                                                }
+                                            };
+                                            for (i, e) in vec73.into_iter().enumerate() {
+                                                let base = result73.add(i * 12);
+                                                {
+                                                    let (t71_0, t71_1) = e;
+                                                    let vec72 =
+                                                        (t71_0.into_bytes()).into_boxed_slice();
+                                                    let ptr72 = vec72.as_ptr().cast::<u8>();
+                                                    let len72 = vec72.len();
+                                                    ::core::mem::forget(vec72);
+                                                    *base.add(4).cast::<usize>() = len72;
+                                                    *base.add(0).cast::<*mut u8>() =
+                                                        ptr72.cast_mut();
+                                                    *base.add(8).cast::<i32>() = _rt::as_i32(t71_1);
+                                                }
+                                            }
+                                            *base.add(12).cast::<usize>() = len73;
+                                            *base.add(8).cast::<*mut u8>() = result73;
+                                        }
+                                    }
+                                }
+                            }
+                            *ptr68.add(8).cast::<usize>() = len74;
+                            *ptr68.add(4).cast::<*mut u8>() = result74;
+                        }
+                        Err(e) => {
+                            *ptr68.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec75 = (e.into_bytes()).into_boxed_slice();
+                            let ptr75 = vec75.as_ptr().cast::<u8>();
+                            let len75 = vec75.len();
+                            ::core::mem::forget(vec75);
+                            *ptr68.add(8).cast::<usize>() = len75;
+                            *ptr68.add(4).cast::<*mut u8>() = ptr75.cast_mut();
                         }
                     };
-                    ptr2
+                    ptr68
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -51,37 +850,93 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                            let l12 = *arg0.add(4).cast::<*mut u8>();
+                            let l13 = *arg0.add(8).cast::<usize>();
+                            let base14 = l12;
+                            let len14 = l13;
+                            for i in 0..len14 {
+                                let base = base14.add(i * 16);
+                                {
+                                    let l1 = i32::from(*base.add(0).cast::<u8>());
+                                    match l1 {
+                                        0 => (),
+                                        1 => (),
+                                        2 => (),
+                                        3 => (),
+                                        4 => {
+                                            let l2 = *base.add(8).cast::<*mut u8>();
+                                            let l3 = *base.add(12).cast::<usize>();
+                                            _rt::cabi_dealloc(l2, l3, 1);
+                                        }
+                                        5 => {
+                                            let l4 = *base.add(8).cast::<*mut u8>();
+                                            let l5 = *base.add(12).cast::<usize>();
+                                            let base6 = l4;
+                                            let len6 = l5;
+                                            _rt::cabi_dealloc(base6, len6 * 4, 4);
+                                        }
+                                        _ => {
+                                            let l9 = *base.add(8).cast::<*mut u8>();
+                                            let l10 = *base.add(12).cast::<usize>();
+                                            let base11 = l9;
+                                            let len11 = l10;
+                                            for i in 0..len11 {
+                                                let base = base11.add(i * 12);
+                                                {
+                                                    let l7 = *base.add(0).cast::<*mut u8>();
+                                                    let l8 = *base.add(4).cast::<usize>();
+                                                    _rt::cabi_dealloc(l7, l8, 1);
+                                                }
+                                            }
+                                            _rt::cabi_dealloc(base11, len11 * 12, 4);
+                                        }
+                                    }
+                                }
+                            }
+                            _rt::cabi_dealloc(base14, len14 * 16, 8);
                         }
                         _ => {
-                            let l3 = *arg0.add(4).cast::<*mut u8>();
-                            let l4 = *arg0.add(8).cast::<usize>();
-                            _rt::cabi_dealloc(l3, l4, 1);
+                            let l15 = *arg0.add(4).cast::<*mut u8>();
+                            let l16 = *arg0.add(8).cast::<usize>();
+                            _rt::cabi_dealloc(l15, l16, 1);
                         }
                     }
                 }
                 pub trait Guest {
-                    fn exec(text: _rt::String) -> Result<_rt::String, _rt::String>;
+                    /// A component can provide any number of filters. `exec` is passed the `name` from the
+                    /// description of the filter being run.
+                    fn describe() -> _rt::Vec<FilterDescription>;
+                    fn exec(
+                        name: _rt::String,
+                        input: Value,
+                        args: Arguments,
+                    ) -> Result<Value, _rt::String>;
                 }
                 #[doc(hidden)]
 
-                macro_rules! __export_fermyon_spin_template_filter_types_0_0_1_cabi{
-        ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
-
-          #[export_name = "fermyon:spin-template-filter/types@0.0.1#exec"]
-          unsafe extern "C" fn export_exec(arg0: *mut u8,arg1: usize,) -> *mut u8 {
-            $($path_to_types)*::_export_exec_cabi::<$ty>(arg0, arg1)
-          }
-          #[export_name = "cabi_post_fermyon:spin-template-filter/types@0.0.1#exec"]
-          unsafe extern "C" fn _post_return_exec(arg0: *mut u8,) {
-            $($path_to_types)*::__post_return_exec::<$ty>(arg0)
-          }
-        };);
-      }
+                macro_rules! __export_fermyon_spin_template_filter_types_0_0_2_cabi{
+      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+        #[export_name = "fermyon:spin-template-filter/types@0.0.2#describe"]
+        unsafe extern "C" fn export_describe() -> *mut u8 {
+          $($path_to_types)*::_export_describe_cabi::<$ty>()
+        }
+        #[export_name = "cabi_post_fermyon:spin-template-filter/types@0.0.2#describe"]
+        unsafe extern "C" fn _post_return_describe(arg0: *mut u8,) {
+          $($path_to_types)*::__post_return_describe::<$ty>(arg0)
+        }
+        #[export_name = "fermyon:spin-template-filter/types@0.0.2#exec"]
+        unsafe extern "C" fn export_exec(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,arg4: *mut u8,arg5: usize,arg6: *mut u8,arg7: usize,) -> *mut u8 {
+          $($path_to_types)*::_export_exec_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7)
+        }
+        #[export_name = "cabi_post_fermyon:spin-template-filter/types@0.0.2#exec"]
+        unsafe extern "C" fn _post_return_exec(arg0: *mut u8,) {
+          $($path_to_types)*::__post_return_exec::<$ty>(arg0)
+        }
+      };);
+    }
                 #[doc(hidden)]
-                pub(crate) use __export_fermyon_spin_template_filter_types_0_0_1_cabi;
+                pub(crate) use __export_fermyon_spin_template_filter_types_0_0_2_cabi;
                 #[repr(align(4))]
                 struct _RetArea([::core::mem::MaybeUninit<u8>; 12]);
                 static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 12]);
@@ -90,11 +945,7 @@ pub mod exports {
     }
 }
 mod _rt {
-
-    #[cfg(target_arch = "wasm32")]
-    pub fn run_ctors_once() {
-        wit_bindgen_rt::run_ctors_once();
-    }
+    pub use alloc_crate::string::String;
     pub use alloc_crate::vec::Vec;
     pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
         if cfg!(debug_assertions) {
@@ -103,6 +954,13 @@ mod _rt {
             String::from_utf8_unchecked(bytes)
         }
     }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -110,9 +968,143 @@ mod _rt {
         let layout = alloc::Layout::from_size_align_unchecked(size, align);
         alloc::dealloc(ptr as *mut u8, layout);
     }
-    pub use alloc_crate::string::String;
-    extern crate alloc as alloc_crate;
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_ctors_once() {
+        wit_bindgen_rt::run_ctors_once();
+    }
     pub use alloc_crate::alloc;
+    pub unsafe fn bool_lift(val: u8) -> bool {
+        if cfg!(debug_assertions) {
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
+        } else {
+            val != 0
+        }
+    }
+
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+
+    pub fn as_f64<T: AsF64>(t: T) -> f64 {
+        t.as_f64()
+    }
+
+    pub trait AsF64 {
+        fn as_f64(self) -> f64;
+    }
+
+    impl<'a, T: Copy + AsF64> AsF64 for &'a T {
+        fn as_f64(self) -> f64 {
+            (*self).as_f64()
+        }
+    }
+
+    impl AsF64 for f64 {
+        #[inline]
+        fn as_f64(self) -> f64 {
+            self as f64
+        }
+    }
+
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    extern crate alloc as alloc_crate;
 }
 
 /// Generates `#[no_mangle]` functions to export the specified type as the
@@ -137,7 +1129,7 @@ mod _rt {
 macro_rules! __export_custom_filter_impl {
   ($ty:ident) => (self::export!($ty with_types_in self););
   ($ty:ident with_types_in $($path_to_types_root:tt)*) => (
-  $($path_to_types_root)*::exports::fermyon::spin_template_filter::types::__export_fermyon_spin_template_filter_types_0_0_1_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template_filter::types);
+  $($path_to_types_root)*::exports::fermyon::spin_template_filter::types::__export_fermyon_spin_template_filter_types_0_0_2_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template_filter::types);
   )
 }
 #[doc(inline)]
@@ -146,13 +1138,22 @@ pub(crate) use __export_custom_filter_impl as export;
 #[cfg(target_arch = "wasm32")]
 #[link_section = "component-type:wit-bindgen:0.25.0:custom-filter:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 265] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x85\x01\x01A\x02\x01\
-A\x02\x01B\x03\x01j\x01s\x01s\x01@\x01\x04texts\0\0\x04\0\x04exec\x01\x01\x04\x01\
-(fermyon:spin-template-filter/types@0.0.1\x05\0\x04\x010fermyon:spin-template-fi\
-lter/custom-filter@0.0.1\x04\0\x0b\x13\x01\0\x0dcustom-filter\x03\0\0\0G\x09prod\
-ucers\x01\x0cprocessed-by\x02\x0dwit-component\x070.208.1\x10wit-bindgen-rust\x06\
-0.25.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 661] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x91\x04\x01A\x02\x01\
+A\x04\x01B\x06\x01ks\x01@\x01\x04names\0\0\x04\0\x03get\x01\x01\x01ps\x01@\0\0\x02\
+\x04\0\x05names\x01\x03\x03\x01,fermyon:spin-template-filter/variables@0.0.2\x05\
+\0\x01B\x17\x01py\x01o\x02sy\x01p\x01\x01q\x07\x03nil\0\0\x04bool\x01\x7f\0\x03i\
+nt\x01x\0\x05float\x01u\0\x06string\x01s\0\x05array\x01\0\0\x06object\x01\x02\0\x04\
+\0\x0avalue-node\x03\0\x03\x01p\x04\x04\0\x05value\x03\0\x05\x01r\x03\x04names\x0b\
+descriptions\x08required\x7f\x04\0\x09parameter\x03\0\x07\x01p\x08\x01r\x04\x04n\
+ames\x0bdescriptions\x0apositional\x09\x07keyword\x09\x04\0\x12filter-descriptio\
+n\x03\0\x0a\x01p\x06\x01o\x02s\x06\x01p\x0d\x01r\x02\x0apositional\x0c\x07keywor\
+d\x0e\x04\0\x09arguments\x03\0\x0f\x01p\x0b\x01@\0\0\x11\x04\0\x08describe\x01\x12\
+\x01j\x01\x06\x01s\x01@\x03\x04names\x05input\x06\x04args\x10\0\x13\x04\0\x04exe\
+c\x01\x14\x04\x01(fermyon:spin-template-filter/types@0.0.2\x05\x01\x04\x010fermy\
+on:spin-template-filter/custom-filter@0.0.2\x04\0\x0b\x13\x01\0\x0dcustom-filter\
+\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.208.1\x10\
+wit-bindgen-rust\x060.25.0";
 
 #[inline(never)]
 #[doc(hidden)]
@@ -1,14 +1,68 @@
 #[allow(warnings)]
 mod bindings;
 
-use bindings::exports::fermyon::spin_template_filter::types::Guest;
+use bindings::exports::fermyon::spin_template_filter::types::{Arguments, FilterDescription, Guest, Parameter, Value, ValueNode};
 
 struct Component;
 
 impl Guest for Component {
-    fn exec(text: String) -> Result<String, String> {
-        let bits = text.split('-').collect::<Vec<_>>();
-        Ok(bits.join("-SPORK-"))
+    fn describe() -> Vec<FilterDescription> {
+        vec![
+            FilterDescription {
+                name: "spork".to_owned(),
+                description: "Replace hyphens with a cutlery-themed separator.".to_owned(),
+                positional: vec![],
+                keyword: vec![Parameter {
+                    name: "utensil".to_owned(),
+                    description: "The separator to use between words. Defaults to SPORK.".to_owned(),
+                    required: false,
+                }],
+            },
+            FilterDescription {
+                name: "tines".to_owned(),
+                description: "Count the hyphen-separated words in the text.".to_owned(),
+                positional: vec![],
+                keyword: vec![],
+            },
+            FilterDescription {
+                name: "signed".to_owned(),
+                description: "Sign the text with the project's authors.".to_owned(),
+                positional: vec![],
+                keyword: vec![],
+            },
+        ]
+    }
+
+    fn exec(name: String, input: Value, args: Arguments) -> Result<Value, String> {
+        let Some(ValueNode::String(text)) = input.first() else {
+            return Err(format!("{name} only works on strings"));
+        };
+        match name.as_str() {
+            "spork" => Ok(spork(text, &args)),
+            "tines" => Ok(vec![ValueNode::Int(text.split('-').count() as i64)]),
+            "signed" => Ok(vec![ValueNode::String(signed(text))]),
+            _ => Err(format!("no filter called {name}")),
+        }
+    }
+}
+
+fn spork(text: &str, args: &Arguments) -> Value {
+    let utensil = args.keyword.iter()
+        .find(|(name, _)| name == "utensil")
+        .and_then(|(_, value)| match value.first() {
+            Some(ValueNode::String(s)) => Some(s.as_str()),
+            _ => None,
+        })
+        .unwrap_or("SPORK");
+    let bits = text.split('-').collect::<Vec<_>>();
+    vec![ValueNode::String(bits.join(&format!("-{utensil}-")))]
+}
+
+// Filters can read, but not change, the variables of the template being rendered
+fn signed(text: &str) -> String {
+    match bindings::fermyon::spin_template_filter::variables::get("authors") {
+        Some(authors) => format!("{text} (by {authors})"),
+        None => text.to_owned(),
     }
 }
 
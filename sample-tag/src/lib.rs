@@ -0,0 +1,56 @@
+#[allow(warnings)]
+mod bindings;
+
+use bindings::exports::fermyon::spin_template_filter::tags::{Guest, TagDescription};
+
+struct Component;
+
+impl Guest for Component {
+    fn describe() -> Vec<TagDescription> {
+        vec![
+            TagDescription {
+                name: "license".to_owned(),
+                description: "A license notice for the project, e.g. {% license \"MIT\" %}. The copyright holder defaults to the project's authors.".to_owned(),
+                block: false,
+            },
+            TagDescription {
+                name: "region".to_owned(),
+                description: "Mark the content as a named region, e.g. {% region \"deps\", \"#\" %}...{% endregion %}, so that it can be found and updated later. The second argument is the comment marker, defaulting to #.".to_owned(),
+                block: true,
+            },
+        ]
+    }
+
+    fn render(name: String, args: Vec<String>, body: Option<String>) -> Result<String, String> {
+        match (name.as_str(), body) {
+            ("license", None) => license(&args),
+            ("region", Some(body)) => region(&args, &body),
+            _ => Err(format!("no tag called {name}")),
+        }
+    }
+}
+
+fn license(args: &[String]) -> Result<String, String> {
+    let (spdx_id, holder) = match args {
+        [spdx_id] => (spdx_id, bindings::fermyon::spin_template_filter::variables::get("authors")),
+        [spdx_id, holder] => (spdx_id, Some(holder.to_owned())),
+        _ => return Err("license expects a license identifier and optionally a copyright holder".to_owned()),
+    };
+    match holder {
+        Some(holder) => Ok(format!("Copyright {holder}. Licensed under {spdx_id}.")),
+        None => Ok(format!("Licensed under {spdx_id}.")),
+    }
+}
+
+fn region(args: &[String], body: &str) -> Result<String, String> {
+    let (name, comment) = match args {
+        [name] => (name.as_str(), "#"),
+        [name, comment] => (name.as_str(), comment.as_str()),
+        _ => return Err("region expects a name and optionally a comment marker".to_owned()),
+    };
+    let body = body.strip_prefix('\n').unwrap_or(body);
+    let newline = if body.is_empty() || body.ends_with('\n') { "" } else { "\n" };
+    Ok(format!("{comment} region: {name}\n{body}{newline}{comment} endregion: {name}"))
+}
+
+bindings::export!(Component with_types_in bindings);
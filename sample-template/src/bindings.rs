@@ -12,17 +12,82 @@ pub mod fermyon {
             static __FORCE_SECTION_REF: fn() =
                 super::super::super::__link_custom_section_describing_imports;
             use super::super::super::_rt;
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, PartialEq)]
+            pub enum TemplateErrorKind {
+                Parse,
+                Render,
+                UndefinedVariables,
+            }
+            impl ::core::fmt::Debug for TemplateErrorKind {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        TemplateErrorKind::Parse => {
+                            f.debug_tuple("TemplateErrorKind::Parse").finish()
+                        }
+                        TemplateErrorKind::Render => {
+                            f.debug_tuple("TemplateErrorKind::Render").finish()
+                        }
+                        TemplateErrorKind::UndefinedVariables => f
+                            .debug_tuple("TemplateErrorKind::UndefinedVariables")
+                            .finish(),
+                    }
+                }
+            }
+
+            impl TemplateErrorKind {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> TemplateErrorKind {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+
+                    match val {
+                        0 => TemplateErrorKind::Parse,
+                        1 => TemplateErrorKind::Render,
+                        2 => TemplateErrorKind::UndefinedVariables,
+
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+
+            /// Where possible the host says where in the template things went wrong.  `source-file`
+            /// is the content file path, and is not known for strings passed to `evaluate-template`.
+            #[derive(Clone)]
+            pub struct TemplateError {
+                pub kind: TemplateErrorKind,
+                pub message: _rt::String,
+                pub source_file: Option<_rt::String>,
+                pub line: Option<u32>,
+                pub column: Option<u32>,
+                pub snippet: Option<_rt::String>,
+            }
+            impl ::core::fmt::Debug for TemplateError {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct("TemplateError")
+                        .field("kind", &self.kind)
+                        .field("message", &self.message)
+                        .field("source-file", &self.source_file)
+                        .field("line", &self.line)
+                        .field("column", &self.column)
+                        .field("snippet", &self.snippet)
+                        .finish()
+                }
+            }
             #[derive(Clone)]
             pub enum Error {
                 Cancel,
                 /// I know it's not truly an error but work with me here
                 Other(_rt::String),
+                Template(TemplateError),
             }
             impl ::core::fmt::Debug for Error {
                 fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                     match self {
                         Error::Cancel => f.debug_tuple("Error::Cancel").finish(),
                         Error::Other(e) => f.debug_tuple("Error::Other").field(e).finish(),
+                        Error::Template(e) => f.debug_tuple("Error::Template").field(e).finish(),
                     }
                 }
             }
@@ -96,7 +161,7 @@ pub mod fermyon {
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[resource-drop]execution-context"]
                             fn drop(_: u32);
@@ -119,7 +184,7 @@ pub mod fermyon {
                         let len1 = vec1.len();
 
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]execution-context.set-variable"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8, _: usize);
@@ -144,14 +209,14 @@ pub mod fermyon {
                 pub fn evaluate_template(&self, template: &str) -> Result<_rt::String, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let vec0 = template;
                         let ptr0 = vec0.as_ptr().cast::<u8>();
                         let len0 = vec0.len();
                         let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]execution-context.evaluate-template"]
                             fn wit_import(_: i32, _: *mut u8, _: usize, _: *mut u8);
@@ -178,11 +243,10 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l6 = i32::from(*ptr1.add(4).cast::<u8>());
-                                    let v10 = match l6 {
+                                    let v26 = match l6 {
                                         0 => Error::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e10 = {
+                                        1 => {
+                                            let e26 = {
                                                 let l7 = *ptr1.add(8).cast::<*mut u8>();
                                                 let l8 = *ptr1.add(12).cast::<usize>();
                                                 let len9 = l8;
@@ -191,11 +255,105 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes9)
                                             };
-                                            Error::Other(e10)
+                                            Error::Other(e26)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e26 = {
+                                                let l10 = i32::from(*ptr1.add(8).cast::<u8>());
+                                                let l11 = *ptr1.add(12).cast::<*mut u8>();
+                                                let l12 = *ptr1.add(16).cast::<usize>();
+                                                let len13 = l12;
+                                                let bytes13 = _rt::Vec::from_raw_parts(
+                                                    l11.cast(),
+                                                    len13,
+                                                    len13,
+                                                );
+                                                let l14 = i32::from(*ptr1.add(20).cast::<u8>());
+                                                let l18 = i32::from(*ptr1.add(32).cast::<u8>());
+                                                let l20 = i32::from(*ptr1.add(40).cast::<u8>());
+                                                let l22 = i32::from(*ptr1.add(48).cast::<u8>());
+
+                                                TemplateError {
+                                                    kind: TemplateErrorKind::_lift(l10 as u8),
+                                                    message: _rt::string_lift(bytes13),
+                                                    source_file: match l14 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l15 =
+                                                                    *ptr1.add(24).cast::<*mut u8>();
+                                                                let l16 =
+                                                                    *ptr1.add(28).cast::<usize>();
+                                                                let len17 = l16;
+                                                                let bytes17 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l15.cast(),
+                                                                        len17,
+                                                                        len17,
+                                                                    );
+
+                                                                _rt::string_lift(bytes17)
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    line: match l18 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l19 =
+                                                                    *ptr1.add(36).cast::<i32>();
+
+                                                                l19 as u32
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    column: match l20 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l21 =
+                                                                    *ptr1.add(44).cast::<i32>();
+
+                                                                l21 as u32
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                    snippet: match l22 {
+                                                        0 => None,
+                                                        1 => {
+                                                            let e = {
+                                                                let l23 =
+                                                                    *ptr1.add(52).cast::<*mut u8>();
+                                                                let l24 =
+                                                                    *ptr1.add(56).cast::<usize>();
+                                                                let len25 = l24;
+                                                                let bytes25 =
+                                                                    _rt::Vec::from_raw_parts(
+                                                                        l23.cast(),
+                                                                        len25,
+                                                                        len25,
+                                                                    );
+
+                                                                _rt::string_lift(bytes25)
+                                                            };
+                                                            Some(e)
+                                                        }
+                                                        _ => _rt::invalid_enum_discriminant(),
+                                                    },
+                                                }
+                                            };
+                                            Error::Template(e26)
                                         }
                                     };
 
-                                    v10
+                                    v26
                                 };
                                 Err(e)
                             }
@@ -204,6 +362,50 @@ pub mod fermyon {
                     }
                 }
             }
+            impl ExecutionContext {
+                #[allow(unused_unsafe, clippy::all)]
+                /// A child context sees the parent's variables (including ones set on the parent
+                /// later) but variables set on the child don't affect the parent or its siblings.
+                pub fn fork(&self) -> ExecutionContext {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
+                        extern "C" {
+                            #[link_name = "[method]execution-context.fork"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ExecutionContext::from_handle(ret as u32)
+                    }
+                }
+            }
+            impl ExecutionContext {
+                #[allow(unused_unsafe, clippy::all)]
+                /// A context with a copy of the variables as they are now.  Binding an action to a snapshot
+                /// means it renders with these values even if the guest goes on to change them.
+                pub fn snapshot(&self) -> ExecutionContext {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "fermyon:spin-template/types@0.0.2")]
+                        extern "C" {
+                            #[link_name = "[method]execution-context.snapshot"]
+                            fn wit_import(_: i32) -> i32;
+                        }
+
+                        #[cfg(not(target_arch = "wasm32"))]
+                        fn wit_import(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = wit_import((self).handle() as i32);
+                        ExecutionContext::from_handle(ret as u32)
+                    }
+                }
+            }
         }
 
         #[allow(dead_code, clippy::all)]
@@ -249,7 +451,7 @@ pub mod fermyon {
 
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[resource-drop]file"]
                             fn drop(_: u32);
@@ -281,7 +483,7 @@ pub mod fermyon {
                     };
                     let ptr3 = ret_area.0.as_mut_ptr().cast::<u8>();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "prompt"]
                         fn wit_import(
@@ -323,7 +525,7 @@ pub mod fermyon {
                         None => (0i32, 0i32),
                     };
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "confirm"]
                         fn wit_import(_: *mut u8, _: usize, _: i32, _: i32) -> i32;
@@ -372,7 +574,7 @@ pub mod fermyon {
                         None => (0i32, 0i32),
                     };
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                    #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                     extern "C" {
                         #[link_name = "select"]
                         fn wit_import(
@@ -413,7 +615,7 @@ pub mod fermyon {
                         let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[static]file.list-all"]
                             fn wit_import(_: *mut u8);
@@ -452,7 +654,7 @@ pub mod fermyon {
                         let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.path"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -476,11 +678,11 @@ pub mod fermyon {
                 pub fn read(&self) -> Result<_rt::String, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.read"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -507,12 +709,11 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l5 = i32::from(*ptr0.add(4).cast::<u8>());
-                                    use super::super::super::fermyon::spin_template::types::Error as V9;
-                                    let v9 = match l5 {
-                                        0 => V9::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e9 = {
+                                    use super::super::super::fermyon::spin_template::types::Error as V25;
+                                    let v25 = match l5 {
+                                        0 => V25::Cancel,
+                                        1 => {
+                                            let e25 = {
                                                 let l6 = *ptr0.add(8).cast::<*mut u8>();
                                                 let l7 = *ptr0.add(12).cast::<usize>();
                                                 let len8 = l7;
@@ -521,11 +722,89 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes8)
                                             };
-                                            V9::Other(e9)
+                                            V25::Other(e25)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e25 = {
+                                                let l9 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let l10 = *ptr0.add(12).cast::<*mut u8>();
+                                                let l11 = *ptr0.add(16).cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                let l13 = i32::from(*ptr0.add(20).cast::<u8>());
+                                                let l17 = i32::from(*ptr0.add(32).cast::<u8>());
+                                                let l19 = i32::from(*ptr0.add(40).cast::<u8>());
+                                                let l21 = i32::from(*ptr0.add(48).cast::<u8>());
+
+                                                super::super::super::fermyon::spin_template::types::TemplateError{
+                          kind: super::super::super::fermyon::spin_template::types::TemplateErrorKind::_lift(l9 as u8),
+                          message: _rt::string_lift(bytes12),
+                          source_file: match l13 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l14 = *ptr0.add(24).cast::<*mut u8>();
+                                let l15 = *ptr0.add(28).cast::<usize>();
+                                let len16 = l15;
+                                let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+
+                                _rt::string_lift(bytes16)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          line: match l17 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l18 = *ptr0.add(36).cast::<i32>();
+
+                                l18 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          column: match l19 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l20 = *ptr0.add(44).cast::<i32>();
+
+                                l20 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          snippet: match l21 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l22 = *ptr0.add(52).cast::<*mut u8>();
+                                let l23 = *ptr0.add(56).cast::<usize>();
+                                let len24 = l23;
+                                let bytes24 = _rt::Vec::from_raw_parts(l22.cast(), len24, len24);
+
+                                _rt::string_lift(bytes24)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                        }
+                                            };
+                                            V25::Template(e25)
                                         }
                                     };
 
-                                    v9
+                                    v25
                                 };
                                 Err(e)
                             }
@@ -539,11 +818,11 @@ pub mod fermyon {
                 pub fn read_binary(&self) -> Result<_rt::Vec<u8>, Error> {
                     unsafe {
                         #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                        struct RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                        let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 60]);
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.1")]
+                        #[link(wasm_import_module = "fermyon:spin-template/ui@0.0.2")]
                         extern "C" {
                             #[link_name = "[method]file.read-binary"]
                             fn wit_import(_: i32, _: *mut u8);
@@ -569,12 +848,11 @@ pub mod fermyon {
                             1 => {
                                 let e = {
                                     let l5 = i32::from(*ptr0.add(4).cast::<u8>());
-                                    use super::super::super::fermyon::spin_template::types::Error as V9;
-                                    let v9 = match l5 {
-                                        0 => V9::Cancel,
-                                        n => {
-                                            debug_assert_eq!(n, 1, "invalid enum discriminant");
-                                            let e9 = {
+                                    use super::super::super::fermyon::spin_template::types::Error as V25;
+                                    let v25 = match l5 {
+                                        0 => V25::Cancel,
+                                        1 => {
+                                            let e25 = {
                                                 let l6 = *ptr0.add(8).cast::<*mut u8>();
                                                 let l7 = *ptr0.add(12).cast::<usize>();
                                                 let len8 = l7;
@@ -583,11 +861,89 @@ pub mod fermyon {
 
                                                 _rt::string_lift(bytes8)
                                             };
-                                            V9::Other(e9)
+                                            V25::Other(e25)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 2, "invalid enum discriminant");
+                                            let e25 = {
+                                                let l9 = i32::from(*ptr0.add(8).cast::<u8>());
+                                                let l10 = *ptr0.add(12).cast::<*mut u8>();
+                                                let l11 = *ptr0.add(16).cast::<usize>();
+                                                let len12 = l11;
+                                                let bytes12 = _rt::Vec::from_raw_parts(
+                                                    l10.cast(),
+                                                    len12,
+                                                    len12,
+                                                );
+                                                let l13 = i32::from(*ptr0.add(20).cast::<u8>());
+                                                let l17 = i32::from(*ptr0.add(32).cast::<u8>());
+                                                let l19 = i32::from(*ptr0.add(40).cast::<u8>());
+                                                let l21 = i32::from(*ptr0.add(48).cast::<u8>());
+
+                                                super::super::super::fermyon::spin_template::types::TemplateError{
+                          kind: super::super::super::fermyon::spin_template::types::TemplateErrorKind::_lift(l9 as u8),
+                          message: _rt::string_lift(bytes12),
+                          source_file: match l13 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l14 = *ptr0.add(24).cast::<*mut u8>();
+                                let l15 = *ptr0.add(28).cast::<usize>();
+                                let len16 = l15;
+                                let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+
+                                _rt::string_lift(bytes16)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          line: match l17 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l18 = *ptr0.add(36).cast::<i32>();
+
+                                l18 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          column: match l19 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l20 = *ptr0.add(44).cast::<i32>();
+
+                                l20 as u32
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                          snippet: match l21 {
+                            0 => None,
+                            1 => {
+                              let e = {
+                                let l22 = *ptr0.add(52).cast::<*mut u8>();
+                                let l23 = *ptr0.add(56).cast::<usize>();
+                                let len24 = l23;
+                                let bytes24 = _rt::Vec::from_raw_parts(l22.cast(), len24, len24);
+
+                                _rt::string_lift(bytes24)
+                              };
+                              Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                          },
+                        }
+                                            };
+                                            V25::Template(e25)
                                         }
                                     };
 
-                                    v9
+                                    v25
                                 };
                                 Err(e)
                             }
@@ -598,6 +954,98 @@ pub mod fermyon {
             }
         }
     }
+    #[allow(dead_code)]
+    pub mod spin_template_filter {
+        #[allow(dead_code, clippy::all)]
+        pub mod variables {
+            #[used]
+            #[doc(hidden)]
+            #[cfg(target_arch = "wasm32")]
+            static __FORCE_SECTION_REF: fn() =
+                super::super::super::__link_custom_section_describing_imports;
+            use super::super::super::_rt;
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn get(name: &str) -> Option<_rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+                    let vec0 = name;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "fermyon:spin-template-filter/variables@0.0.2")]
+                    extern "C" {
+                        #[link_name = "get"]
+                        fn wit_import(_: *mut u8, _: usize, _: *mut u8);
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0.cast_mut(), len0, ptr1);
+                    let l2 = i32::from(*ptr1.add(0).cast::<u8>());
+                    match l2 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l3 = *ptr1.add(4).cast::<*mut u8>();
+                                let l4 = *ptr1.add(8).cast::<usize>();
+                                let len5 = l4;
+                                let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+
+                                _rt::string_lift(bytes5)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn names() -> _rt::Vec<_rt::String> {
+                unsafe {
+                    #[repr(align(4))]
+                    struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                    let mut ret_area = RetArea([::core::mem::MaybeUninit::uninit(); 8]);
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "fermyon:spin-template-filter/variables@0.0.2")]
+                    extern "C" {
+                        #[link_name = "names"]
+                        fn wit_import(_: *mut u8);
+                    }
+
+                    #[cfg(not(target_arch = "wasm32"))]
+                    fn wit_import(_: *mut u8) {
+                        unreachable!()
+                    }
+                    wit_import(ptr0);
+                    let l1 = *ptr0.add(0).cast::<*mut u8>();
+                    let l2 = *ptr0.add(4).cast::<usize>();
+                    let base6 = l1;
+                    let len6 = l2;
+                    let mut result6 = _rt::Vec::with_capacity(len6);
+                    for i in 0..len6 {
+                        let base = base6.add(i * 8);
+                        let e6 = {
+                            let l3 = *base.add(0).cast::<*mut u8>();
+                            let l4 = *base.add(4).cast::<usize>();
+                            let len5 = l4;
+                            let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+
+                            _rt::string_lift(bytes5)
+                        };
+                        result6.push(e6);
+                    }
+                    _rt::cabi_dealloc(base6, len6 * 8, 4);
+                    result6
+                }
+            }
+        }
+    }
 }
 #[allow(dead_code)]
 pub mod exports {
@@ -749,7 +1197,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-drop]edit"]
@@ -763,7 +1211,10 @@ pub mod exports {
 
                 pub enum Action {
                     CopyFileSubstituted(_rt::String),
+                    CopyFileSubstitutedIn((_rt::String, ExecutionContext)),
                     CopyFileToSubstituted((_rt::String, _rt::String)),
+                    CopyFileToSubstitutedIn((_rt::String, _rt::String, ExecutionContext)),
+                    /// e.g. rendering the same file for each of several components
                     CopyFileToRaw((_rt::String, _rt::String)),
                     WriteFile((_rt::String, _rt::String)),
                     WriteFileBinary((_rt::String, _rt::Vec<u8>)),
@@ -771,6 +1222,7 @@ pub mod exports {
                     /// normally auto but in case you need an empty directory a la fileserver
                     /// edit-file(tuple<string, func(existing: string) -> string>), // no! no! no!
                     EditFile((_rt::String, Edit)),
+                    EditFileIn((_rt::String, Edit, ExecutionContext)),
                 }
                 impl ::core::fmt::Debug for Action {
                     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
@@ -779,10 +1231,18 @@ pub mod exports {
                                 .debug_tuple("Action::CopyFileSubstituted")
                                 .field(e)
                                 .finish(),
+                            Action::CopyFileSubstitutedIn(e) => f
+                                .debug_tuple("Action::CopyFileSubstitutedIn")
+                                .field(e)
+                                .finish(),
                             Action::CopyFileToSubstituted(e) => f
                                 .debug_tuple("Action::CopyFileToSubstituted")
                                 .field(e)
                                 .finish(),
+                            Action::CopyFileToSubstitutedIn(e) => f
+                                .debug_tuple("Action::CopyFileToSubstitutedIn")
+                                .field(e)
+                                .finish(),
                             Action::CopyFileToRaw(e) => {
                                 f.debug_tuple("Action::CopyFileToRaw").field(e).finish()
                             }
@@ -798,6 +1258,9 @@ pub mod exports {
                             Action::EditFile(e) => {
                                 f.debug_tuple("Action::EditFile").field(e).finish()
                             }
+                            Action::EditFileIn(e) => {
+                                f.debug_tuple("Action::EditFileIn").field(e).finish()
+                            }
                         }
                     }
                 }
@@ -835,12 +1298,12 @@ pub mod exports {
                         }
                         Err(e) => {
                             *ptr3.add(0).cast::<u8>() = (1i32) as u8;
-                            use super::super::super::super::fermyon::spin_template::types::Error as V6;
+                            use super::super::super::super::fermyon::spin_template::types::Error as V10;
                             match e {
-                                V6::Cancel => {
+                                V10::Cancel => {
                                     *ptr3.add(4).cast::<u8>() = (0i32) as u8;
                                 }
-                                V6::Other(e) => {
+                                V10::Other(e) => {
                                     *ptr3.add(4).cast::<u8>() = (1i32) as u8;
                                     let vec5 = (e.into_bytes()).into_boxed_slice();
                                     let ptr5 = vec5.as_ptr().cast::<u8>();
@@ -849,6 +1312,63 @@ pub mod exports {
                                     *ptr3.add(12).cast::<usize>() = len5;
                                     *ptr3.add(8).cast::<*mut u8>() = ptr5.cast_mut();
                                 }
+                                V10::Template(e) => {
+                                    *ptr3.add(4).cast::<u8>() = (2i32) as u8;
+                                    let super::super::super::super::fermyon::spin_template::types::TemplateError{ kind:kind6, message:message6, source_file:source_file6, line:line6, column:column6, snippet:snippet6, } = e;
+                                    *ptr3.add(8).cast::<u8>() = (kind6.clone() as i32) as u8;
+                                    let vec7 = (message6.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr3.add(16).cast::<usize>() = len7;
+                                    *ptr3.add(12).cast::<*mut u8>() = ptr7.cast_mut();
+                                    match source_file6 {
+                                        Some(e) => {
+                                            *ptr3.add(20).cast::<u8>() = (1i32) as u8;
+                                            let vec8 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr8 = vec8.as_ptr().cast::<u8>();
+                                            let len8 = vec8.len();
+                                            ::core::mem::forget(vec8);
+                                            *ptr3.add(28).cast::<usize>() = len8;
+                                            *ptr3.add(24).cast::<*mut u8>() = ptr8.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr3.add(20).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match line6 {
+                                        Some(e) => {
+                                            *ptr3.add(32).cast::<u8>() = (1i32) as u8;
+                                            *ptr3.add(36).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr3.add(32).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match column6 {
+                                        Some(e) => {
+                                            *ptr3.add(40).cast::<u8>() = (1i32) as u8;
+                                            *ptr3.add(44).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr3.add(40).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match snippet6 {
+                                        Some(e) => {
+                                            *ptr3.add(48).cast::<u8>() = (1i32) as u8;
+                                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *ptr3.add(56).cast::<usize>() = len9;
+                                            *ptr3.add(52).cast::<*mut u8>() = ptr9.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr3.add(48).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
                             }
                         }
                     };
@@ -868,11 +1388,34 @@ pub mod exports {
                             let l3 = i32::from(*arg0.add(4).cast::<u8>());
                             match l3 {
                                 0 => (),
-                                _ => {
+                                1 => {
                                     let l4 = *arg0.add(8).cast::<*mut u8>();
                                     let l5 = *arg0.add(12).cast::<usize>();
                                     _rt::cabi_dealloc(l4, l5, 1);
                                 }
+                                _ => {
+                                    let l6 = *arg0.add(12).cast::<*mut u8>();
+                                    let l7 = *arg0.add(16).cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                    let l8 = i32::from(*arg0.add(20).cast::<u8>());
+                                    match l8 {
+                                        0 => (),
+                                        _ => {
+                                            let l9 = *arg0.add(24).cast::<*mut u8>();
+                                            let l10 = *arg0.add(28).cast::<usize>();
+                                            _rt::cabi_dealloc(l9, l10, 1);
+                                        }
+                                    }
+                                    let l11 = i32::from(*arg0.add(48).cast::<u8>());
+                                    match l11 {
+                                        0 => (),
+                                        _ => {
+                                            let l12 = *arg0.add(52).cast::<*mut u8>();
+                                            let l13 = *arg0.add(56).cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -917,14 +1460,14 @@ pub mod exports {
                     match result3 {
                         Ok(e) => {
                             *ptr4.add(0).cast::<u8>() = (0i32) as u8;
-                            let vec21 = e;
-                            let len21 = vec21.len();
-                            let layout21 =
-                                _rt::alloc::Layout::from_size_align_unchecked(vec21.len() * 20, 4);
-                            let result21 = if layout21.size() != 0 {
-                                let ptr = _rt::alloc::alloc(layout21).cast::<u8>();
+                            let vec28 = e;
+                            let len28 = vec28.len();
+                            let layout28 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec28.len() * 24, 4);
+                            let result28 = if layout28.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout28).cast::<u8>();
                                 if ptr.is_null() {
-                                    _rt::alloc::handle_alloc_error(layout21);
+                                    _rt::alloc::handle_alloc_error(layout28);
                                 }
                                 ptr
                             } else {
@@ -932,8 +1475,8 @@ pub mod exports {
                                     ::core::ptr::null_mut()
                                 }
                             };
-                            for (i, e) in vec21.into_iter().enumerate() {
-                                let base = result21.add(i * 20);
+                            for (i, e) in vec28.into_iter().enumerate() {
+                                let base = result28.add(i * 24);
                                 {
                                     match e {
                                         Action::CopyFileSubstituted(e) => {
@@ -945,7 +1488,7 @@ pub mod exports {
                                             *base.add(8).cast::<usize>() = len5;
                                             *base.add(4).cast::<*mut u8>() = ptr5.cast_mut();
                                         }
-                                        Action::CopyFileToSubstituted(e) => {
+                                        Action::CopyFileSubstitutedIn(e) => {
                                             *base.add(0).cast::<u8>() = (1i32) as u8;
                                             let (t6_0, t6_1) = e;
                                             let vec7 = (t6_0.into_bytes()).into_boxed_slice();
@@ -954,103 +1497,204 @@ pub mod exports {
                                             ::core::mem::forget(vec7);
                                             *base.add(8).cast::<usize>() = len7;
                                             *base.add(4).cast::<*mut u8>() = ptr7.cast_mut();
-                                            let vec8 = (t6_1.into_bytes()).into_boxed_slice();
-                                            let ptr8 = vec8.as_ptr().cast::<u8>();
-                                            let len8 = vec8.len();
-                                            ::core::mem::forget(vec8);
-                                            *base.add(16).cast::<usize>() = len8;
-                                            *base.add(12).cast::<*mut u8>() = ptr8.cast_mut();
+                                            *base.add(12).cast::<i32>() =
+                                                (t6_1).take_handle() as i32;
                                         }
-                                        Action::CopyFileToRaw(e) => {
+                                        Action::CopyFileToSubstituted(e) => {
                                             *base.add(0).cast::<u8>() = (2i32) as u8;
-                                            let (t9_0, t9_1) = e;
-                                            let vec10 = (t9_0.into_bytes()).into_boxed_slice();
+                                            let (t8_0, t8_1) = e;
+                                            let vec9 = (t8_0.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *base.add(8).cast::<usize>() = len9;
+                                            *base.add(4).cast::<*mut u8>() = ptr9.cast_mut();
+                                            let vec10 = (t8_1.into_bytes()).into_boxed_slice();
                                             let ptr10 = vec10.as_ptr().cast::<u8>();
                                             let len10 = vec10.len();
                                             ::core::mem::forget(vec10);
-                                            *base.add(8).cast::<usize>() = len10;
-                                            *base.add(4).cast::<*mut u8>() = ptr10.cast_mut();
-                                            let vec11 = (t9_1.into_bytes()).into_boxed_slice();
-                                            let ptr11 = vec11.as_ptr().cast::<u8>();
-                                            let len11 = vec11.len();
-                                            ::core::mem::forget(vec11);
-                                            *base.add(16).cast::<usize>() = len11;
-                                            *base.add(12).cast::<*mut u8>() = ptr11.cast_mut();
+                                            *base.add(16).cast::<usize>() = len10;
+                                            *base.add(12).cast::<*mut u8>() = ptr10.cast_mut();
                                         }
-                                        Action::WriteFile(e) => {
+                                        Action::CopyFileToSubstitutedIn(e) => {
                                             *base.add(0).cast::<u8>() = (3i32) as u8;
-                                            let (t12_0, t12_1) = e;
-                                            let vec13 = (t12_0.into_bytes()).into_boxed_slice();
+                                            let (t11_0, t11_1, t11_2) = e;
+                                            let vec12 = (t11_0.into_bytes()).into_boxed_slice();
+                                            let ptr12 = vec12.as_ptr().cast::<u8>();
+                                            let len12 = vec12.len();
+                                            ::core::mem::forget(vec12);
+                                            *base.add(8).cast::<usize>() = len12;
+                                            *base.add(4).cast::<*mut u8>() = ptr12.cast_mut();
+                                            let vec13 = (t11_1.into_bytes()).into_boxed_slice();
                                             let ptr13 = vec13.as_ptr().cast::<u8>();
                                             let len13 = vec13.len();
                                             ::core::mem::forget(vec13);
-                                            *base.add(8).cast::<usize>() = len13;
-                                            *base.add(4).cast::<*mut u8>() = ptr13.cast_mut();
-                                            let vec14 = (t12_1.into_bytes()).into_boxed_slice();
-                                            let ptr14 = vec14.as_ptr().cast::<u8>();
-                                            let len14 = vec14.len();
-                                            ::core::mem::forget(vec14);
-                                            *base.add(16).cast::<usize>() = len14;
-                                            *base.add(12).cast::<*mut u8>() = ptr14.cast_mut();
+                                            *base.add(16).cast::<usize>() = len13;
+                                            *base.add(12).cast::<*mut u8>() = ptr13.cast_mut();
+                                            *base.add(20).cast::<i32>() =
+                                                (t11_2).take_handle() as i32;
                                         }
-                                        Action::WriteFileBinary(e) => {
+                                        Action::CopyFileToRaw(e) => {
                                             *base.add(0).cast::<u8>() = (4i32) as u8;
-                                            let (t15_0, t15_1) = e;
-                                            let vec16 = (t15_0.into_bytes()).into_boxed_slice();
+                                            let (t14_0, t14_1) = e;
+                                            let vec15 = (t14_0.into_bytes()).into_boxed_slice();
+                                            let ptr15 = vec15.as_ptr().cast::<u8>();
+                                            let len15 = vec15.len();
+                                            ::core::mem::forget(vec15);
+                                            *base.add(8).cast::<usize>() = len15;
+                                            *base.add(4).cast::<*mut u8>() = ptr15.cast_mut();
+                                            let vec16 = (t14_1.into_bytes()).into_boxed_slice();
                                             let ptr16 = vec16.as_ptr().cast::<u8>();
                                             let len16 = vec16.len();
                                             ::core::mem::forget(vec16);
-                                            *base.add(8).cast::<usize>() = len16;
-                                            *base.add(4).cast::<*mut u8>() = ptr16.cast_mut();
-                                            let vec17 = (t15_1).into_boxed_slice();
-                                            let ptr17 = vec17.as_ptr().cast::<u8>();
-                                            let len17 = vec17.len();
-                                            ::core::mem::forget(vec17);
-                                            *base.add(16).cast::<usize>() = len17;
-                                            *base.add(12).cast::<*mut u8>() = ptr17.cast_mut();
+                                            *base.add(16).cast::<usize>() = len16;
+                                            *base.add(12).cast::<*mut u8>() = ptr16.cast_mut();
                                         }
-                                        Action::CreateDir(e) => {
+                                        Action::WriteFile(e) => {
                                             *base.add(0).cast::<u8>() = (5i32) as u8;
-                                            let vec18 = (e.into_bytes()).into_boxed_slice();
+                                            let (t17_0, t17_1) = e;
+                                            let vec18 = (t17_0.into_bytes()).into_boxed_slice();
                                             let ptr18 = vec18.as_ptr().cast::<u8>();
                                             let len18 = vec18.len();
                                             ::core::mem::forget(vec18);
                                             *base.add(8).cast::<usize>() = len18;
                                             *base.add(4).cast::<*mut u8>() = ptr18.cast_mut();
+                                            let vec19 = (t17_1.into_bytes()).into_boxed_slice();
+                                            let ptr19 = vec19.as_ptr().cast::<u8>();
+                                            let len19 = vec19.len();
+                                            ::core::mem::forget(vec19);
+                                            *base.add(16).cast::<usize>() = len19;
+                                            *base.add(12).cast::<*mut u8>() = ptr19.cast_mut();
                                         }
-                                        Action::EditFile(e) => {
+                                        Action::WriteFileBinary(e) => {
                                             *base.add(0).cast::<u8>() = (6i32) as u8;
-                                            let (t19_0, t19_1) = e;
-                                            let vec20 = (t19_0.into_bytes()).into_boxed_slice();
-                                            let ptr20 = vec20.as_ptr().cast::<u8>();
-                                            let len20 = vec20.len();
-                                            ::core::mem::forget(vec20);
-                                            *base.add(8).cast::<usize>() = len20;
-                                            *base.add(4).cast::<*mut u8>() = ptr20.cast_mut();
+                                            let (t20_0, t20_1) = e;
+                                            let vec21 = (t20_0.into_bytes()).into_boxed_slice();
+                                            let ptr21 = vec21.as_ptr().cast::<u8>();
+                                            let len21 = vec21.len();
+                                            ::core::mem::forget(vec21);
+                                            *base.add(8).cast::<usize>() = len21;
+                                            *base.add(4).cast::<*mut u8>() = ptr21.cast_mut();
+                                            let vec22 = (t20_1).into_boxed_slice();
+                                            let ptr22 = vec22.as_ptr().cast::<u8>();
+                                            let len22 = vec22.len();
+                                            ::core::mem::forget(vec22);
+                                            *base.add(16).cast::<usize>() = len22;
+                                            *base.add(12).cast::<*mut u8>() = ptr22.cast_mut();
+                                        }
+                                        Action::CreateDir(e) => {
+                                            *base.add(0).cast::<u8>() = (7i32) as u8;
+                                            let vec23 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr23 = vec23.as_ptr().cast::<u8>();
+                                            let len23 = vec23.len();
+                                            ::core::mem::forget(vec23);
+                                            *base.add(8).cast::<usize>() = len23;
+                                            *base.add(4).cast::<*mut u8>() = ptr23.cast_mut();
+                                        }
+                                        Action::EditFile(e) => {
+                                            *base.add(0).cast::<u8>() = (8i32) as u8;
+                                            let (t24_0, t24_1) = e;
+                                            let vec25 = (t24_0.into_bytes()).into_boxed_slice();
+                                            let ptr25 = vec25.as_ptr().cast::<u8>();
+                                            let len25 = vec25.len();
+                                            ::core::mem::forget(vec25);
+                                            *base.add(8).cast::<usize>() = len25;
+                                            *base.add(4).cast::<*mut u8>() = ptr25.cast_mut();
+                                            *base.add(12).cast::<i32>() =
+                                                (t24_1).take_handle() as i32;
+                                        }
+                                        Action::EditFileIn(e) => {
+                                            *base.add(0).cast::<u8>() = (9i32) as u8;
+                                            let (t26_0, t26_1, t26_2) = e;
+                                            let vec27 = (t26_0.into_bytes()).into_boxed_slice();
+                                            let ptr27 = vec27.as_ptr().cast::<u8>();
+                                            let len27 = vec27.len();
+                                            ::core::mem::forget(vec27);
+                                            *base.add(8).cast::<usize>() = len27;
+                                            *base.add(4).cast::<*mut u8>() = ptr27.cast_mut();
                                             *base.add(12).cast::<i32>() =
-                                                (t19_1).take_handle() as i32;
+                                                (t26_1).take_handle() as i32;
+                                            *base.add(16).cast::<i32>() =
+                                                (t26_2).take_handle() as i32;
                                         }
                                     }
                                 }
                             }
-                            *ptr4.add(8).cast::<usize>() = len21;
-                            *ptr4.add(4).cast::<*mut u8>() = result21;
+                            *ptr4.add(8).cast::<usize>() = len28;
+                            *ptr4.add(4).cast::<*mut u8>() = result28;
                         }
                         Err(e) => {
                             *ptr4.add(0).cast::<u8>() = (1i32) as u8;
-                            use super::super::super::super::fermyon::spin_template::types::Error as V23;
+                            use super::super::super::super::fermyon::spin_template::types::Error as V34;
                             match e {
-                                V23::Cancel => {
+                                V34::Cancel => {
                                     *ptr4.add(4).cast::<u8>() = (0i32) as u8;
                                 }
-                                V23::Other(e) => {
+                                V34::Other(e) => {
                                     *ptr4.add(4).cast::<u8>() = (1i32) as u8;
-                                    let vec22 = (e.into_bytes()).into_boxed_slice();
-                                    let ptr22 = vec22.as_ptr().cast::<u8>();
-                                    let len22 = vec22.len();
-                                    ::core::mem::forget(vec22);
-                                    *ptr4.add(12).cast::<usize>() = len22;
-                                    *ptr4.add(8).cast::<*mut u8>() = ptr22.cast_mut();
+                                    let vec29 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr29 = vec29.as_ptr().cast::<u8>();
+                                    let len29 = vec29.len();
+                                    ::core::mem::forget(vec29);
+                                    *ptr4.add(12).cast::<usize>() = len29;
+                                    *ptr4.add(8).cast::<*mut u8>() = ptr29.cast_mut();
+                                }
+                                V34::Template(e) => {
+                                    *ptr4.add(4).cast::<u8>() = (2i32) as u8;
+                                    let super::super::super::super::fermyon::spin_template::types::TemplateError{ kind:kind30, message:message30, source_file:source_file30, line:line30, column:column30, snippet:snippet30, } = e;
+                                    *ptr4.add(8).cast::<u8>() = (kind30.clone() as i32) as u8;
+                                    let vec31 = (message30.into_bytes()).into_boxed_slice();
+                                    let ptr31 = vec31.as_ptr().cast::<u8>();
+                                    let len31 = vec31.len();
+                                    ::core::mem::forget(vec31);
+                                    *ptr4.add(16).cast::<usize>() = len31;
+                                    *ptr4.add(12).cast::<*mut u8>() = ptr31.cast_mut();
+                                    match source_file30 {
+                                        Some(e) => {
+                                            *ptr4.add(20).cast::<u8>() = (1i32) as u8;
+                                            let vec32 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr32 = vec32.as_ptr().cast::<u8>();
+                                            let len32 = vec32.len();
+                                            ::core::mem::forget(vec32);
+                                            *ptr4.add(28).cast::<usize>() = len32;
+                                            *ptr4.add(24).cast::<*mut u8>() = ptr32.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr4.add(20).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match line30 {
+                                        Some(e) => {
+                                            *ptr4.add(32).cast::<u8>() = (1i32) as u8;
+                                            *ptr4.add(36).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr4.add(32).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match column30 {
+                                        Some(e) => {
+                                            *ptr4.add(40).cast::<u8>() = (1i32) as u8;
+                                            *ptr4.add(44).cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *ptr4.add(40).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match snippet30 {
+                                        Some(e) => {
+                                            *ptr4.add(48).cast::<u8>() = (1i32) as u8;
+                                            let vec33 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr33 = vec33.as_ptr().cast::<u8>();
+                                            let len33 = vec33.len();
+                                            ::core::mem::forget(vec33);
+                                            *ptr4.add(56).cast::<usize>() = len33;
+                                            *ptr4.add(52).cast::<*mut u8>() = ptr33.cast_mut();
+                                        }
+                                        None => {
+                                            *ptr4.add(48).cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
                                 }
                             }
                         }
@@ -1063,12 +1707,12 @@ pub mod exports {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {
-                            let l25 = *arg0.add(4).cast::<*mut u8>();
-                            let l26 = *arg0.add(8).cast::<usize>();
-                            let base27 = l25;
-                            let len27 = l26;
-                            for i in 0..len27 {
-                                let base = base27.add(i * 20);
+                            let l33 = *arg0.add(4).cast::<*mut u8>();
+                            let l34 = *arg0.add(8).cast::<usize>();
+                            let base35 = l33;
+                            let len35 = l34;
+                            for i in 0..len35 {
+                                let base = base35.add(i * 24);
                                 {
                                     let l1 = i32::from(*base.add(0).cast::<u8>());
                                     match l1 {
@@ -1081,59 +1725,100 @@ pub mod exports {
                                             let l4 = *base.add(4).cast::<*mut u8>();
                                             let l5 = *base.add(8).cast::<usize>();
                                             _rt::cabi_dealloc(l4, l5, 1);
-                                            let l6 = *base.add(12).cast::<*mut u8>();
-                                            let l7 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l6, l7, 1);
                                         }
                                         2 => {
-                                            let l8 = *base.add(4).cast::<*mut u8>();
-                                            let l9 = *base.add(8).cast::<usize>();
+                                            let l6 = *base.add(4).cast::<*mut u8>();
+                                            let l7 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l6, l7, 1);
+                                            let l8 = *base.add(12).cast::<*mut u8>();
+                                            let l9 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l8, l9, 1);
-                                            let l10 = *base.add(12).cast::<*mut u8>();
-                                            let l11 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l10, l11, 1);
                                         }
                                         3 => {
-                                            let l12 = *base.add(4).cast::<*mut u8>();
-                                            let l13 = *base.add(8).cast::<usize>();
+                                            let l10 = *base.add(4).cast::<*mut u8>();
+                                            let l11 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l10, l11, 1);
+                                            let l12 = *base.add(12).cast::<*mut u8>();
+                                            let l13 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l12, l13, 1);
-                                            let l14 = *base.add(12).cast::<*mut u8>();
-                                            let l15 = *base.add(16).cast::<usize>();
-                                            _rt::cabi_dealloc(l14, l15, 1);
                                         }
                                         4 => {
-                                            let l16 = *base.add(4).cast::<*mut u8>();
-                                            let l17 = *base.add(8).cast::<usize>();
+                                            let l14 = *base.add(4).cast::<*mut u8>();
+                                            let l15 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l14, l15, 1);
+                                            let l16 = *base.add(12).cast::<*mut u8>();
+                                            let l17 = *base.add(16).cast::<usize>();
                                             _rt::cabi_dealloc(l16, l17, 1);
-                                            let l18 = *base.add(12).cast::<*mut u8>();
-                                            let l19 = *base.add(16).cast::<usize>();
-                                            let base20 = l18;
-                                            let len20 = l19;
-                                            _rt::cabi_dealloc(base20, len20 * 1, 1);
                                         }
                                         5 => {
-                                            let l21 = *base.add(4).cast::<*mut u8>();
-                                            let l22 = *base.add(8).cast::<usize>();
-                                            _rt::cabi_dealloc(l21, l22, 1);
+                                            let l18 = *base.add(4).cast::<*mut u8>();
+                                            let l19 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l18, l19, 1);
+                                            let l20 = *base.add(12).cast::<*mut u8>();
+                                            let l21 = *base.add(16).cast::<usize>();
+                                            _rt::cabi_dealloc(l20, l21, 1);
+                                        }
+                                        6 => {
+                                            let l22 = *base.add(4).cast::<*mut u8>();
+                                            let l23 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l22, l23, 1);
+                                            let l24 = *base.add(12).cast::<*mut u8>();
+                                            let l25 = *base.add(16).cast::<usize>();
+                                            let base26 = l24;
+                                            let len26 = l25;
+                                            _rt::cabi_dealloc(base26, len26 * 1, 1);
+                                        }
+                                        7 => {
+                                            let l27 = *base.add(4).cast::<*mut u8>();
+                                            let l28 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l27, l28, 1);
+                                        }
+                                        8 => {
+                                            let l29 = *base.add(4).cast::<*mut u8>();
+                                            let l30 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l29, l30, 1);
                                         }
                                         _ => {
-                                            let l23 = *base.add(4).cast::<*mut u8>();
-                                            let l24 = *base.add(8).cast::<usize>();
-                                            _rt::cabi_dealloc(l23, l24, 1);
+                                            let l31 = *base.add(4).cast::<*mut u8>();
+                                            let l32 = *base.add(8).cast::<usize>();
+                                            _rt::cabi_dealloc(l31, l32, 1);
                                         }
                                     }
                                 }
                             }
-                            _rt::cabi_dealloc(base27, len27 * 20, 4);
+                            _rt::cabi_dealloc(base35, len35 * 24, 4);
                         }
                         _ => {
-                            let l28 = i32::from(*arg0.add(4).cast::<u8>());
-                            match l28 {
+                            let l36 = i32::from(*arg0.add(4).cast::<u8>());
+                            match l36 {
                                 0 => (),
+                                1 => {
+                                    let l37 = *arg0.add(8).cast::<*mut u8>();
+                                    let l38 = *arg0.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l37, l38, 1);
+                                }
                                 _ => {
-                                    let l29 = *arg0.add(8).cast::<*mut u8>();
-                                    let l30 = *arg0.add(12).cast::<usize>();
-                                    _rt::cabi_dealloc(l29, l30, 1);
+                                    let l39 = *arg0.add(12).cast::<*mut u8>();
+                                    let l40 = *arg0.add(16).cast::<usize>();
+                                    _rt::cabi_dealloc(l39, l40, 1);
+                                    let l41 = i32::from(*arg0.add(20).cast::<u8>());
+                                    match l41 {
+                                        0 => (),
+                                        _ => {
+                                            let l42 = *arg0.add(24).cast::<*mut u8>();
+                                            let l43 = *arg0.add(28).cast::<usize>();
+                                            _rt::cabi_dealloc(l42, l43, 1);
+                                        }
+                                    }
+                                    let l44 = i32::from(*arg0.add(48).cast::<u8>());
+                                    match l44 {
+                                        0 => (),
+                                        _ => {
+                                            let l45 = *arg0.add(52).cast::<*mut u8>();
+                                            let l46 = *arg0.add(56).cast::<usize>();
+                                            _rt::cabi_dealloc(l45, l46, 1);
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -1161,7 +1846,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-new]edit"]
@@ -1185,7 +1870,7 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.1"
+                                wasm_import_module = "[export]fermyon:spin-template/template@0.0.2"
                             )]
                             extern "C" {
                                 #[link_name = "[resource-rep]edit"]
@@ -1203,73 +1888,938 @@ pub mod exports {
                 }
                 #[doc(hidden)]
 
-                macro_rules! __export_fermyon_spin_template_template_0_0_1_cabi{
-      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
-
-        #[export_name = "fermyon:spin-template/template@0.0.1#[method]edit.apply"]
-        unsafe extern "C" fn export_method_edit_apply(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,) -> *mut u8 {
-          $($path_to_types)*::_export_method_edit_apply_cabi::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0, arg1, arg2, arg3)
-        }
-        #[export_name = "cabi_post_fermyon:spin-template/template@0.0.1#[method]edit.apply"]
-        unsafe extern "C" fn _post_return_method_edit_apply(arg0: *mut u8,) {
-          $($path_to_types)*::__post_return_method_edit_apply::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0)
-        }
-        #[export_name = "fermyon:spin-template/template@0.0.1#run"]
-        unsafe extern "C" fn export_run(arg0: i32,arg1: i32,arg2: *mut u8,arg3: usize,arg4: i32,) -> *mut u8 {
-          $($path_to_types)*::_export_run_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4)
-        }
-        #[export_name = "cabi_post_fermyon:spin-template/template@0.0.1#run"]
-        unsafe extern "C" fn _post_return_run(arg0: *mut u8,) {
-          $($path_to_types)*::__post_return_run::<$ty>(arg0)
-        }
+                macro_rules! __export_fermyon_spin_template_template_0_0_2_cabi{
+          ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
 
-        const _: () = {
-          #[doc(hidden)]
-          #[export_name = "fermyon:spin-template/template@0.0.1#[dtor]edit"]
-          #[allow(non_snake_case)]
-          unsafe extern "C" fn dtor(rep: *mut u8) {
-            $($path_to_types)*::Edit::dtor::<
-            <$ty as $($path_to_types)*::Guest>::Edit
-            >(rep)
-          }
-        };
+            #[export_name = "fermyon:spin-template/template@0.0.2#[method]edit.apply"]
+            unsafe extern "C" fn export_method_edit_apply(arg0: *mut u8,arg1: *mut u8,arg2: usize,arg3: i32,) -> *mut u8 {
+              $($path_to_types)*::_export_method_edit_apply_cabi::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0, arg1, arg2, arg3)
+            }
+            #[export_name = "cabi_post_fermyon:spin-template/template@0.0.2#[method]edit.apply"]
+            unsafe extern "C" fn _post_return_method_edit_apply(arg0: *mut u8,) {
+              $($path_to_types)*::__post_return_method_edit_apply::<<$ty as $($path_to_types)*::Guest>::Edit>(arg0)
+            }
+            #[export_name = "fermyon:spin-template/template@0.0.2#run"]
+            unsafe extern "C" fn export_run(arg0: i32,arg1: i32,arg2: *mut u8,arg3: usize,arg4: i32,) -> *mut u8 {
+              $($path_to_types)*::_export_run_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4)
+            }
+            #[export_name = "cabi_post_fermyon:spin-template/template@0.0.2#run"]
+            unsafe extern "C" fn _post_return_run(arg0: *mut u8,) {
+              $($path_to_types)*::__post_return_run::<$ty>(arg0)
+            }
 
-      };);
-    }
+            const _: () = {
+              #[doc(hidden)]
+              #[export_name = "fermyon:spin-template/template@0.0.2#[dtor]edit"]
+              #[allow(non_snake_case)]
+              unsafe extern "C" fn dtor(rep: *mut u8) {
+                $($path_to_types)*::Edit::dtor::<
+                <$ty as $($path_to_types)*::Guest>::Edit
+                >(rep)
+              }
+            };
+
+          };);
+        }
                 #[doc(hidden)]
-                pub(crate) use __export_fermyon_spin_template_template_0_0_1_cabi;
+                pub(crate) use __export_fermyon_spin_template_template_0_0_2_cabi;
                 #[repr(align(4))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 16]);
-                static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 16]);
+                struct _RetArea([::core::mem::MaybeUninit<u8>; 60]);
+                static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 60]);
             }
         }
-    }
-}
-mod _rt {
-    pub use alloc_crate::string::String;
-
-    use core::fmt;
-    use core::marker;
-    use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
-
-    /// A type which represents a component model resource, either imported or
-    /// exported into this component.
-    ///
-    /// This is a low-level wrapper which handles the lifetime of the resource
-    /// (namely this has a destructor). The `T` provided defines the component model
-    /// intrinsics that this wrapper uses.
-    ///
-    /// One of the chief purposes of this type is to provide `Deref` implementations
-    /// to access the underlying data when it is owned.
-    ///
-    /// This type is primarily used in generated code for exported and imported
-    /// resources.
-    #[repr(transparent)]
-    pub struct Resource<T: WasmResource> {
-        // NB: This would ideally be `u32` but it is not. The fact that this has
-        // interior mutability is not exposed in the API of this type except for the
-        // `take_handle` method which is supposed to in theory be private.
-        //
+        #[allow(dead_code)]
+        pub mod spin_template_filter {
+            #[allow(dead_code, clippy::all)]
+            pub mod types {
+                #[used]
+                #[doc(hidden)]
+                #[cfg(target_arch = "wasm32")]
+                static __FORCE_SECTION_REF: fn() =
+                    super::super::super::super::__link_custom_section_describing_imports;
+                use super::super::super::super::_rt;
+                /// Mirrors the Liquid data model. WIT doesn't allow recursive types, so a value is a
+                /// tree flattened into a list of nodes: the first node is the root, and arrays and
+                /// objects refer to their members by index. Members always come after the array or
+                /// object that contains them.
+                #[derive(Clone)]
+                pub enum ValueNode {
+                    Nil,
+                    Bool(bool),
+                    Int(i64),
+                    Float(f64),
+                    String(_rt::String),
+                    Array(_rt::Vec<u32>),
+                    Object(_rt::Vec<(_rt::String, u32)>),
+                }
+                impl ::core::fmt::Debug for ValueNode {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        match self {
+                            ValueNode::Nil => f.debug_tuple("ValueNode::Nil").finish(),
+                            ValueNode::Bool(e) => {
+                                f.debug_tuple("ValueNode::Bool").field(e).finish()
+                            }
+                            ValueNode::Int(e) => f.debug_tuple("ValueNode::Int").field(e).finish(),
+                            ValueNode::Float(e) => {
+                                f.debug_tuple("ValueNode::Float").field(e).finish()
+                            }
+                            ValueNode::String(e) => {
+                                f.debug_tuple("ValueNode::String").field(e).finish()
+                            }
+                            ValueNode::Array(e) => {
+                                f.debug_tuple("ValueNode::Array").field(e).finish()
+                            }
+                            ValueNode::Object(e) => {
+                                f.debug_tuple("ValueNode::Object").field(e).finish()
+                            }
+                        }
+                    }
+                }
+                pub type Value = _rt::Vec<ValueNode>;
+                #[derive(Clone)]
+                pub struct Parameter {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    pub required: bool,
+                }
+                impl ::core::fmt::Debug for Parameter {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("Parameter")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("required", &self.required)
+                            .finish()
+                    }
+                }
+                /// Documents the filter and the arguments it accepts, e.g. `{{ name | truncate_id: 20, sep: "-" }}`
+                /// has one positional parameter and a keyword parameter called `sep`.  `name` identifies the
+                /// filter within the component, and is the name it is used under unless the template chooses
+                /// a name or prefix of its own in `spin-template.toml`.
+                #[derive(Clone)]
+                pub struct FilterDescription {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    pub positional: _rt::Vec<Parameter>,
+                    pub keyword: _rt::Vec<Parameter>,
+                }
+                impl ::core::fmt::Debug for FilterDescription {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("FilterDescription")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("positional", &self.positional)
+                            .field("keyword", &self.keyword)
+                            .finish()
+                    }
+                }
+                /// Optional positional arguments that were not supplied are omitted from the end of the
+                /// list; keyword arguments that were not supplied are omitted altogether.
+                #[derive(Clone)]
+                pub struct Arguments {
+                    pub positional: _rt::Vec<Value>,
+                    pub keyword: _rt::Vec<(_rt::String, Value)>,
+                }
+                impl ::core::fmt::Debug for Arguments {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        f.debug_struct("Arguments")
+                            .field("positional", &self.positional)
+                            .field("keyword", &self.keyword)
+                            .finish()
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_describe_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")]
+                    _rt::run_ctors_once();
+                    let result0 = T::describe();
+                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let vec13 = result0;
+                    let len13 = vec13.len();
+                    let layout13 =
+                        _rt::alloc::Layout::from_size_align_unchecked(vec13.len() * 32, 4);
+                    let result13 = if layout13.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout13).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout13);
+                        }
+                        ptr
+                    } else {
+                        {
+                            ::core::ptr::null_mut()
+                        }
+                    };
+                    for (i, e) in vec13.into_iter().enumerate() {
+                        let base = result13.add(i * 32);
+                        {
+                            let FilterDescription {
+                                name: name2,
+                                description: description2,
+                                positional: positional2,
+                                keyword: keyword2,
+                            } = e;
+                            let vec3 = (name2.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *base.add(4).cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = (description2.into_bytes()).into_boxed_slice();
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            ::core::mem::forget(vec4);
+                            *base.add(12).cast::<usize>() = len4;
+                            *base.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+                            let vec8 = positional2;
+                            let len8 = vec8.len();
+                            let layout8 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec8.len() * 20, 4);
+                            let result8 = if layout8.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout8);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec8.into_iter().enumerate() {
+                                let base = result8.add(i * 20);
+                                {
+                                    let Parameter {
+                                        name: name5,
+                                        description: description5,
+                                        required: required5,
+                                    } = e;
+                                    let vec6 = (name5.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *base.add(4).cast::<usize>() = len6;
+                                    *base.add(0).cast::<*mut u8>() = ptr6.cast_mut();
+                                    let vec7 = (description5.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *base.add(12).cast::<usize>() = len7;
+                                    *base.add(8).cast::<*mut u8>() = ptr7.cast_mut();
+                                    *base.add(16).cast::<u8>() = (match required5 {
+                                        true => 1,
+                                        false => 0,
+                                    })
+                                        as u8;
+                                }
+                            }
+                            *base.add(20).cast::<usize>() = len8;
+                            *base.add(16).cast::<*mut u8>() = result8;
+                            let vec12 = keyword2;
+                            let len12 = vec12.len();
+                            let layout12 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec12.len() * 20, 4);
+                            let result12 = if layout12.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout12);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec12.into_iter().enumerate() {
+                                let base = result12.add(i * 20);
+                                {
+                                    let Parameter {
+                                        name: name9,
+                                        description: description9,
+                                        required: required9,
+                                    } = e;
+                                    let vec10 = (name9.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *base.add(4).cast::<usize>() = len10;
+                                    *base.add(0).cast::<*mut u8>() = ptr10.cast_mut();
+                                    let vec11 = (description9.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *base.add(12).cast::<usize>() = len11;
+                                    *base.add(8).cast::<*mut u8>() = ptr11.cast_mut();
+                                    *base.add(16).cast::<u8>() = (match required9 {
+                                        true => 1,
+                                        false => 0,
+                                    })
+                                        as u8;
+                                }
+                            }
+                            *base.add(28).cast::<usize>() = len12;
+                            *base.add(24).cast::<*mut u8>() = result12;
+                        }
+                    }
+                    *ptr1.add(4).cast::<usize>() = len13;
+                    *ptr1.add(0).cast::<*mut u8>() = result13;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_describe<T: Guest>(arg0: *mut u8) {
+                    let l18 = *arg0.add(0).cast::<*mut u8>();
+                    let l19 = *arg0.add(4).cast::<usize>();
+                    let base20 = l18;
+                    let len20 = l19;
+                    for i in 0..len20 {
+                        let base = base20.add(i * 32);
+                        {
+                            let l0 = *base.add(0).cast::<*mut u8>();
+                            let l1 = *base.add(4).cast::<usize>();
+                            _rt::cabi_dealloc(l0, l1, 1);
+                            let l2 = *base.add(8).cast::<*mut u8>();
+                            let l3 = *base.add(12).cast::<usize>();
+                            _rt::cabi_dealloc(l2, l3, 1);
+                            let l8 = *base.add(16).cast::<*mut u8>();
+                            let l9 = *base.add(20).cast::<usize>();
+                            let base10 = l8;
+                            let len10 = l9;
+                            for i in 0..len10 {
+                                let base = base10.add(i * 20);
+                                {
+                                    let l4 = *base.add(0).cast::<*mut u8>();
+                                    let l5 = *base.add(4).cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                    let l6 = *base.add(8).cast::<*mut u8>();
+                                    let l7 = *base.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(base10, len10 * 20, 4);
+                            let l15 = *base.add(24).cast::<*mut u8>();
+                            let l16 = *base.add(28).cast::<usize>();
+                            let base17 = l15;
+                            let len17 = l16;
+                            for i in 0..len17 {
+                                let base = base17.add(i * 20);
+                                {
+                                    let l11 = *base.add(0).cast::<*mut u8>();
+                                    let l12 = *base.add(4).cast::<usize>();
+                                    _rt::cabi_dealloc(l11, l12, 1);
+                                    let l13 = *base.add(8).cast::<*mut u8>();
+                                    let l14 = *base.add(12).cast::<usize>();
+                                    _rt::cabi_dealloc(l13, l14, 1);
+                                }
+                            }
+                            _rt::cabi_dealloc(base17, len17 * 20, 4);
+                        }
+                    }
+                    _rt::cabi_dealloc(base20, len20 * 32, 4);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_exec_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                    arg4: *mut u8,
+                    arg5: usize,
+                    arg6: *mut u8,
+                    arg7: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")]
+                    _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base19 = arg2;
+                    let len19 = arg3;
+                    let mut result19 = _rt::Vec::with_capacity(len19);
+                    for i in 0..len19 {
+                        let base = base19.add(i * 16);
+                        let e19 = {
+                            let l1 = i32::from(*base.add(0).cast::<u8>());
+                            let v18 = match l1 {
+                                0 => ValueNode::Nil,
+                                1 => {
+                                    let e18 = {
+                                        let l2 = i32::from(*base.add(8).cast::<u8>());
+
+                                        _rt::bool_lift(l2 as u8)
+                                    };
+                                    ValueNode::Bool(e18)
+                                }
+                                2 => {
+                                    let e18 = {
+                                        let l3 = *base.add(8).cast::<i64>();
+
+                                        l3
+                                    };
+                                    ValueNode::Int(e18)
+                                }
+                                3 => {
+                                    let e18 = {
+                                        let l4 = *base.add(8).cast::<f64>();
+
+                                        l4
+                                    };
+                                    ValueNode::Float(e18)
+                                }
+                                4 => {
+                                    let e18 = {
+                                        let l5 = *base.add(8).cast::<*mut u8>();
+                                        let l6 = *base.add(12).cast::<usize>();
+                                        let len7 = l6;
+                                        let bytes7 =
+                                            _rt::Vec::from_raw_parts(l5.cast(), len7, len7);
+
+                                        _rt::string_lift(bytes7)
+                                    };
+                                    ValueNode::String(e18)
+                                }
+                                5 => {
+                                    let e18 = {
+                                        let l8 = *base.add(8).cast::<*mut u8>();
+                                        let l9 = *base.add(12).cast::<usize>();
+                                        let len10 = l9;
+
+                                        _rt::Vec::from_raw_parts(l8.cast(), len10, len10)
+                                    };
+                                    ValueNode::Array(e18)
+                                }
+                                n => {
+                                    debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                    let e18 = {
+                                        let l11 = *base.add(8).cast::<*mut u8>();
+                                        let l12 = *base.add(12).cast::<usize>();
+                                        let base17 = l11;
+                                        let len17 = l12;
+                                        let mut result17 = _rt::Vec::with_capacity(len17);
+                                        for i in 0..len17 {
+                                            let base = base17.add(i * 12);
+                                            let e17 = {
+                                                let l13 = *base.add(0).cast::<*mut u8>();
+                                                let l14 = *base.add(4).cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                let l16 = *base.add(8).cast::<i32>();
+
+                                                (_rt::string_lift(bytes15), l16 as u32)
+                                            };
+                                            result17.push(e17);
+                                        }
+                                        _rt::cabi_dealloc(base17, len17 * 12, 4);
+
+                                        result17
+                                    };
+                                    ValueNode::Object(e18)
+                                }
+                            };
+
+                            v18
+                        };
+                        result19.push(e19);
+                    }
+                    _rt::cabi_dealloc(base19, len19 * 16, 8);
+                    let base41 = arg4;
+                    let len41 = arg5;
+                    let mut result41 = _rt::Vec::with_capacity(len41);
+                    for i in 0..len41 {
+                        let base = base41.add(i * 8);
+                        let e41 = {
+                            let l20 = *base.add(0).cast::<*mut u8>();
+                            let l21 = *base.add(4).cast::<usize>();
+                            let base40 = l20;
+                            let len40 = l21;
+                            let mut result40 = _rt::Vec::with_capacity(len40);
+                            for i in 0..len40 {
+                                let base = base40.add(i * 16);
+                                let e40 = {
+                                    let l22 = i32::from(*base.add(0).cast::<u8>());
+                                    let v39 = match l22 {
+                                        0 => ValueNode::Nil,
+                                        1 => {
+                                            let e39 = {
+                                                let l23 = i32::from(*base.add(8).cast::<u8>());
+
+                                                _rt::bool_lift(l23 as u8)
+                                            };
+                                            ValueNode::Bool(e39)
+                                        }
+                                        2 => {
+                                            let e39 = {
+                                                let l24 = *base.add(8).cast::<i64>();
+
+                                                l24
+                                            };
+                                            ValueNode::Int(e39)
+                                        }
+                                        3 => {
+                                            let e39 = {
+                                                let l25 = *base.add(8).cast::<f64>();
+
+                                                l25
+                                            };
+                                            ValueNode::Float(e39)
+                                        }
+                                        4 => {
+                                            let e39 = {
+                                                let l26 = *base.add(8).cast::<*mut u8>();
+                                                let l27 = *base.add(12).cast::<usize>();
+                                                let len28 = l27;
+                                                let bytes28 = _rt::Vec::from_raw_parts(
+                                                    l26.cast(),
+                                                    len28,
+                                                    len28,
+                                                );
+
+                                                _rt::string_lift(bytes28)
+                                            };
+                                            ValueNode::String(e39)
+                                        }
+                                        5 => {
+                                            let e39 = {
+                                                let l29 = *base.add(8).cast::<*mut u8>();
+                                                let l30 = *base.add(12).cast::<usize>();
+                                                let len31 = l30;
+
+                                                _rt::Vec::from_raw_parts(l29.cast(), len31, len31)
+                                            };
+                                            ValueNode::Array(e39)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                            let e39 = {
+                                                let l32 = *base.add(8).cast::<*mut u8>();
+                                                let l33 = *base.add(12).cast::<usize>();
+                                                let base38 = l32;
+                                                let len38 = l33;
+                                                let mut result38 = _rt::Vec::with_capacity(len38);
+                                                for i in 0..len38 {
+                                                    let base = base38.add(i * 12);
+                                                    let e38 = {
+                                                        let l34 = *base.add(0).cast::<*mut u8>();
+                                                        let l35 = *base.add(4).cast::<usize>();
+                                                        let len36 = l35;
+                                                        let bytes36 = _rt::Vec::from_raw_parts(
+                                                            l34.cast(),
+                                                            len36,
+                                                            len36,
+                                                        );
+                                                        let l37 = *base.add(8).cast::<i32>();
+
+                                                        (_rt::string_lift(bytes36), l37 as u32)
+                                                    };
+                                                    result38.push(e38);
+                                                }
+                                                _rt::cabi_dealloc(base38, len38 * 12, 4);
+
+                                                result38
+                                            };
+                                            ValueNode::Object(e39)
+                                        }
+                                    };
+
+                                    v39
+                                };
+                                result40.push(e40);
+                            }
+                            _rt::cabi_dealloc(base40, len40 * 16, 8);
+
+                            result40
+                        };
+                        result41.push(e41);
+                    }
+                    _rt::cabi_dealloc(base41, len41 * 8, 4);
+                    let base66 = arg6;
+                    let len66 = arg7;
+                    let mut result66 = _rt::Vec::with_capacity(len66);
+                    for i in 0..len66 {
+                        let base = base66.add(i * 16);
+                        let e66 = {
+                            let l42 = *base.add(0).cast::<*mut u8>();
+                            let l43 = *base.add(4).cast::<usize>();
+                            let len44 = l43;
+                            let bytes44 = _rt::Vec::from_raw_parts(l42.cast(), len44, len44);
+                            let l45 = *base.add(8).cast::<*mut u8>();
+                            let l46 = *base.add(12).cast::<usize>();
+                            let base65 = l45;
+                            let len65 = l46;
+                            let mut result65 = _rt::Vec::with_capacity(len65);
+                            for i in 0..len65 {
+                                let base = base65.add(i * 16);
+                                let e65 = {
+                                    let l47 = i32::from(*base.add(0).cast::<u8>());
+                                    let v64 = match l47 {
+                                        0 => ValueNode::Nil,
+                                        1 => {
+                                            let e64 = {
+                                                let l48 = i32::from(*base.add(8).cast::<u8>());
+
+                                                _rt::bool_lift(l48 as u8)
+                                            };
+                                            ValueNode::Bool(e64)
+                                        }
+                                        2 => {
+                                            let e64 = {
+                                                let l49 = *base.add(8).cast::<i64>();
+
+                                                l49
+                                            };
+                                            ValueNode::Int(e64)
+                                        }
+                                        3 => {
+                                            let e64 = {
+                                                let l50 = *base.add(8).cast::<f64>();
+
+                                                l50
+                                            };
+                                            ValueNode::Float(e64)
+                                        }
+                                        4 => {
+                                            let e64 = {
+                                                let l51 = *base.add(8).cast::<*mut u8>();
+                                                let l52 = *base.add(12).cast::<usize>();
+                                                let len53 = l52;
+                                                let bytes53 = _rt::Vec::from_raw_parts(
+                                                    l51.cast(),
+                                                    len53,
+                                                    len53,
+                                                );
+
+                                                _rt::string_lift(bytes53)
+                                            };
+                                            ValueNode::String(e64)
+                                        }
+                                        5 => {
+                                            let e64 = {
+                                                let l54 = *base.add(8).cast::<*mut u8>();
+                                                let l55 = *base.add(12).cast::<usize>();
+                                                let len56 = l55;
+
+                                                _rt::Vec::from_raw_parts(l54.cast(), len56, len56)
+                                            };
+                                            ValueNode::Array(e64)
+                                        }
+                                        n => {
+                                            debug_assert_eq!(n, 6, "invalid enum discriminant");
+                                            let e64 = {
+                                                let l57 = *base.add(8).cast::<*mut u8>();
+                                                let l58 = *base.add(12).cast::<usize>();
+                                                let base63 = l57;
+                                                let len63 = l58;
+                                                let mut result63 = _rt::Vec::with_capacity(len63);
+                                                for i in 0..len63 {
+                                                    let base = base63.add(i * 12);
+                                                    let e63 = {
+                                                        let l59 = *base.add(0).cast::<*mut u8>();
+                                                        let l60 = *base.add(4).cast::<usize>();
+                                                        let len61 = l60;
+                                                        let bytes61 = _rt::Vec::from_raw_parts(
+                                                            l59.cast(),
+                                                            len61,
+                                                            len61,
+                                                        );
+                                                        let l62 = *base.add(8).cast::<i32>();
+
+                                                        (_rt::string_lift(bytes61), l62 as u32)
+                                                    };
+                                                    result63.push(e63);
+                                                }
+                                                _rt::cabi_dealloc(base63, len63 * 12, 4);
+
+                                                result63
+                                            };
+                                            ValueNode::Object(e64)
+                                        }
+                                    };
+
+                                    v64
+                                };
+                                result65.push(e65);
+                            }
+                            _rt::cabi_dealloc(base65, len65 * 16, 8);
+
+                            (_rt::string_lift(bytes44), result65)
+                        };
+                        result66.push(e66);
+                    }
+                    _rt::cabi_dealloc(base66, len66 * 16, 4);
+                    let result67 = T::exec(
+                        _rt::string_lift(bytes0),
+                        result19,
+                        Arguments {
+                            positional: result41,
+                            keyword: result66,
+                        },
+                    );
+                    let ptr68 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    match result67 {
+                        Ok(e) => {
+                            *ptr68.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec74 = e;
+                            let len74 = vec74.len();
+                            let layout74 =
+                                _rt::alloc::Layout::from_size_align_unchecked(vec74.len() * 16, 8);
+                            let result74 = if layout74.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout74).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout74);
+                                }
+                                ptr
+                            } else {
+                                {
+                                    ::core::ptr::null_mut()
+                                }
+                            };
+                            for (i, e) in vec74.into_iter().enumerate() {
+                                let base = result74.add(i * 16);
+                                {
+                                    match e {
+                                        ValueNode::Nil => {
+                                            *base.add(0).cast::<u8>() = (0i32) as u8;
+                                        }
+                                        ValueNode::Bool(e) => {
+                                            *base.add(0).cast::<u8>() = (1i32) as u8;
+                                            *base.add(8).cast::<u8>() = (match e {
+                                                true => 1,
+                                                false => 0,
+                                            })
+                                                as u8;
+                                        }
+                                        ValueNode::Int(e) => {
+                                            *base.add(0).cast::<u8>() = (2i32) as u8;
+                                            *base.add(8).cast::<i64>() = _rt::as_i64(e);
+                                        }
+                                        ValueNode::Float(e) => {
+                                            *base.add(0).cast::<u8>() = (3i32) as u8;
+                                            *base.add(8).cast::<f64>() = _rt::as_f64(e);
+                                        }
+                                        ValueNode::String(e) => {
+                                            *base.add(0).cast::<u8>() = (4i32) as u8;
+                                            let vec69 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr69 = vec69.as_ptr().cast::<u8>();
+                                            let len69 = vec69.len();
+                                            ::core::mem::forget(vec69);
+                                            *base.add(12).cast::<usize>() = len69;
+                                            *base.add(8).cast::<*mut u8>() = ptr69.cast_mut();
+                                        }
+                                        ValueNode::Array(e) => {
+                                            *base.add(0).cast::<u8>() = (5i32) as u8;
+                                            let vec70 = (e).into_boxed_slice();
+                                            let ptr70 = vec70.as_ptr().cast::<u8>();
+                                            let len70 = vec70.len();
+                                            ::core::mem::forget(vec70);
+                                            *base.add(12).cast::<usize>() = len70;
+                                            *base.add(8).cast::<*mut u8>() = ptr70.cast_mut();
+                                        }
+                                        ValueNode::Object(e) => {
+                                            *base.add(0).cast::<u8>() = (6i32) as u8;
+                                            let vec73 = e;
+                                            let len73 = vec73.len();
+                                            let layout73 =
+                                                _rt::alloc::Layout::from_size_align_unchecked(
+                                                    vec73.len() * 12,
+                                                    4,
+                                                );
+                                            let result73 = if layout73.size() != 0 {
+                                                let ptr = _rt::alloc::alloc(layout73).cast::<u8>();
+                                                if ptr.is_null() {
+                                                    _rt::alloc::handle_alloc_error(layout73);
+                                                }
+                                                ptr
+                                            } else {
+                                                {
+                                                    ::core::ptr::null_mut()
+                                                }
+                                            };
+                                            for (i, e) in vec73.into_iter().enumerate() {
+                                                let base = result73.add(i * 12);
+                                                {
+                                                    let (t71_0, t71_1) = e;
+                                                    let vec72 =
+                                                        (t71_0.into_bytes()).into_boxed_slice();
+                                                    let ptr72 = vec72.as_ptr().cast::<u8>();
+                                                    let len72 = vec72.len();
+                                                    ::core::mem::forget(vec72);
+                                                    *base.add(4).cast::<usize>() = len72;
+                                                    *base.add(0).cast::<*mut u8>() =
+                                                        ptr72.cast_mut();
+                                                    *base.add(8).cast::<i32>() = _rt::as_i32(t71_1);
+                                                }
+                                            }
+                                            *base.add(12).cast::<usize>() = len73;
+                                            *base.add(8).cast::<*mut u8>() = result73;
+                                        }
+                                    }
+                                }
+                            }
+                            *ptr68.add(8).cast::<usize>() = len74;
+                            *ptr68.add(4).cast::<*mut u8>() = result74;
+                        }
+                        Err(e) => {
+                            *ptr68.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec75 = (e.into_bytes()).into_boxed_slice();
+                            let ptr75 = vec75.as_ptr().cast::<u8>();
+                            let len75 = vec75.len();
+                            ::core::mem::forget(vec75);
+                            *ptr68.add(8).cast::<usize>() = len75;
+                            *ptr68.add(4).cast::<*mut u8>() = ptr75.cast_mut();
+                        }
+                    };
+                    ptr68
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_exec<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l12 = *arg0.add(4).cast::<*mut u8>();
+                            let l13 = *arg0.add(8).cast::<usize>();
+                            let base14 = l12;
+                            let len14 = l13;
+                            for i in 0..len14 {
+                                let base = base14.add(i * 16);
+                                {
+                                    let l1 = i32::from(*base.add(0).cast::<u8>());
+                                    match l1 {
+                                        0 => (),
+                                        1 => (),
+                                        2 => (),
+                                        3 => (),
+                                        4 => {
+                                            let l2 = *base.add(8).cast::<*mut u8>();
+                                            let l3 = *base.add(12).cast::<usize>();
+                                            _rt::cabi_dealloc(l2, l3, 1);
+                                        }
+                                        5 => {
+                                            let l4 = *base.add(8).cast::<*mut u8>();
+                                            let l5 = *base.add(12).cast::<usize>();
+                                            let base6 = l4;
+                                            let len6 = l5;
+                                            _rt::cabi_dealloc(base6, len6 * 4, 4);
+                                        }
+                                        _ => {
+                                            let l9 = *base.add(8).cast::<*mut u8>();
+                                            let l10 = *base.add(12).cast::<usize>();
+                                            let base11 = l9;
+                                            let len11 = l10;
+                                            for i in 0..len11 {
+                                                let base = base11.add(i * 12);
+                                                {
+                                                    let l7 = *base.add(0).cast::<*mut u8>();
+                                                    let l8 = *base.add(4).cast::<usize>();
+                                                    _rt::cabi_dealloc(l7, l8, 1);
+                                                }
+                                            }
+                                            _rt::cabi_dealloc(base11, len11 * 12, 4);
+                                        }
+                                    }
+                                }
+                            }
+                            _rt::cabi_dealloc(base14, len14 * 16, 8);
+                        }
+                        _ => {
+                            let l15 = *arg0.add(4).cast::<*mut u8>();
+                            let l16 = *arg0.add(8).cast::<usize>();
+                            _rt::cabi_dealloc(l15, l16, 1);
+                        }
+                    }
+                }
+                pub trait Guest {
+                    /// A component can provide any number of filters. `exec` is passed the `name` from the
+                    /// description of the filter being run.
+                    fn describe() -> _rt::Vec<FilterDescription>;
+                    fn exec(
+                        name: _rt::String,
+                        input: Value,
+                        args: Arguments,
+                    ) -> Result<Value, _rt::String>;
+                }
+                #[doc(hidden)]
+
+                macro_rules! __export_fermyon_spin_template_filter_types_0_0_2_cabi{
+      ($ty:ident with_types_in $($path_to_types:tt)*) => (const _: () = {
+
+        #[export_name = "fermyon:spin-template-filter/types@0.0.2#describe"]
+        unsafe extern "C" fn export_describe() -> *mut u8 {
+          $($path_to_types)*::_export_describe_cabi::<$ty>()
+        }
+        #[export_name = "cabi_post_fermyon:spin-template-filter/types@0.0.2#describe"]
+        unsafe extern "C" fn _post_return_describe(arg0: *mut u8,) {
+          $($path_to_types)*::__post_return_describe::<$ty>(arg0)
+        }
+        #[export_name = "fermyon:spin-template-filter/types@0.0.2#exec"]
+        unsafe extern "C" fn export_exec(arg0: *mut u8,arg1: usize,arg2: *mut u8,arg3: usize,arg4: *mut u8,arg5: usize,arg6: *mut u8,arg7: usize,) -> *mut u8 {
+          $($path_to_types)*::_export_exec_cabi::<$ty>(arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7)
+        }
+        #[export_name = "cabi_post_fermyon:spin-template-filter/types@0.0.2#exec"]
+        unsafe extern "C" fn _post_return_exec(arg0: *mut u8,) {
+          $($path_to_types)*::__post_return_exec::<$ty>(arg0)
+        }
+      };);
+    }
+                #[doc(hidden)]
+                pub(crate) use __export_fermyon_spin_template_filter_types_0_0_2_cabi;
+                #[repr(align(4))]
+                struct _RetArea([::core::mem::MaybeUninit<u8>; 12]);
+                static mut _RET_AREA: _RetArea = _RetArea([::core::mem::MaybeUninit::uninit(); 12]);
+            }
+        }
+    }
+}
+mod _rt {
+    pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            core::hint::unreachable_unchecked()
+        }
+    }
+    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
+        if size == 0 {
+            return;
+        }
+        let layout = alloc::Layout::from_size_align_unchecked(size, align);
+        alloc::dealloc(ptr as *mut u8, layout);
+    }
+
+    use core::fmt;
+    use core::marker;
+    use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+    /// A type which represents a component model resource, either imported or
+    /// exported into this component.
+    ///
+    /// This is a low-level wrapper which handles the lifetime of the resource
+    /// (namely this has a destructor). The `T` provided defines the component model
+    /// intrinsics that this wrapper uses.
+    ///
+    /// One of the chief purposes of this type is to provide `Deref` implementations
+    /// to access the underlying data when it is owned.
+    ///
+    /// This type is primarily used in generated code for exported and imported
+    /// resources.
+    #[repr(transparent)]
+    pub struct Resource<T: WasmResource> {
+        // NB: This would ideally be `u32` but it is not. The fact that this has
+        // interior mutability is not exposed in the API of this type except for the
+        // `take_handle` method which is supposed to in theory be private.
+        //
         // This represents, almost all the time, a valid handle value. When it's
         // invalid it's stored as `u32::MAX`.
         handle: AtomicU32,
@@ -1342,21 +2892,6 @@ mod _rt {
             }
         }
     }
-    pub use alloc_crate::vec::Vec;
-    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
-        if cfg!(debug_assertions) {
-            String::from_utf8(bytes).unwrap()
-        } else {
-            String::from_utf8_unchecked(bytes)
-        }
-    }
-    pub unsafe fn invalid_enum_discriminant<T>() -> T {
-        if cfg!(debug_assertions) {
-            panic!("invalid enum discriminant")
-        } else {
-            core::hint::unreachable_unchecked()
-        }
-    }
     pub unsafe fn bool_lift(val: u8) -> bool {
         if cfg!(debug_assertions) {
             match val {
@@ -1439,19 +2974,61 @@ mod _rt {
             self as i32
         }
     }
-    pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
-        if size == 0 {
-            return;
-        }
-        let layout = alloc::Layout::from_size_align_unchecked(size, align);
-        alloc::dealloc(ptr as *mut u8, layout);
-    }
-    pub use alloc_crate::boxed::Box;
 
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
     }
+
+    pub fn as_i64<T: AsI64>(t: T) -> i64 {
+        t.as_i64()
+    }
+
+    pub trait AsI64 {
+        fn as_i64(self) -> i64;
+    }
+
+    impl<'a, T: Copy + AsI64> AsI64 for &'a T {
+        fn as_i64(self) -> i64 {
+            (*self).as_i64()
+        }
+    }
+
+    impl AsI64 for i64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+
+    impl AsI64 for u64 {
+        #[inline]
+        fn as_i64(self) -> i64 {
+            self as i64
+        }
+    }
+
+    pub fn as_f64<T: AsF64>(t: T) -> f64 {
+        t.as_f64()
+    }
+
+    pub trait AsF64 {
+        fn as_f64(self) -> f64;
+    }
+
+    impl<'a, T: Copy + AsF64> AsF64 for &'a T {
+        fn as_f64(self) -> f64 {
+            (*self).as_f64()
+        }
+    }
+
+    impl AsF64 for f64 {
+        #[inline]
+        fn as_f64(self) -> f64 {
+            self as f64
+        }
+    }
+    pub use alloc_crate::boxed::Box;
     extern crate alloc as alloc_crate;
 }
 
@@ -1474,48 +3051,67 @@ mod _rt {
 #[allow(unused_macros)]
 #[doc(hidden)]
 
-macro_rules! __export_run_template_impl {
+macro_rules! __export_run_template_with_filters_impl {
   ($ty:ident) => (self::export!($ty with_types_in self););
   ($ty:ident with_types_in $($path_to_types_root:tt)*) => (
-  $($path_to_types_root)*::exports::fermyon::spin_template::template::__export_fermyon_spin_template_template_0_0_1_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template::template);
+  $($path_to_types_root)*::exports::fermyon::spin_template_filter::types::__export_fermyon_spin_template_filter_types_0_0_2_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template_filter::types);
+  $($path_to_types_root)*::exports::fermyon::spin_template::template::__export_fermyon_spin_template_template_0_0_2_cabi!($ty with_types_in $($path_to_types_root)*::exports::fermyon::spin_template::template);
   )
 }
 #[doc(inline)]
-pub(crate) use __export_run_template_impl as export;
+pub(crate) use __export_run_template_with_filters_impl as export;
 
 #[cfg(target_arch = "wasm32")]
-#[link_section = "component-type:wit-bindgen:0.25.0:run-template:encoded world"]
+#[link_section = "component-type:wit-bindgen:0.25.0:run-template-with-filters:encoded world"]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1311] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x9c\x09\x01A\x02\x01\
-A\x09\x01B\x0d\x01q\x02\x06cancel\0\0\x05other\x01s\0\x04\0\x05error\x03\0\0\x01\
-q\x02\x0acreate-new\0\0\x06add-to\x01s\0\x04\0\x0bcreate-mode\x03\0\x02\x01r\x02\
-\x04mode\x03\x12use-default-values\x7f\x04\0\x0brun-options\x03\0\x04\x04\0\x11e\
-xecution-context\x03\x01\x01h\x06\x01@\x03\x04self\x07\x04names\x05values\x01\0\x04\
-\0&[method]execution-context.set-variable\x01\x08\x01j\x01s\x01\x01\x01@\x02\x04\
-self\x07\x08templates\0\x09\x04\0+[method]execution-context.evaluate-template\x01\
-\x0a\x03\x01!fermyon:spin-template/types@0.0.1\x05\0\x02\x03\0\0\x05error\x01B\x1b\
-\x02\x03\x02\x01\x01\x04\0\x05error\x03\0\0\x04\0\x04file\x03\x01\x01i\x02\x01p\x03\
-\x01@\0\0\x04\x04\0\x15[static]file.list-all\x01\x05\x01h\x02\x01@\x01\x04self\x06\
-\0s\x04\0\x11[method]file.path\x01\x07\x01j\x01s\x01\x01\x01@\x01\x04self\x06\0\x08\
-\x04\0\x11[method]file.read\x01\x09\x01p}\x01j\x01\x0a\x01\x01\x01@\x01\x04self\x06\
-\0\x0b\x04\0\x18[method]file.read-binary\x01\x0c\x01ks\x01@\x02\x06prompts\x0dde\
-fault-value\x0d\0s\x04\0\x06prompt\x01\x0e\x01k\x7f\x01@\x02\x06prompts\x0ddefau\
-lt-value\x0f\0\x7f\x04\0\x07confirm\x01\x10\x01ps\x01k}\x01@\x03\x06prompts\x05i\
-tems\x11\x0ddefault-index\x12\0}\x04\0\x06select\x01\x13\x03\x01\x1efermyon:spin\
--template/ui@0.0.1\x05\x02\x02\x03\0\0\x11execution-context\x02\x03\0\0\x0brun-o\
-ptions\x01B\x17\x02\x03\x02\x01\x01\x04\0\x05error\x03\0\0\x02\x03\x02\x01\x03\x04\
-\0\x11execution-context\x03\0\x02\x02\x03\x02\x01\x04\x04\0\x0brun-options\x03\0\
-\x04\x04\0\x04edit\x03\x01\x01o\x02ss\x01p}\x01o\x02s\x08\x01i\x06\x01o\x02s\x0a\
-\x01q\x07\x15copy-file-substituted\x01s\0\x18copy-file-to-substituted\x01\x07\0\x10\
-copy-file-to-raw\x01\x07\0\x0awrite-file\x01\x07\0\x11write-file-binary\x01\x09\0\
-\x0acreate-dir\x01s\0\x09edit-file\x01\x0b\0\x04\0\x06action\x03\0\x0c\x01h\x06\x01\
-h\x03\x01j\x01s\x01\x01\x01@\x03\x04self\x0e\x04texts\x07context\x0f\0\x10\x04\0\
-\x12[method]edit.apply\x01\x11\x01p\x0d\x01j\x01\x12\x01\x01\x01@\x02\x07context\
-\x0f\x07options\x05\0\x13\x04\0\x03run\x01\x14\x04\x01$fermyon:spin-template/tem\
-plate@0.0.1\x05\x05\x04\x01(fermyon:spin-template/run-template@0.0.1\x04\0\x0b\x12\
-\x01\0\x0crun-template\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-co\
-mponent\x070.208.1\x10wit-bindgen-rust\x060.25.0";
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2143] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xcf\x0f\x01A\x02\x01\
+A\x0d\x01B\x06\x01ks\x01@\x01\x04names\0\0\x04\0\x03get\x01\x01\x01ps\x01@\0\0\x02\
+\x04\0\x05names\x01\x03\x03\x01,fermyon:spin-template-filter/variables@0.0.2\x05\
+\0\x01B\x17\x01m\x03\x05parse\x06render\x13undefined-variables\x04\0\x13template\
+-error-kind\x03\0\0\x01ks\x01ky\x01r\x06\x04kind\x01\x07messages\x0bsource-file\x02\
+\x04line\x03\x06column\x03\x07snippet\x02\x04\0\x0etemplate-error\x03\0\x04\x01q\
+\x03\x06cancel\0\0\x05other\x01s\0\x08template\x01\x05\0\x04\0\x05error\x03\0\x06\
+\x01q\x02\x0acreate-new\0\0\x06add-to\x01s\0\x04\0\x0bcreate-mode\x03\0\x08\x01r\
+\x02\x04mode\x09\x12use-default-values\x7f\x04\0\x0brun-options\x03\0\x0a\x04\0\x11\
+execution-context\x03\x01\x01h\x0c\x01@\x03\x04self\x0d\x04names\x05values\x01\0\
+\x04\0&[method]execution-context.set-variable\x01\x0e\x01j\x01s\x01\x07\x01@\x02\
+\x04self\x0d\x08templates\0\x0f\x04\0+[method]execution-context.evaluate-templat\
+e\x01\x10\x01i\x0c\x01@\x01\x04self\x0d\0\x11\x04\0\x1e[method]execution-context\
+.fork\x01\x12\x04\0\"[method]execution-context.snapshot\x01\x12\x03\x01!fermyon:\
+spin-template/types@0.0.2\x05\x01\x02\x03\0\x01\x05error\x01B\x1b\x02\x03\x02\x01\
+\x02\x04\0\x05error\x03\0\0\x04\0\x04file\x03\x01\x01i\x02\x01p\x03\x01@\0\0\x04\
+\x04\0\x15[static]file.list-all\x01\x05\x01h\x02\x01@\x01\x04self\x06\0s\x04\0\x11\
+[method]file.path\x01\x07\x01j\x01s\x01\x01\x01@\x01\x04self\x06\0\x08\x04\0\x11\
+[method]file.read\x01\x09\x01p}\x01j\x01\x0a\x01\x01\x01@\x01\x04self\x06\0\x0b\x04\
+\0\x18[method]file.read-binary\x01\x0c\x01ks\x01@\x02\x06prompts\x0ddefault-valu\
+e\x0d\0s\x04\0\x06prompt\x01\x0e\x01k\x7f\x01@\x02\x06prompts\x0ddefault-value\x0f\
+\0\x7f\x04\0\x07confirm\x01\x10\x01ps\x01k}\x01@\x03\x06prompts\x05items\x11\x0d\
+default-index\x12\0}\x04\0\x06select\x01\x13\x03\x01\x1efermyon:spin-template/ui\
+@0.0.2\x05\x03\x01B\x17\x01py\x01o\x02sy\x01p\x01\x01q\x07\x03nil\0\0\x04bool\x01\
+\x7f\0\x03int\x01x\0\x05float\x01u\0\x06string\x01s\0\x05array\x01\0\0\x06object\
+\x01\x02\0\x04\0\x0avalue-node\x03\0\x03\x01p\x04\x04\0\x05value\x03\0\x05\x01r\x03\
+\x04names\x0bdescriptions\x08required\x7f\x04\0\x09parameter\x03\0\x07\x01p\x08\x01\
+r\x04\x04names\x0bdescriptions\x0apositional\x09\x07keyword\x09\x04\0\x12filter-\
+description\x03\0\x0a\x01p\x06\x01o\x02s\x06\x01p\x0d\x01r\x02\x0apositional\x0c\
+\x07keyword\x0e\x04\0\x09arguments\x03\0\x0f\x01p\x0b\x01@\0\0\x11\x04\0\x08desc\
+ribe\x01\x12\x01j\x01\x06\x01s\x01@\x03\x04names\x05input\x06\x04args\x10\0\x13\x04\
+\0\x04exec\x01\x14\x04\x01(fermyon:spin-template-filter/types@0.0.2\x05\x04\x02\x03\
+\0\x01\x11execution-context\x02\x03\0\x01\x0brun-options\x01B\x1b\x02\x03\x02\x01\
+\x02\x04\0\x05error\x03\0\0\x02\x03\x02\x01\x05\x04\0\x11execution-context\x03\0\
+\x02\x02\x03\x02\x01\x06\x04\0\x0brun-options\x03\0\x04\x04\0\x04edit\x03\x01\x01\
+i\x03\x01o\x02s\x07\x01o\x02ss\x01o\x03ss\x07\x01p}\x01o\x02s\x0b\x01i\x06\x01o\x02\
+s\x0d\x01o\x03s\x0d\x07\x01q\x0a\x15copy-file-substituted\x01s\0\x18copy-file-su\
+bstituted-in\x01\x08\0\x18copy-file-to-substituted\x01\x09\0\x1bcopy-file-to-sub\
+stituted-in\x01\x0a\0\x10copy-file-to-raw\x01\x09\0\x0awrite-file\x01\x09\0\x11w\
+rite-file-binary\x01\x0c\0\x0acreate-dir\x01s\0\x09edit-file\x01\x0e\0\x0cedit-f\
+ile-in\x01\x0f\0\x04\0\x06action\x03\0\x10\x01h\x06\x01h\x03\x01j\x01s\x01\x01\x01\
+@\x03\x04self\x12\x04texts\x07context\x13\0\x14\x04\0\x12[method]edit.apply\x01\x15\
+\x01p\x11\x01j\x01\x16\x01\x01\x01@\x02\x07context\x13\x07options\x05\0\x17\x04\0\
+\x03run\x01\x18\x04\x01$fermyon:spin-template/template@0.0.2\x05\x07\x04\x015fer\
+myon:spin-template/run-template-with-filters@0.0.2\x04\0\x0b\x1f\x01\0\x19run-te\
+mplate-with-filters\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-compo\
+nent\x070.208.1\x10wit-bindgen-rust\x060.25.0";
 
 #[inline(never)]
 #[doc(hidden)]
@@ -71,9 +71,13 @@ impl Guest for Component {
         context.set_variable("project-description", &desc);
         context.set_variable("http-path", &http_path);
 
-        let fruit_info = context.evaluate_template("om nom nom {{ fruit }}")?;
+        let fruit_info = context.evaluate_template("om nom nom {{ fruit }} {{ fruit | fruit_emoji }}")?;
         actions.push(Action::WriteFile((dest, fruit_info)));
 
+        let cherry = context.fork();
+        cherry.set_variable("fruit", "Cherry");
+        actions.push(Action::CopyFileToSubstitutedIn(("fruit.txt".to_owned(), "cherry.txt".to_owned(), cherry)));
+
         actions.push(Action::WriteFileBinary(("binned.bin".to_owned(), vec![1,2,3,4])));
 
         let edit: Self::Edit = if src == 1 {
@@ -94,6 +98,40 @@ impl Guest for Component {
     }
 }
 
+// The template can provide filters for its own content files, instead of needing
+// a separate filter component
+impl bindings::exports::fermyon::spin_template_filter::types::Guest for Component {
+    fn describe() -> Vec<bindings::exports::fermyon::spin_template_filter::types::FilterDescription> {
+        vec![bindings::exports::fermyon::spin_template_filter::types::FilterDescription {
+            name: "fruit_emoji".to_owned(),
+            description: "Turn the name of a fruit into its emoji. With no input, uses the `fruit` variable.".to_owned(),
+            positional: vec![],
+            keyword: vec![],
+        }]
+    }
+
+    fn exec(name: String, input: bindings::exports::fermyon::spin_template_filter::types::Value, _args: bindings::exports::fermyon::spin_template_filter::types::Arguments) -> Result<bindings::exports::fermyon::spin_template_filter::types::Value, String> {
+        use bindings::exports::fermyon::spin_template_filter::types::ValueNode;
+
+        if name != "fruit_emoji" {
+            return Err(format!("no filter called {name}"));
+        }
+        let fruit = match input.first() {
+            Some(ValueNode::String(fruit)) => fruit.to_owned(),
+            Some(ValueNode::Nil) => bindings::fermyon::spin_template_filter::variables::get("fruit")
+                .ok_or_else(|| "fruit_emoji has no input and there's no fruit variable".to_owned())?,
+            _ => return Err("fruit_emoji only works on strings".to_owned()),
+        };
+        let emoji = match fruit.to_lowercase().as_str() {
+            "apple" => "🍎",
+            "banana" => "🍌",
+            "cherry" => "🍒",
+            _ => &fruit,
+        };
+        Ok(vec![ValueNode::String(emoji.to_owned())])
+    }
+}
+
 impl bindings::exports::fermyon::spin_template::template::GuestEdit for Box<dyn bindings::exports::fermyon::spin_template::template::GuestEdit> {
     fn apply(&self, text: String, context: &bindings::exports::fermyon::spin_template::template::ExecutionContext) -> Result<String, TemplateError> {
         self.as_ref().apply(text, context)